@@ -3,6 +3,8 @@ import {Keypair, Connection, SYSVAR_RENT_PUBKEY, SystemProgram} from '@solana/we
 import { AnchorProvider, Wallet } from '@coral-xyz/anchor';
 import { getVoucherExchangeProgram, VOUCHER_EXCHANGE_PROGRAM_ID } from "@project/voucher-exchange";
 import { getExchangePDA } from '../service/voucher-exchange-program/pda';
+import { EXCHANGE_NAMESPACE } from '../utils/constants';
+import BN from 'bn.js';
 import {TOKEN_PROGRAM_ID} from "@solana/spl-token"
 import fs from 'fs';
 import path from 'path';
@@ -40,12 +42,22 @@ async function main() {
         const program = getVoucherExchangeProgram(provider);
 
         // 5. Get the exchange PDA
-        const [exchangePDA] = await getExchangePDA(VOUCHER_EXCHANGE_PROGRAM_ID);
+        const [exchangePDA] = await getExchangePDA(EXCHANGE_NAMESPACE, VOUCHER_EXCHANGE_PROGRAM_ID);
         console.log(`Exchange PDA: ${exchangePDA.toString()}`);
 
-        // 6. Initialize the exchange
+        // 6. Initialize the exchange, fees default to zero and the delay to one day
+        const makerFeeBasisPoints = Number(process.env.MAKER_FEE_BASIS_POINTS || 0);
+        const takerFeeBasisPoints = Number(process.env.TAKER_FEE_BASIS_POINTS || 0);
+        const feeUpdateDelay = new BN(process.env.FEE_UPDATE_DELAY || 24 * 60 * 60);
+
         const tx = await program.methods
-            .initializeExchange()
+            .initializeExchange(
+                EXCHANGE_NAMESPACE,
+                makerFeeBasisPoints,
+                takerFeeBasisPoints,
+                keypair.publicKey,
+                feeUpdateDelay
+            )
             .accounts({
                 authority: keypair.publicKey,
                 tokenProgram: TOKEN_PROGRAM_ID
@@ -61,6 +73,7 @@ async function main() {
             const exchangeAccount = await program.account.voucherExchange.fetch(exchangePDA);
             console.log('Exchange initialized successfully!');
             console.log('Exchange data:', {
+                namespace: exchangeAccount.namespace,
                 authority: exchangeAccount.authority.toString(),
                 totalListings: exchangeAccount.totalListings.toString(),
                 totalBids: exchangeAccount.totalBids.toString(),
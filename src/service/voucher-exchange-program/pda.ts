@@ -8,27 +8,50 @@ export const VOUCHER_LISTING_SEED = "voucher_listing";
 export const VOUCHER_BID_SEED = "voucher_bid";
 export const ESCROW_SEED = "escrow";
 export const VOUCHER_STATE_SEED = "voucher_state";
+export const CLOSED_NAMESPACE_SEED = "closed_namespace";
 
 /**
  * Get the Voucher Exchange PDA
+ * @param namespace The brand namespace the exchange was initialized with
  * @param programId The Voucher Exchange program ID
  * @returns [exchangePDA, exchangeBump]
  */
-export async function getExchangePDA(programId: PublicKey): Promise<[PublicKey, number]> {
+export async function getExchangePDA(
+    namespace: string,
+    programId: PublicKey
+): Promise<[PublicKey, number]> {
+    return await PublicKey.findProgramAddress(
+        [Buffer.from(VOUCHER_EXCHANGE_SEED), Buffer.from(namespace)],
+        programId
+    );
+}
+
+/**
+ * Get the tombstone PDA a closed exchange leaves for its namespace
+ * @param namespace The brand namespace of the exchange
+ * @param programId The Voucher Exchange program ID
+ * @returns [closedNamespacePDA, closedNamespaceBump]
+ */
+export async function getClosedNamespacePDA(
+    namespace: string,
+    programId: PublicKey
+): Promise<[PublicKey, number]> {
     return await PublicKey.findProgramAddress(
-        [Buffer.from(VOUCHER_EXCHANGE_SEED)],
+        [Buffer.from(CLOSED_NAMESPACE_SEED), Buffer.from(namespace)],
         programId
     );
 }
 
 /**
  * Get the Voucher Listing PDA
+ * @param exchange The exchange the listing belongs to
  * @param owner The owner's public key
  * @param nftMint The NFT mint address
  * @param programId The Voucher Exchange program ID
  * @returns [listingPDA, listingBump]
  */
 export async function getListingPDA(
+    exchange: PublicKey,
     owner: PublicKey,
     nftMint: PublicKey,
     programId: PublicKey
@@ -36,6 +59,7 @@ export async function getListingPDA(
     return await PublicKey.findProgramAddress(
         [
             Buffer.from(VOUCHER_LISTING_SEED),
+            exchange.toBuffer(),
             owner.toBuffer(),
             nftMint.toBuffer(),
         ],
@@ -45,12 +69,14 @@ export async function getListingPDA(
 
 /**
  * Get the Voucher Bid PDA
+ * @param exchange The exchange the bid belongs to
  * @param bidder The bidder's public key
  * @param nftMint The NFT mint address
  * @param programId The Voucher Exchange program ID
  * @returns [bidPDA, bidBump]
  */
 export async function getBidPDA(
+    exchange: PublicKey,
     bidder: PublicKey,
     nftMint: PublicKey,
     programId: PublicKey
@@ -58,6 +84,7 @@ export async function getBidPDA(
     return await PublicKey.findProgramAddress(
         [
             Buffer.from(VOUCHER_BID_SEED),
+            exchange.toBuffer(),
             bidder.toBuffer(),
             nftMint.toBuffer(),
         ],
@@ -68,17 +95,20 @@ export async function getBidPDA(
 /**
  * Get the Escrow PDA for an NFT (for listings)
  * This is used when creating a listing to hold the NFT
+ * @param exchange The exchange the listing belongs to
  * @param nftMint The NFT mint address
  * @param programId The Voucher Exchange program ID
  * @returns [escrowNftPDA, escrowNftBump]
  */
 export async function getEscrowNftPDA(
+    exchange: PublicKey,
     nftMint: PublicKey,
     programId: PublicKey
 ): Promise<[PublicKey, number]> {
     return await PublicKey.findProgramAddress(
         [
             Buffer.from(ESCROW_SEED),
+            exchange.toBuffer(),
             nftMint.toBuffer(),
         ],
         programId
@@ -88,12 +118,14 @@ export async function getEscrowNftPDA(
 /**
  * Get the Escrow PDA for a bid (for bid payments)
  * This is used when creating a bid to hold the payment tokens
+ * @param exchange The exchange the bid belongs to
  * @param bidder The bidder's public key
  * @param nftMint The NFT mint address
  * @param programId The Voucher Exchange program ID
  * @returns [escrowBidPDA, escrowBidBump]
  */
 export async function getEscrowBidPDA(
+    exchange: PublicKey,
     bidder: PublicKey,
     nftMint: PublicKey,
     programId: PublicKey
@@ -101,6 +133,7 @@ export async function getEscrowBidPDA(
     return await PublicKey.findProgramAddress(
         [
             Buffer.from(ESCROW_SEED),
+            exchange.toBuffer(),
             bidder.toBuffer(),
             nftMint.toBuffer(),
         ],
@@ -110,23 +143,25 @@ export async function getEscrowBidPDA(
 
 /**
  * Get the Escrow PDA - determines correct escrow based on parameters
+ * @param exchange The exchange the escrow belongs to
  * @param first The first identifier (either bidder PublicKey or nftMint)
  * @param second Optional second identifier (nftMint if first is bidder)
  * @param programId The Voucher Exchange program ID
  * @returns [escrowPDA, escrowBump]
  */
 export async function getEscrowPDA(
+    exchange: PublicKey,
     first: PublicKey,
     second?: PublicKey,
     programId?: PublicKey
 ): Promise<[PublicKey, number]> {
     // If only one parameter is provided, assume it's an NFT mint for listings
     if (!second || !programId) {
-        return getEscrowNftPDA(first, second! || programId);
+        return getEscrowNftPDA(exchange, first, second! || programId);
     }
 
     // If both parameters are provided, use them for bid escrow
-    return getEscrowBidPDA(first, second, programId);
+    return getEscrowBidPDA(exchange, first, second, programId);
 }
 
 /**
@@ -147,3 +182,61 @@ export async function getNftStatePDA(
         programId
     );
 }
+
+/**
+ * Get the PDAs of a listing created before exchanges were namespaced
+ * Only used to return these listings with returnLegacyListing
+ * @param owner The owner's public key
+ * @param nftMint The NFT mint address
+ * @param programId The Voucher Exchange program ID
+ * @returns [[listingPDA, listingBump], [escrowNftPDA, escrowNftBump]]
+ */
+export async function getLegacyListingPDAs(
+    owner: PublicKey,
+    nftMint: PublicKey,
+    programId: PublicKey
+): Promise<[[PublicKey, number], [PublicKey, number]]> {
+    return [
+        await PublicKey.findProgramAddress(
+            [
+                Buffer.from(VOUCHER_LISTING_SEED),
+                owner.toBuffer(),
+                nftMint.toBuffer(),
+            ],
+            programId
+        ),
+        await PublicKey.findProgramAddress(
+            [Buffer.from(ESCROW_SEED), nftMint.toBuffer()],
+            programId
+        ),
+    ];
+}
+
+/**
+ * Get the PDAs of a bid created before exchanges were namespaced
+ * Only used to return these bids with returnLegacyBid
+ * @param bidder The bidder's public key
+ * @param nftMint The NFT mint address
+ * @param programId The Voucher Exchange program ID
+ * @returns [[bidPDA, bidBump], [escrowBidPDA, escrowBidBump]]
+ */
+export async function getLegacyBidPDAs(
+    bidder: PublicKey,
+    nftMint: PublicKey,
+    programId: PublicKey
+): Promise<[[PublicKey, number], [PublicKey, number]]> {
+    return [
+        await PublicKey.findProgramAddress(
+            [
+                Buffer.from(VOUCHER_BID_SEED),
+                bidder.toBuffer(),
+                nftMint.toBuffer(),
+            ],
+            programId
+        ),
+        await PublicKey.findProgramAddress(
+            [Buffer.from(ESCROW_SEED), bidder.toBuffer(), nftMint.toBuffer()],
+            programId
+        ),
+    ];
+}
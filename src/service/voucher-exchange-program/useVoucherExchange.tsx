@@ -6,10 +6,13 @@ import {
 } from "@project/voucher-exchange";
 import {
   getExchangePDA,
+  getClosedNamespacePDA,
   getListingPDA,
   getBidPDA,
   getEscrowNftPDA,
   getEscrowBidPDA,
+  getLegacyListingPDAs,
+  getLegacyBidPDAs,
 } from "./pda";
 import {
   PublicKey,
@@ -33,17 +36,24 @@ import toast from "react-hot-toast";
 import { useCluster } from "../cluster/use-cluster-provider";
 import { useAnchorProvider } from "../solana/use-solana-provider";
 import bs58 from "bs58";
-import { connection } from "@/utils/constants";
+import { connection, EXCHANGE_NAMESPACE } from "@/utils/constants";
 
 // Types and Interfaces
 export interface VoucherExchange {
+  namespace: string;
   authority: PublicKey;
   totalListings: BN;
   totalBids: BN;
   bump: number;
 }
 
+export interface PayoutSplit {
+  recipient: PublicKey;
+  basisPoints: number;
+}
+
 export interface VoucherListing {
+  exchange: PublicKey;
   owner: PublicKey;
   nftMint: PublicKey;
   nftAccount: PublicKey;
@@ -51,10 +61,17 @@ export interface VoucherListing {
   paymentMint: PublicKey;
   active: boolean;
   bump: number;
-  // Removed escrow_bump field
+  payoutSplits: PayoutSplit[];
+  version: number;
+  expiresAt: BN;
+  // Only buyer allowed to fulfill the listing, null for a public listing
+  reservedBuyer: PublicKey | null;
+  dutchAuction: object | null;
+  englishAuction: object | null;
 }
 
 export interface VoucherBid {
+  exchange: PublicKey;
   bidder: PublicKey;
   nftMint: PublicKey;
   price: BN;
@@ -80,6 +97,37 @@ export enum TransactionErrorCode {
   // Add other codes as needed
 }
 
+// Byte offsets of the fixed-size fields used in memcmp filters, following the field order
+// of VoucherListing and VoucherBid in programs/voucher_exchange/src/state.rs. A listing's
+// payout_splits Vec is variable length, so the fields after it (version, expires_at,
+// reserved_buyer and the auctions) have no fixed offset and are filtered client-side.
+const DISCRIMINATOR_SIZE = 8;
+const PUBKEY_SIZE = 32;
+const U64_SIZE = 8;
+
+const LISTING_OFFSETS = (() => {
+  const exchange = DISCRIMINATOR_SIZE;
+  const owner = exchange + PUBKEY_SIZE;
+  const nftMint = owner + PUBKEY_SIZE;
+  const nftAccount = nftMint + PUBKEY_SIZE;
+  const price = nftAccount + PUBKEY_SIZE;
+  const paymentMint = price + U64_SIZE;
+  const active = paymentMint + PUBKEY_SIZE;
+  return { exchange, owner, nftMint, nftAccount, price, paymentMint, active };
+})();
+
+const BID_OFFSETS = (() => {
+  const exchange = DISCRIMINATOR_SIZE;
+  const bidder = exchange + PUBKEY_SIZE;
+  const nftMint = bidder + PUBKEY_SIZE;
+  const price = nftMint + PUBKEY_SIZE;
+  const paymentMint = price + U64_SIZE;
+  const escrowAccount = paymentMint + PUBKEY_SIZE;
+  const active = escrowAccount + PUBKEY_SIZE;
+  const requiresRefund = active + 1;
+  return { exchange, bidder, nftMint, price, paymentMint, escrowAccount, active, requiresRefund };
+})();
+
 // Default query options
 const DEFAULT_QUERY_OPTIONS = {
   staleTime: 60000, // 1 minute
@@ -109,7 +157,10 @@ export function useVoucherExchange() {
   const getExchangeAccount = useQuery({
     queryKey: ["get-exchange-account", { cluster }],
     queryFn: async () => {
-      const [exchangePDA] = await getExchangePDA(VOUCHER_EXCHANGE_PROGRAM_ID);
+      const [exchangePDA] = await getExchangePDA(
+        EXCHANGE_NAMESPACE,
+        VOUCHER_EXCHANGE_PROGRAM_ID,
+      );
       return program.account.voucherExchange.fetch(exchangePDA);
     },
     ...DEFAULT_QUERY_OPTIONS,
@@ -124,7 +175,12 @@ export function useVoucherExchange() {
         { owner: owner?.toBase58(), nftMint: nftMint?.toBase58(), cluster },
       ],
       queryFn: async () => {
+        const [exchangePDA] = await getExchangePDA(
+          EXCHANGE_NAMESPACE,
+          VOUCHER_EXCHANGE_PROGRAM_ID,
+        );
         const [listingPDA] = await getListingPDA(
+          exchangePDA,
           owner,
           nftMint,
           VOUCHER_EXCHANGE_PROGRAM_ID,
@@ -144,7 +200,12 @@ export function useVoucherExchange() {
         { bidder: bidder?.toBase58(), nftMint: nftMint?.toBase58(), cluster },
       ],
       queryFn: async () => {
+        const [exchangePDA] = await getExchangePDA(
+          EXCHANGE_NAMESPACE,
+          VOUCHER_EXCHANGE_PROGRAM_ID,
+        );
         const [bidPDA] = await getBidPDA(
+          exchangePDA,
           bidder,
           nftMint,
           VOUCHER_EXCHANGE_PROGRAM_ID,
@@ -161,21 +222,33 @@ export function useVoucherExchange() {
   // Fetch active listings
   const fetchActiveListings = async () => {
     try {
+      const [exchangePDA] = await getExchangePDA(
+        EXCHANGE_NAMESPACE,
+        VOUCHER_EXCHANGE_PROGRAM_ID,
+      );
       const listings = await program.account.voucherListing.all([
         {
           memcmp: {
-            offset: 8 + 32 + 32 + 32 + 8 + 32, // Skip to active field
+            offset: LISTING_OFFSETS.exchange,
+            bytes: exchangePDA.toBase58(),
+          },
+        },
+        {
+          memcmp: {
+            offset: LISTING_OFFSETS.active,
             bytes: bs58.encode(Buffer.from([1])),
           },
         },
       ]);
 
-      return listings.map(
-        (listing: { publicKey: PublicKey; account: any }) => ({
+      // Private listings are reserved for one buyer and left out of public views.
+      // reserved_buyer follows the payout splits and cannot be matched with memcmp.
+      return listings
+        .filter((listing: { account: any }) => !listing.account.reservedBuyer)
+        .map((listing: { publicKey: PublicKey; account: any }) => ({
           address: listing.publicKey,
           data: listing.account as VoucherListing,
-        }),
-      );
+        }));
     } catch (error) {
       console.error("Error fetching active listings:", error);
       throw error;
@@ -187,36 +260,97 @@ export function useVoucherExchange() {
     if (!nftMint) return [];
 
     try {
+      const [exchangePDA] = await getExchangePDA(
+        EXCHANGE_NAMESPACE,
+        VOUCHER_EXCHANGE_PROGRAM_ID,
+      );
       const listings = await program.account.voucherListing.all([
         {
           memcmp: {
-            offset: 8 + 32, // Skip discriminator and owner
+            offset: LISTING_OFFSETS.exchange,
+            bytes: exchangePDA.toBase58(),
+          },
+        },
+        {
+          memcmp: {
+            offset: LISTING_OFFSETS.nftMint,
             bytes: nftMint.toBase58(),
           },
         },
       ]);
 
-      return listings.map(
-        (listing: { publicKey: PublicKey; account: any }) => ({
+      // Private listings are reserved for one buyer and left out of public views.
+      // reserved_buyer follows the payout splits and cannot be matched with memcmp.
+      return listings
+        .filter((listing: { account: any }) => !listing.account.reservedBuyer)
+        .map((listing: { publicKey: PublicKey; account: any }) => ({
           address: listing.publicKey,
           data: listing.account as VoucherListing,
-        }),
-      );
+        }));
     } catch (error) {
       console.error("Error fetching listings by NFT mint:", error);
       throw error;
     }
   };
 
+  // Fetch active listings reserved for a buyer, which public views leave out
+  const fetchListingsReservedFor = async (buyer: PublicKey) => {
+    if (!buyer) return [];
+
+    try {
+      const [exchangePDA] = await getExchangePDA(
+        EXCHANGE_NAMESPACE,
+        VOUCHER_EXCHANGE_PROGRAM_ID,
+      );
+      const listings = await program.account.voucherListing.all([
+        {
+          memcmp: {
+            offset: LISTING_OFFSETS.exchange,
+            bytes: exchangePDA.toBase58(),
+          },
+        },
+        {
+          memcmp: {
+            offset: LISTING_OFFSETS.active,
+            bytes: bs58.encode(Buffer.from([1])),
+          },
+        },
+      ]);
+
+      // reserved_buyer follows the payout splits and cannot be matched with memcmp
+      return listings
+        .filter((listing: { account: any }) =>
+          listing.account.reservedBuyer?.equals(buyer),
+        )
+        .map((listing: { publicKey: PublicKey; account: any }) => ({
+          address: listing.publicKey,
+          data: listing.account as VoucherListing,
+        }));
+    } catch (error) {
+      console.error("Error fetching reserved listings:", error);
+      throw error;
+    }
+  };
+
   // Fetch listings by owner
   const fetchListingsByOwner = async (owner: PublicKey) => {
     if (!owner) return [];
 
     try {
+      const [exchangePDA] = await getExchangePDA(
+        EXCHANGE_NAMESPACE,
+        VOUCHER_EXCHANGE_PROGRAM_ID,
+      );
       const listings = await program.account.voucherListing.all([
         {
           memcmp: {
-            offset: 8, // Skip discriminator
+            offset: LISTING_OFFSETS.exchange,
+            bytes: exchangePDA.toBase58(),
+          },
+        },
+        {
+          memcmp: {
+            offset: LISTING_OFFSETS.owner,
             bytes: owner.toBase58(),
           },
         },
@@ -237,10 +371,20 @@ export function useVoucherExchange() {
   // Fetch active bids
   const fetchActiveBids = async () => {
     try {
+      const [exchangePDA] = await getExchangePDA(
+        EXCHANGE_NAMESPACE,
+        VOUCHER_EXCHANGE_PROGRAM_ID,
+      );
       const bids = await program.account.voucherBid.all([
         {
           memcmp: {
-            offset: 8 + 32 + 32 + 8 + 32 + 32, // Skip to active field
+            offset: BID_OFFSETS.exchange,
+            bytes: exchangePDA.toBase58(),
+          },
+        },
+        {
+          memcmp: {
+            offset: BID_OFFSETS.active,
             bytes: bs58.encode(Buffer.from([1])), // 1 = true
           },
         },
@@ -261,10 +405,20 @@ export function useVoucherExchange() {
     if (!nftMint) return [];
 
     try {
+      const [exchangePDA] = await getExchangePDA(
+        EXCHANGE_NAMESPACE,
+        VOUCHER_EXCHANGE_PROGRAM_ID,
+      );
       const bids = await program.account.voucherBid.all([
         {
           memcmp: {
-            offset: 8 + 32, // Skip discriminator and bidder
+            offset: BID_OFFSETS.exchange,
+            bytes: exchangePDA.toBase58(),
+          },
+        },
+        {
+          memcmp: {
+            offset: BID_OFFSETS.nftMint,
             bytes: nftMint.toBase58(),
           },
         },
@@ -285,10 +439,20 @@ export function useVoucherExchange() {
     if (!bidder) return [];
 
     try {
+      const [exchangePDA] = await getExchangePDA(
+        EXCHANGE_NAMESPACE,
+        VOUCHER_EXCHANGE_PROGRAM_ID,
+      );
       const bids = await program.account.voucherBid.all([
         {
           memcmp: {
-            offset: 8, // Skip discriminator
+            offset: BID_OFFSETS.exchange,
+            bytes: exchangePDA.toBase58(),
+          },
+        },
+        {
+          memcmp: {
+            offset: BID_OFFSETS.bidder,
             bytes: bidder.toBase58(),
           },
         },
@@ -307,10 +471,20 @@ export function useVoucherExchange() {
   // Fetch bids requiring refund
   const fetchBidsRequiringRefund = async () => {
     try {
+      const [exchangePDA] = await getExchangePDA(
+        EXCHANGE_NAMESPACE,
+        VOUCHER_EXCHANGE_PROGRAM_ID,
+      );
       const bids = await program.account.voucherBid.all([
         {
           memcmp: {
-            offset: 8 + 32 + 32 + 8 + 32 + 32 + 1, // Skip to requiresRefund field
+            offset: BID_OFFSETS.exchange,
+            bytes: exchangePDA.toBase58(),
+          },
+        },
+        {
+          memcmp: {
+            offset: BID_OFFSETS.requiresRefund,
             bytes: bs58.encode(Buffer.from([1])), // 1 = true
           },
         },
@@ -331,17 +505,41 @@ export function useVoucherExchange() {
   // Initialize Exchange Mutation
   const initializeExchange = useMutation({
     mutationKey: ["initialize-exchange", { cluster }],
-    mutationFn: async () => {
+    mutationFn: async ({
+      makerFeeBasisPoints,
+      takerFeeBasisPoints,
+      feeRecipient,
+      feeUpdateDelay,
+    }: {
+      makerFeeBasisPoints: number;
+      takerFeeBasisPoints: number;
+      feeRecipient: PublicKey;
+      feeUpdateDelay: BN;
+    }) => {
       if (!publicKey) throw new Error("Wallet not connected");
 
       try {
         console.log("Initializing exchange");
-        const [exchangePDA] = await getExchangePDA(VOUCHER_EXCHANGE_PROGRAM_ID);
+        const [exchangePDA] = await getExchangePDA(
+          EXCHANGE_NAMESPACE,
+          VOUCHER_EXCHANGE_PROGRAM_ID,
+        );
+        const [closedNamespacePDA] = await getClosedNamespacePDA(
+          EXCHANGE_NAMESPACE,
+          VOUCHER_EXCHANGE_PROGRAM_ID,
+        );
 
         const tx = await program.methods
-          .initializeExchange()
+          .initializeExchange(
+            EXCHANGE_NAMESPACE,
+            makerFeeBasisPoints,
+            takerFeeBasisPoints,
+            feeRecipient,
+            feeUpdateDelay,
+          )
           .accounts({
             exchange: exchangePDA,
+            closedNamespace: closedNamespacePDA,
             authority: publicKey,
             systemProgram: SystemProgram.programId,
             rent: SYSVAR_RENT_PUBKEY,
@@ -411,8 +609,12 @@ export function useVoucherExchange() {
       if (!publicKey) throw new Error("Wallet not connected");
 
       try {
-        const [exchangePDA] = await getExchangePDA(VOUCHER_EXCHANGE_PROGRAM_ID);
+        const [exchangePDA] = await getExchangePDA(
+          EXCHANGE_NAMESPACE,
+          VOUCHER_EXCHANGE_PROGRAM_ID,
+        );
         const [listingPDA] = await getListingPDA(
+          exchangePDA,
           publicKey,
           nftMint,
           VOUCHER_EXCHANGE_PROGRAM_ID,
@@ -420,6 +622,7 @@ export function useVoucherExchange() {
 
         // Get escrow NFT account PDA - using the NFT-only variant
         const [escrowNftPDA] = await getEscrowNftPDA(
+          exchangePDA,
           nftMint,
           VOUCHER_EXCHANGE_PROGRAM_ID,
         );
@@ -568,14 +771,19 @@ export function useVoucherExchange() {
           isToken2022 ? TOKEN_2022_PROGRAM_ID : TOKEN_PROGRAM_ID,
         );
 
-        const [exchangePDA] = await getExchangePDA(VOUCHER_EXCHANGE_PROGRAM_ID);
+        const [exchangePDA] = await getExchangePDA(
+          EXCHANGE_NAMESPACE,
+          VOUCHER_EXCHANGE_PROGRAM_ID,
+        );
         const [bidPDA] = await getBidPDA(
+          exchangePDA,
           publicKey,
           nftMint,
           VOUCHER_EXCHANGE_PROGRAM_ID,
         );
 
         const [escrowBidPDA, escrowBump] = await getEscrowBidPDA(
+          exchangePDA,
           publicKey,
           nftMint,
           VOUCHER_EXCHANGE_PROGRAM_ID,
@@ -696,13 +904,18 @@ export function useVoucherExchange() {
         );
 
         // Get PDAs
-        const [exchangePDA] = await getExchangePDA(VOUCHER_EXCHANGE_PROGRAM_ID);
+        const [exchangePDA] = await getExchangePDA(
+          EXCHANGE_NAMESPACE,
+          VOUCHER_EXCHANGE_PROGRAM_ID,
+        );
         const [bidPDA] = await getBidPDA(
+          exchangePDA,
           bidder,
           nftMint,
           VOUCHER_EXCHANGE_PROGRAM_ID,
         );
         const [listingPDA] = await getListingPDA(
+          exchangePDA,
           publicKey,
           nftMint,
           VOUCHER_EXCHANGE_PROGRAM_ID,
@@ -710,10 +923,12 @@ export function useVoucherExchange() {
 
         // Get escrow accounts
         const [escrowNftPDA] = await getEscrowNftPDA(
+          exchangePDA,
           nftMint,
           VOUCHER_EXCHANGE_PROGRAM_ID,
         );
         const [escrowBidPDA] = await getEscrowBidPDA(
+          exchangePDA,
           bidder,
           nftMint,
           VOUCHER_EXCHANGE_PROGRAM_ID,
@@ -919,13 +1134,18 @@ export function useVoucherExchange() {
           TOKEN_PROGRAM_ID,
         );
 
-        const [exchangePDA] = await getExchangePDA(VOUCHER_EXCHANGE_PROGRAM_ID);
+        const [exchangePDA] = await getExchangePDA(
+          EXCHANGE_NAMESPACE,
+          VOUCHER_EXCHANGE_PROGRAM_ID,
+        );
         const [listingPDA] = await getListingPDA(
+          exchangePDA,
           owner,
           nftMint,
           VOUCHER_EXCHANGE_PROGRAM_ID,
         );
         const [escrowNftPDA] = await getEscrowNftPDA(
+          exchangePDA,
           nftMint,
           VOUCHER_EXCHANGE_PROGRAM_ID,
         );
@@ -1071,8 +1291,12 @@ export function useVoucherExchange() {
 
       try {
 
-        const [exchangePDA] = await getExchangePDA(VOUCHER_EXCHANGE_PROGRAM_ID);
+        const [exchangePDA] = await getExchangePDA(
+          EXCHANGE_NAMESPACE,
+          VOUCHER_EXCHANGE_PROGRAM_ID,
+        );
         const [listingPDA] = await getListingPDA(
+          exchangePDA,
           publicKey,
           nftMint,
           VOUCHER_EXCHANGE_PROGRAM_ID,
@@ -1086,6 +1310,7 @@ export function useVoucherExchange() {
 
         // Get escrow NFT account PDA - using the NFT-only variant
         const [escrowNftPDA] = await getEscrowNftPDA(
+          exchangePDA,
           nftMint,
           VOUCHER_EXCHANGE_PROGRAM_ID,
         );
@@ -1167,8 +1392,12 @@ export function useVoucherExchange() {
         const isToken2022 =
             paymentMintInfo?.owner.equals(TOKEN_2022_PROGRAM_ID) ?? true;
 
-        const [exchangePDA] = await getExchangePDA(VOUCHER_EXCHANGE_PROGRAM_ID);
+        const [exchangePDA] = await getExchangePDA(
+          EXCHANGE_NAMESPACE,
+          VOUCHER_EXCHANGE_PROGRAM_ID,
+        );
         const [bidPDA] = await getBidPDA(
+          exchangePDA,
           publicKey,
           nftMint,
           VOUCHER_EXCHANGE_PROGRAM_ID,
@@ -1183,6 +1412,7 @@ export function useVoucherExchange() {
 
         // Using bidder-specific escrow for bid cancellation
         const [escrowBidPDA] = await getEscrowBidPDA(
+          exchangePDA,
           publicKey,
           nftMint,
           VOUCHER_EXCHANGE_PROGRAM_ID,
@@ -1269,8 +1499,12 @@ export function useVoucherExchange() {
           bidder.toString(),
         );
 
-        const [exchangePDA] = await getExchangePDA(VOUCHER_EXCHANGE_PROGRAM_ID);
+        const [exchangePDA] = await getExchangePDA(
+          EXCHANGE_NAMESPACE,
+          VOUCHER_EXCHANGE_PROGRAM_ID,
+        );
         const [bidPDA] = await getBidPDA(
+          exchangePDA,
           bidder,
           nftMint,
           VOUCHER_EXCHANGE_PROGRAM_ID,
@@ -1349,8 +1583,12 @@ export function useVoucherExchange() {
       if (!publicKey) throw new Error("Wallet not connected");
 
       try {
-        const [exchangePDA] = await getExchangePDA(VOUCHER_EXCHANGE_PROGRAM_ID);
+        const [exchangePDA] = await getExchangePDA(
+          EXCHANGE_NAMESPACE,
+          VOUCHER_EXCHANGE_PROGRAM_ID,
+        );
         const [bidPDA] = await getBidPDA(
+          exchangePDA,
           publicKey,
           nftMint,
           VOUCHER_EXCHANGE_PROGRAM_ID,
@@ -1358,6 +1596,7 @@ export function useVoucherExchange() {
 
         // Using bidder-specific escrow for bid refunds
         const [escrowBidPDA] = await getEscrowBidPDA(
+          exchangePDA,
           publicKey,
           nftMint,
           VOUCHER_EXCHANGE_PROGRAM_ID,
@@ -1424,6 +1663,173 @@ export function useVoucherExchange() {
     },
   });
 
+  // Return Legacy Listing Mutation
+  // Listings created before exchanges were namespaced sit at the old PDAs and can only be returned
+  const returnLegacyListing = useMutation({
+    mutationKey: ["return-legacy-listing", { cluster }],
+    mutationFn: async ({ nftMint }: { nftMint: PublicKey }) => {
+      if (!publicKey) throw new Error("Wallet not connected");
+
+      try {
+        const [[listingPDA], [escrowNftPDA]] = await getLegacyListingPDAs(
+          publicKey,
+          nftMint,
+          VOUCHER_EXCHANGE_PROGRAM_ID,
+        );
+
+        const ownerNftAccount = await getAssociatedTokenAddress(
+          nftMint,
+          publicKey,
+        );
+
+        const tx = await program.methods
+          .returnLegacyListing()
+          .accounts({
+            listing: listingPDA,
+            owner: publicKey,
+            ownerNftAccount: ownerNftAccount,
+            nftMint: nftMint,
+            escrowNftAccount: escrowNftPDA,
+            tokenProgram: TOKEN_PROGRAM_ID,
+          })
+          .transaction();
+
+        // Send and sign transaction
+        const { blockhash, lastValidBlockHeight } =
+          await connection.getLatestBlockhash("confirmed");
+        tx.recentBlockhash = blockhash;
+        tx.feePayer = publicKey;
+
+        const signedTx = await wallet.signTransaction?.(tx);
+        if (!signedTx) throw new Error("Failed to sign transaction");
+
+        const signature = await connection.sendRawTransaction(
+          signedTx.serialize(),
+        );
+        console.log(`Transaction sent: ${signature}`);
+
+        // Wait for confirmation
+        const confirmation = await connection.confirmTransaction(
+          {
+            blockhash,
+            lastValidBlockHeight,
+            signature,
+          },
+          "confirmed",
+        );
+
+        if (confirmation.value.err) {
+          console.error(
+            "Transaction failed after confirmation:",
+            confirmation.value.err,
+          );
+          throw new Error(
+            `Transaction confirmed but failed: ${JSON.stringify(confirmation.value.err)}`,
+          );
+        }
+
+        return signature;
+      } catch (error) {
+        console.error("Error in returnLegacyListing:", error);
+        throw error;
+      }
+    },
+    onSuccess: (signature) => {},
+    onError: (error) => {
+      console.error("Full return legacy listing error:", error);
+    },
+  });
+
+  // Return Legacy Bid Mutation
+  // Bids created before exchanges were namespaced sit at the old PDAs and can only be returned
+  const returnLegacyBid = useMutation({
+    mutationKey: ["return-legacy-bid", { cluster }],
+    mutationFn: async ({
+      nftMint,
+      paymentMint,
+    }: {
+      nftMint: PublicKey;
+      paymentMint: PublicKey;
+    }) => {
+      if (!publicKey) throw new Error("Wallet not connected");
+
+      try {
+        const paymentMintInfo = await connection.getAccountInfo(paymentMint);
+        const isToken2022 =
+          paymentMintInfo?.owner.equals(TOKEN_2022_PROGRAM_ID) ?? true;
+
+        const [[bidPDA], [escrowBidPDA]] = await getLegacyBidPDAs(
+          publicKey,
+          nftMint,
+          VOUCHER_EXCHANGE_PROGRAM_ID,
+        );
+
+        const bidderTokenAccount = await getAssociatedTokenAddress(
+          paymentMint,
+          publicKey,
+          false,
+          isToken2022 ? TOKEN_2022_PROGRAM_ID : TOKEN_PROGRAM_ID,
+        );
+
+        const tx = await program.methods
+          .returnLegacyBid()
+          .accounts({
+            bid: bidPDA,
+            bidder: publicKey,
+            nftMint: nftMint,
+            bidderTokenAccount: bidderTokenAccount,
+            paymentMint: paymentMint,
+            escrowAccount: escrowBidPDA,
+            tokenProgram: isToken2022 ? TOKEN_2022_PROGRAM_ID : TOKEN_PROGRAM_ID,
+          })
+          .transaction();
+
+        // Send and sign transaction
+        const { blockhash, lastValidBlockHeight } =
+          await connection.getLatestBlockhash("confirmed");
+        tx.recentBlockhash = blockhash;
+        tx.feePayer = publicKey;
+
+        const signedTx = await wallet.signTransaction?.(tx);
+        if (!signedTx) throw new Error("Failed to sign transaction");
+
+        const signature = await connection.sendRawTransaction(
+          signedTx.serialize(),
+        );
+        console.log(`Transaction sent: ${signature}`);
+
+        // Wait for confirmation
+        const confirmation = await connection.confirmTransaction(
+          {
+            blockhash,
+            lastValidBlockHeight,
+            signature,
+          },
+          "confirmed",
+        );
+
+        if (confirmation.value.err) {
+          console.error(
+            "Transaction failed after confirmation:",
+            confirmation.value.err,
+          );
+          throw new Error(
+            `Transaction confirmed but failed: ${JSON.stringify(confirmation.value.err)}`,
+          );
+        }
+
+        return signature;
+      } catch (error) {
+        console.error("Error in returnLegacyBid:", error);
+        throw error;
+      }
+    },
+    onSuccess: (signature) => {},
+    onError: (error) => {
+      console.error("Full return legacy bid error:", error);
+    },
+  });
+
   return {
     program,
     programId: VOUCHER_EXCHANGE_PROGRAM_ID,
@@ -1439,6 +1845,7 @@ export function useVoucherExchange() {
     // Fetch functions
     fetchActiveListings,
     fetchListingsByNftMint,
+    fetchListingsReservedFor,
     fetchListingsByOwner,
     fetchActiveBids,
     fetchBidsByNftMint,
@@ -1455,5 +1862,7 @@ export function useVoucherExchange() {
     cancelVoucherBid,
     markBidForRefund,
     refundBid,
+    returnLegacyListing,
+    returnLegacyBid,
   };
 }
@@ -17,6 +17,9 @@ export const OptToken = new PublicKey(
   "opt687jT4FsT6mza5rxMVoZkoSPaNpnorUBMeLY7e61",
 );
 
+// Brand namespace of the exchange this app trades on
+export const EXCHANGE_NAMESPACE = "omnipoints";
+
 export const axios = new Axios({
   transformRequest: [(data) => JSON.stringify(data)],
   transformResponse: [(data) => JSON.parse(data)],
@@ -0,0 +1,75 @@
+use anchor_lang::prelude::*;
+use anchor_spl::metadata::MetadataAccount;
+use anchor_spl::metadata::mpl_token_metadata;
+use crate::state::*;
+use crate::errors::*;
+use crate::constants::*;
+
+// Metaplex metadata of the NFT, if it has any
+pub fn read_metadata(nft_metadata: &AccountInfo) -> Result<Option<MetadataAccount>> {
+    if nft_metadata.owner != &mpl_token_metadata::ID || nft_metadata.data_is_empty() {
+        return Ok(None);
+    }
+
+    let metadata = MetadataAccount::try_deserialize(&mut &nft_metadata.data.borrow()[..])?;
+    Ok(Some(metadata))
+}
+
+// Collection mint of the NFT, if its metadata carries a verified collection
+pub fn verified_collection(metadata: Option<&MetadataAccount>) -> Option<Pubkey> {
+    metadata
+        .and_then(|metadata| metadata.collection.as_ref())
+        .filter(|collection| collection.verified)
+        .map(|collection| collection.key)
+}
+
+// Checks the NFT's verified collection is the approved one
+pub fn require_approved_collection(
+    metadata: Option<&MetadataAccount>,
+    approved_collection: &ApprovedCollection,
+) -> Result<()> {
+    let collection_mint = verified_collection(metadata).ok_or(VoucherExchangeError::UnverifiedCollection)?;
+    require_keys_eq!(
+        collection_mint,
+        approved_collection.collection_mint,
+        VoucherExchangeError::CollectionNotApproved
+    );
+
+    Ok(())
+}
+
+// Loads the override for the NFT's verified collection.
+// When the NFT belongs to a verified collection the config PDA must be passed,
+// so an existing override cannot be skipped by leaving the account out.
+pub fn load_collection_config(
+    exchange: &Pubkey,
+    metadata: Option<&MetadataAccount>,
+    collection_config: Option<&AccountInfo>,
+) -> Result<Option<CollectionConfig>> {
+    let Some(collection_mint) = verified_collection(metadata) else {
+        return Ok(None);
+    };
+
+    let collection_config = collection_config.ok_or(VoucherExchangeError::InvalidCollectionConfig)?;
+    let (expected_address, _) = Pubkey::find_program_address(
+        &[
+            COLLECTION_CONFIG_SEED,
+            exchange.as_ref(),
+            collection_mint.as_ref(),
+        ],
+        &crate::ID,
+    );
+    require_keys_eq!(
+        collection_config.key(),
+        expected_address,
+        VoucherExchangeError::InvalidCollectionConfig
+    );
+
+    // No override has been set for this collection
+    if collection_config.owner != &crate::ID || collection_config.data_is_empty() {
+        return Ok(None);
+    }
+
+    let config = CollectionConfig::try_deserialize(&mut &collection_config.data.borrow()[..])?;
+    Ok(Some(config))
+}
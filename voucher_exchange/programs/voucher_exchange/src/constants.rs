@@ -1,8 +1,48 @@
+use anchor_lang::prelude::*;
+
 pub const EXCHANGE_SEED: &[u8] = b"voucher_exchange";
 pub const VOUCHER_LISTING_SEED: &[u8] = b"voucher_listing";
 pub const VOUCHER_BID_SEED: &[u8] = b"voucher_bid";
 pub const ESCROW_SEED: &[u8] = b"escrow";
 pub const VOUCHER_STATE_SEED: &[u8] = b"voucher_state";
+pub const COLLECTION_CONFIG_SEED: &[u8] = b"collection_config";
+pub const FEE_VAULT_SEED: &[u8] = b"fee_vault";
+pub const REFERRER_SEED: &[u8] = b"referrer";
+pub const FEE_DISCOUNT_SEED: &[u8] = b"fee_discount";
+pub const PAYMENT_MINT_CONFIG_SEED: &[u8] = b"payment_mint_config";
+pub const APPROVED_COLLECTION_SEED: &[u8] = b"approved_collection";
+pub const BLOCKED_WALLET_SEED: &[u8] = b"blocked_wallet";
+pub const RECOVERY_ADDRESS_SEED: &[u8] = b"recovery_address";
+pub const RESCUE_REQUEST_SEED: &[u8] = b"rescue_request";
+pub const CLOSED_NAMESPACE_SEED: &[u8] = b"closed_namespace";
 
 pub const BASIS_POINTS_DIVISOR: u16 = 10000;
-pub const MAX_FEE_BASIS_POINTS: u16 = 1000; // 10% maximum fee
\ No newline at end of file
+pub const MAX_FEE_BASIS_POINTS: u16 = 1000; // 10% maximum fee
+pub const MAX_REFERRER_SHARE_BASIS_POINTS: u16 = 5000; // Referrers get at most half of the fee
+pub const MAX_FEE_DISCOUNT_TIERS: usize = 5;
+pub const MAX_PAYOUT_SPLITS: usize = 5;
+pub const MAX_NAMESPACE_LEN: usize = 32;
+pub const EXPIRY_CRANK_REWARD: u64 = 100_000; // Lamports of reclaimed rent paid for expiring a listing
+pub const MIN_FEE_UPDATE_DELAY: i64 = 24 * 60 * 60; // Fee updates wait at least a day after proposal
+pub const RESCUE_TIMELOCK: i64 = 30 * 24 * 60 * 60; // Rescues wait 30 days after announcement
+pub const MAX_DUTCH_AUCTION_DURATION: i64 = 365 * 24 * 60 * 60; // Dutch auctions fall for at most a year and start within a year
+pub const AUCTION_EXTENSION_WINDOW: i64 = 5 * 60; // Auction bids in the last 5 minutes push the end back to 5 minutes out
+pub const AUCTION_SETTLEMENT_GRACE: i64 = 7 * 24 * 60 * 60; // Top bids unsettled a week after the auction ends can be refunded by anyone
+
+// Current layout versions of the versioned accounts, bumped by every layout change.
+// Older accounts are upgraded by the migrate_* instructions.
+pub const EXCHANGE_VERSION: u8 = 1;
+pub const LISTING_VERSION: u8 = 1;
+pub const BID_VERSION: u8 = 1;
+pub const VOUCHER_STATE_VERSION: u8 = 1;
+
+// Operations the pauser can disable, as bits of `VoucherExchange.paused_operations`
+pub const PAUSE_CREATE_LISTING: u8 = 1 << 0;
+pub const PAUSE_CREATE_BID: u8 = 1 << 1;
+pub const PAUSE_FULFILL_LISTING: u8 = 1 << 2;
+pub const PAUSE_ACCEPT_BID: u8 = 1 << 3;
+pub const PAUSE_UPDATE_LISTING: u8 = 1 << 4;
+pub const PAUSE_ALL: u8 = PAUSE_CREATE_LISTING | PAUSE_CREATE_BID | PAUSE_FULFILL_LISTING | PAUSE_ACCEPT_BID | PAUSE_UPDATE_LISTING;
+
+// OmniPoints loyalty token (Token-2022)
+pub const OPT_MINT: Pubkey = pubkey!("opt687jT4FsT6mza5rxMVoZkoSPaNpnorUBMeLY7e61");
@@ -46,4 +46,193 @@ pub enum VoucherExchangeError {
 
     #[msg("Invalid nft account")]
     InvalidNFTAccount,
+
+    #[msg("Invalid fee account")]
+    InvalidFeeAccount,
+
+    #[msg("Invalid payment mint")]
+    InvalidPaymentMint,
+
+    #[msg("Arithmetic overflow")]
+    MathOverflow,
+
+    #[msg("Invalid fee update delay")]
+    InvalidFeeUpdateDelay,
+
+    #[msg("No fee update is pending")]
+    NoPendingFeeUpdate,
+
+    #[msg("Fee update delay has not elapsed yet")]
+    FeeUpdateNotReady,
+
+    #[msg("Invalid creator payment account")]
+    InvalidCreatorAccount,
+
+    #[msg("Royalty cap is too high")]
+    RoyaltyCapTooHigh,
+
+    #[msg("Fees and royalties exceed the sale price")]
+    InsufficientProceeds,
+
+    #[msg("Invalid collection config account")]
+    InvalidCollectionConfig,
+
+    #[msg("Price is outside the collection price range")]
+    PriceOutOfRange,
+
+    #[msg("No fees to withdraw")]
+    NoFeesToWithdraw,
+
+    #[msg("Referrer share is too high")]
+    ReferrerShareTooHigh,
+
+    #[msg("Invalid referrer")]
+    InvalidReferrer,
+
+    #[msg("Referrer is paused")]
+    ReferrerPaused,
+
+    #[msg("Invalid fee discount tiers")]
+    InvalidFeeDiscountTiers,
+
+    #[msg("Invalid fee discount account")]
+    InvalidFeeDiscountAccount,
+
+    #[msg("Fee burn rate is too high")]
+    FeeBurnTooHigh,
+
+    #[msg("Invalid payout splits")]
+    InvalidPayoutSplits,
+
+    #[msg("Invalid payout account")]
+    InvalidPayoutAccount,
+
+    #[msg("No authority handover is pending")]
+    NoPendingAuthority,
+
+    #[msg("Not the pending exchange authority")]
+    NotPendingAuthority,
+
+    #[msg("Signer does not hold the required exchange role")]
+    MissingRole,
+
+    #[msg("This operation is paused on the exchange")]
+    ExchangePaused,
+
+    #[msg("Unknown pause flags")]
+    InvalidPauseFlags,
+
+    #[msg("Invalid exchange namespace")]
+    InvalidNamespace,
+
+    #[msg("Account belongs to a different exchange")]
+    InvalidExchange,
+
+    #[msg("Payment mint decimals do not match its config")]
+    InvalidPaymentMintDecimals,
+
+    #[msg("Price is below the payment mint minimum")]
+    PriceBelowMinimum,
+
+    #[msg("NFT does not belong to a verified collection")]
+    UnverifiedCollection,
+
+    #[msg("NFT collection is not approved on this exchange")]
+    CollectionNotApproved,
+
+    #[msg("Wallet is blocked from trading on this exchange")]
+    WalletBlocked,
+
+    #[msg("Account is already at the current version")]
+    AccountAlreadyMigrated,
+
+    #[msg("Rescue timelock has not elapsed")]
+    RescueTimelockActive,
+
+    #[msg("Rescued assets can only go to the owner, bidder or their recovery address")]
+    InvalidRescueDestination,
+
+    #[msg("Exchange is winding down")]
+    ExchangeWindingDown,
+
+    #[msg("Exchange is not winding down")]
+    ExchangeNotWindingDown,
+
+    #[msg("Exchange still has open listings or bids")]
+    ExchangeNotEmpty,
+
+    #[msg("Expiry must be in the future")]
+    InvalidExpiry,
+
+    #[msg("Listing has expired")]
+    ListingExpired,
+
+    #[msg("Listing has not expired")]
+    ListingNotExpired,
+
+    #[msg("Listing is reserved for another buyer")]
+    NotReservedBuyer,
+
+    #[msg("Dutch auction must fall to a non-zero end price between a start and a later end time")]
+    InvalidDutchAuction,
+
+    #[msg("Current price is above the buyer's maximum")]
+    PriceAboveMaximum,
+
+    #[msg("English auction needs a non-zero increment, a future end time and no other listing mode")]
+    InvalidEnglishAuction,
+
+    #[msg("Listing is an English auction and sells through auction bids")]
+    AuctionListing,
+
+    #[msg("Listing is not an English auction")]
+    NotAnAuction,
+
+    #[msg("Auction has ended")]
+    AuctionEnded,
+
+    #[msg("Auction has not ended")]
+    AuctionNotEnded,
+
+    #[msg("Bid is below the reserve or the minimum increment")]
+    BidTooLow,
+
+    #[msg("Auction already has bids")]
+    AuctionHasBids,
+
+    #[msg("Auction has no bids")]
+    AuctionHasNoBids,
+
+    #[msg("Account does not belong to the top bidder")]
+    NotTopBidder,
+
+    #[msg("Seller cannot bid on their own auction")]
+    SellerCannotBid,
+
+    #[msg("Collection fees must match the exchange fees before the override is removed")]
+    CollectionFeesDiffer,
+
+    #[msg("Account layout is not a known version")]
+    UnknownAccountLayout,
+
+    #[msg("Bid is marked for refund")]
+    BidMarkedForRefund,
+
+    #[msg("Fee vault still holds fees")]
+    FeeVaultNotEmpty,
+
+    #[msg("All fee vaults must be closed first")]
+    FeeVaultsOpen,
+
+    #[msg("Exchange namespace has been closed")]
+    NamespaceClosed,
+
+    #[msg("The top bid can only be refunded after the settlement grace period, or by the refund operator while settlement is blocked")]
+    AuctionRefundNotAvailable,
+
+    #[msg("Rescue was announced for an earlier listing or bid at this address")]
+    StaleRescueRequest,
+
+    #[msg("Listing is a Dutch auction, whose price schedule cannot be updated")]
+    DutchAuctionListing,
 }
@@ -0,0 +1,72 @@
+use anchor_lang::prelude::*;
+
+// Emitted when a voucher changes hands through a listing or an accepted bid
+#[event]
+pub struct VoucherSold {
+    pub exchange: Pubkey,
+    pub nft_mint: Pubkey,
+    pub payment_mint: Pubkey,
+    pub seller: Pubkey,
+    pub buyer: Pubkey,
+    // Side that placed the resting order: the lister or the bidder
+    pub maker: Pubkey,
+    // Side that filled it: the buyer or the bid acceptor
+    pub taker: Pubkey,
+    pub price: u64,
+    pub maker_fee: u64,
+    pub taker_fee: u64,
+    pub royalties: u64,
+    // Amount paid out to the seller or their payout splits
+    pub seller_proceeds: u64,
+    pub timestamp: i64,
+}
+
+// Emitted when the authority announces a rescue of a listing or bid escrow
+#[event]
+pub struct RescueAnnounced {
+    pub exchange: Pubkey,
+    // Listing or bid being rescued
+    pub target: Pubkey,
+    pub destination: Pubkey,
+    pub executable_at: i64,
+}
+
+// Emitted when an announced rescue is withdrawn before being executed
+#[event]
+pub struct RescueCancelled {
+    pub exchange: Pubkey,
+    pub target: Pubkey,
+}
+
+// Emitted when escrowed assets are sent out by a rescue
+#[event]
+pub struct RescueExecuted {
+    pub exchange: Pubkey,
+    pub target: Pubkey,
+    pub destination: Pubkey,
+    pub mint: Pubkey,
+    pub amount: u64,
+}
+
+// Emitted when a bid becomes the top bid of an English auction
+#[event]
+pub struct AuctionBidPlaced {
+    pub exchange: Pubkey,
+    pub listing: Pubkey,
+    pub nft_mint: Pubkey,
+    pub bidder: Pubkey,
+    pub amount: u64,
+    // End time after any anti-sniping extension
+    pub end_time: i64,
+}
+
+// Emitted when an outbid or unsettled top bid is refunded to its bidder
+#[event]
+pub struct AuctionBidRefunded {
+    pub exchange: Pubkey,
+    pub listing: Pubkey,
+    pub nft_mint: Pubkey,
+    pub bidder: Pubkey,
+    // Bid and fee deposit refunded
+    pub amount: u64,
+}
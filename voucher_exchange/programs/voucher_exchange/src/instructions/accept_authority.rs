@@ -0,0 +1,43 @@
+use anchor_lang::prelude::*;
+use crate::state::*;
+use crate::errors::*;
+use crate::constants::*;
+
+// The proposed authority must sign, so a mistyped key can never take over
+#[derive(Accounts)]
+pub struct AcceptAuthority<'info> {
+    pub new_authority: Signer<'info>,
+
+    #[account(
+        mut,
+        seeds = [EXCHANGE_SEED, exchange.namespace.as_bytes()],
+        bump = exchange.bump
+    )]
+    pub exchange: Account<'info, VoucherExchange>,
+}
+
+pub fn handler(
+    ctx: Context<AcceptAuthority>,
+) -> Result<()> {
+    let exchange = &mut ctx.accounts.exchange;
+
+    // Check there is a handover to accept
+    require!(
+        exchange.pending_authority != Pubkey::default(),
+        VoucherExchangeError::NoPendingAuthority
+    );
+
+    // Check the signer is the proposed authority
+    require_keys_eq!(
+        ctx.accounts.new_authority.key(),
+        exchange.pending_authority,
+        VoucherExchangeError::NotPendingAuthority
+    );
+
+    exchange.authority = exchange.pending_authority;
+    exchange.pending_authority = Pubkey::default();
+
+    msg!("Authority handed over to {}", exchange.authority);
+
+    Ok(())
+}
@@ -1,13 +1,17 @@
 use anchor_lang::prelude::*;
 use anchor_spl::token_interface::{
     TokenAccount as TokenAccountInterface, Mint as MintInterface, TokenInterface,
-    TransferChecked, transfer_checked, CloseAccount, close_account
+    CloseAccount, close_account
 };
 use anchor_spl::token::{Token, Mint, TokenAccount, transfer}; // Add standard Token and Mint imports
 use anchor_spl::associated_token::AssociatedToken;
+use anchor_spl::metadata::Metadata;
+use anchor_spl::metadata::mpl_token_metadata;
 use crate::state::*;
 use crate::errors::*;
 use crate::constants::*;
+use crate::settlement::*;
+use crate::collection::*;
 
 #[derive(Accounts)]
 #[instruction(params: AcceptVoucherBidParams)]  // Add this line to derive Bumps
@@ -16,11 +20,13 @@ pub struct AcceptVoucherBid<'info> {
         mut,
         seeds = [
             VOUCHER_BID_SEED,
+            exchange.key().as_ref(),
             bidder.key().as_ref(),
             nft_mint.key().as_ref()
         ],
         bump = bid.bump,
-        constraint = bid.active == true @ VoucherExchangeError::BidNotActive,
+        has_one = exchange @ VoucherExchangeError::InvalidExchange,
+        constraint = bid.active @ VoucherExchangeError::BidNotActive,
         close = owner
     )]
     pub bid: Account<'info, VoucherBid>,
@@ -32,10 +38,49 @@ pub struct AcceptVoucherBid<'info> {
     #[account(mut, constraint = bidder.key() == bid.bidder @ VoucherExchangeError::NotBidder)]
     pub bidder: AccountInfo<'info>,
 
+    /// CHECK: BlockedWallet PDA of the owner, must not exist
+    #[account(
+        seeds = [
+            BLOCKED_WALLET_SEED,
+            exchange.key().as_ref(),
+            owner.key().as_ref()
+        ],
+        bump,
+        constraint = owner_blocked_wallet.data_is_empty() @ VoucherExchangeError::WalletBlocked
+    )]
+    pub owner_blocked_wallet: UncheckedAccount<'info>,
+
+    /// CHECK: BlockedWallet PDA of the bidder, must not exist
+    #[account(
+        seeds = [
+            BLOCKED_WALLET_SEED,
+            exchange.key().as_ref(),
+            bidder.key().as_ref()
+        ],
+        bump,
+        constraint = bidder_blocked_wallet.data_is_empty() @ VoucherExchangeError::WalletBlocked
+    )]
+    pub bidder_blocked_wallet: UncheckedAccount<'info>,
+
     // Changed to regular Mint instead of InterfaceAccount
     #[account(mut)]
     pub nft_mint: Account<'info, Mint>,
 
+    /// CHECK: Metaplex metadata PDA of the NFT, parsed in the handler if it exists
+    #[account(
+        seeds = [
+            mpl_token_metadata::accounts::Metadata::PREFIX,
+            token_metadata_program.key().as_ref(),
+            nft_mint.key().as_ref()
+        ],
+        seeds::program = token_metadata_program.key(),
+        bump
+    )]
+    pub nft_metadata: UncheckedAccount<'info>,
+
+    /// CHECK: Override for the NFT's verified collection, validated in the handler
+    pub collection_config: Option<UncheckedAccount<'info>>,
+
     #[account(
         mut,
         constraint = escrow_nft_account.mint == nft_mint.key() @ VoucherExchangeError::InvalidNFTAccount,
@@ -47,12 +92,15 @@ pub struct AcceptVoucherBid<'info> {
         mut,
         seeds = [
             VOUCHER_LISTING_SEED,
+            exchange.key().as_ref(),
             owner.key().as_ref(),
             nft_mint.key().as_ref()
         ],
         bump = listing.bump,
-        constraint = listing.active == true @ VoucherExchangeError::ListingNotActive,
+        has_one = exchange @ VoucherExchangeError::InvalidExchange,
+        constraint = listing.active @ VoucherExchangeError::ListingNotActive,
         constraint = listing.owner == owner.key() @ VoucherExchangeError::NotListingOwner,
+        constraint = listing.english_auction.is_none() @ VoucherExchangeError::AuctionListing,
         close = owner
     )]
     pub listing: Account<'info, VoucherListing>,
@@ -74,6 +122,14 @@ pub struct AcceptVoucherBid<'info> {
     )]
     pub escrow_payment_account: InterfaceAccount<'info, TokenAccountInterface>,
 
+    // Receives the part of the maker fee deposit that is not charged
+    #[account(
+        mut,
+        constraint = bidder_payment_account.mint == payment_mint.key() @ VoucherExchangeError::InvalidPaymentMint,
+        constraint = bidder_payment_account.owner == bidder.key() @ VoucherExchangeError::NotBidder,
+    )]
+    pub bidder_payment_account: InterfaceAccount<'info, TokenAccountInterface>,
+
     #[account(
         mut,
         constraint = owner_payment_account.mint == payment_mint.key(),
@@ -81,7 +137,46 @@ pub struct AcceptVoucherBid<'info> {
     )]
     pub owner_payment_account: InterfaceAccount<'info, TokenAccountInterface>,
 
+    #[account(
+        mut,
+        seeds = [
+            FEE_VAULT_SEED,
+            exchange.key().as_ref(),
+            payment_mint.key().as_ref()
+        ],
+        bump = fee_vault.bump,
+        has_one = exchange @ VoucherExchangeError::InvalidExchange
+    )]
+    pub fee_vault: Account<'info, FeeVault>,
+
+    #[account(
+        mut,
+        address = fee_vault.vault @ VoucherExchangeError::InvalidFeeAccount
+    )]
+    pub fee_vault_token_account: InterfaceAccount<'info, TokenAccountInterface>,
+
+    // Optional referrer approved on this exchange, paid a share of the fee
+    #[account(mut)]
+    pub referrer: Option<Account<'info, Referrer>>,
+
     #[account(mut)]
+    pub referrer_payment_account: Option<InterfaceAccount<'info, TokenAccountInterface>>,
+
+    // Optional OPT holder discount, applied to the bidder's fee
+    #[account(
+        seeds = [FEE_DISCOUNT_SEED, exchange.key().as_ref()],
+        bump = fee_discount_config.bump
+    )]
+    pub fee_discount_config: Option<Account<'info, FeeDiscountConfig>>,
+
+    pub bidder_opt_account: Option<InterfaceAccount<'info, TokenAccountInterface>>,
+
+    #[account(
+        mut,
+        seeds = [EXCHANGE_SEED, exchange.namespace.as_bytes()],
+        bump = exchange.bump,
+        constraint = !exchange.is_paused(PAUSE_ACCEPT_BID) @ VoucherExchangeError::ExchangePaused
+    )]
     pub exchange: Account<'info, VoucherExchange>,
 
     pub token_program: Interface<'info, TokenInterface>,
@@ -89,6 +184,7 @@ pub struct AcceptVoucherBid<'info> {
     // Changed to standard Token program
     pub token_nft_program: Program<'info, Token>,
 
+    pub token_metadata_program: Program<'info, Metadata>,
     pub associated_token_program: Program<'info, AssociatedToken>,
     pub system_program: Program<'info, System>,
 }
@@ -97,8 +193,12 @@ pub struct AcceptVoucherBid<'info> {
 #[derive(AnchorSerialize, AnchorDeserialize)]
 pub struct AcceptVoucherBidParams {}
 
-pub fn handler(
-    ctx: Context<AcceptVoucherBid>,
+// Remaining accounts: payment token accounts of the verified creators, see `pay_royalties`,
+// followed by one payment token account per payout split of the listing.
+// The bidder is the maker and the owner the taker: the maker fee is taken from the
+// fee deposit escrowed with the bid and the taker fee comes out of the seller proceeds.
+pub fn handler<'info>(
+    ctx: Context<'_, '_, 'info, 'info, AcceptVoucherBid<'info>>,
 ) -> Result<()> {
     // Check NFT amount in escrow
     require!(
@@ -110,7 +210,8 @@ pub fn handler(
     let price = ctx.accounts.bid.price;
 
     // Transfer payment from escrow to seller
-    let (bid_bump, listing_bump) = (ctx.accounts.bid.bump, ctx.accounts.listing.bump);
+    let listing_bump = ctx.accounts.listing.bump;
+    let exchange_key = ctx.accounts.exchange.key();
     let bidder_key = ctx.accounts.bidder.key();
     let owner_key = ctx.accounts.owner.key();
     let nft_mint_key = ctx.accounts.nft_mint.key();
@@ -120,6 +221,7 @@ pub fn handler(
     // Get bid seeds for signing
     let escrow_seeds = &[
         ESCROW_SEED,
+        exchange_key.as_ref(),
         bidder_key.as_ref(),
         nft_mint_key.as_ref(),
         &[ctx.accounts.bid.escrow_bump],
@@ -127,23 +229,59 @@ pub fn handler(
 
     let escrow_signer_seeds = &[&escrow_seeds[..]];
 
-    // 1. Transfer payment from escrow to owner
-    transfer_checked(
-        CpiContext::new_with_signer(
-            payment_token_program.clone(),
-            TransferChecked {
-                from: ctx.accounts.escrow_payment_account.to_account_info(),
-                mint: ctx.accounts.payment_mint.to_account_info(),
-                to: ctx.accounts.owner_payment_account.to_account_info(),
-                authority: ctx.accounts.escrow_payment_account.to_account_info(),
-            },
-            escrow_signer_seeds,
-        ),
-        price,
-        ctx.accounts.payment_mint.decimals,
+    // Resolve fee and royalty terms, preferring the collection override
+    // and applying the bidder's OPT holder discount to the maker fee
+    let metadata = read_metadata(&ctx.accounts.nft_metadata)?;
+    let collection_config = load_collection_config(
+        &ctx.accounts.exchange.key(),
+        metadata.as_ref(),
+        ctx.accounts.collection_config.as_deref(),
     )?;
+    let mut terms = SaleTerms::new(&ctx.accounts.exchange, collection_config.as_ref());
+    terms.apply_maker_discount(fee_discount_basis_points(
+        &ctx.accounts.bidder.key(),
+        ctx.accounts.fee_discount_config.as_deref(),
+        ctx.accounts.bidder_opt_account.as_ref(),
+    )?);
+
+    let source = PaymentSource {
+        token_program: payment_token_program.clone(),
+        mint: &ctx.accounts.payment_mint,
+        from: ctx.accounts.escrow_payment_account.to_account_info(),
+        authority: ctx.accounts.escrow_payment_account.to_account_info(),
+        signer_seeds: escrow_signer_seeds,
+    };
+
+    // The maker fee never exceeds what the bidder deposited
+    let fee_deposit = ctx.accounts.bid.fee_deposit;
+    let sale = Sale {
+        nft_mint: nft_mint_key,
+        seller: owner_key,
+        buyer: bidder_key,
+        buyer_is_maker: true,
+        price,
+        terms,
+        metadata: metadata.as_ref(),
+        buyer_fee_deposit: Some(fee_deposit),
+    };
+
+    // 1. Transfer fees, royalties and the seller proceeds from escrow
+    let maker_fee = settle_sale(&source, &sale, SaleAccounts {
+        exchange: &mut ctx.accounts.exchange,
+        fee_vault: &mut ctx.accounts.fee_vault,
+        fee_vault_token_account: ctx.accounts.fee_vault_token_account.to_account_info(),
+        referrer: ctx.accounts.referrer.as_mut(),
+        referrer_payment_account: ctx.accounts.referrer_payment_account.as_ref(),
+        seller_payment_account: ctx.accounts.owner_payment_account.to_account_info(),
+        payout_splits: &ctx.accounts.listing.payout_splits,
+        remaining_accounts: ctx.remaining_accounts,
+    })?;
+
+    // 2. Refund the unused maker fee deposit to the bidder
+    let deposit_refund = fee_deposit.checked_sub(maker_fee).ok_or(VoucherExchangeError::MathOverflow)?;
+    source.pay(ctx.accounts.bidder_payment_account.to_account_info(), deposit_refund)?;
 
-    // 2. Close escrow payment account and return rent to bidder
+    // Close escrow payment account and return rent to bidder
     close_account(
         CpiContext::new_with_signer(
             payment_token_program,
@@ -159,6 +297,7 @@ pub fn handler(
     // Get listing seeds for signing
     let listing_seeds = &[
         VOUCHER_LISTING_SEED,
+        exchange_key.as_ref(),
         owner_key.as_ref(),
         nft_mint_key.as_ref(),
         &[listing_bump],
@@ -197,8 +336,12 @@ pub fn handler(
 
     // Update exchange statistics
     let exchange = &mut ctx.accounts.exchange;
-    exchange.total_bids = exchange.total_bids.checked_sub(1).unwrap_or(0);
-    exchange.total_listings = exchange.total_listings.checked_sub(1).unwrap_or(0);
+    exchange.total_bids = exchange.total_bids
+        .checked_sub(1)
+        .ok_or(VoucherExchangeError::MathOverflow)?;
+    exchange.total_listings = exchange.total_listings
+        .checked_sub(1)
+        .ok_or(VoucherExchangeError::MathOverflow)?;
 
     Ok(())
 }
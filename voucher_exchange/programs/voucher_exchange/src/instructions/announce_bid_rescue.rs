@@ -0,0 +1,74 @@
+use anchor_lang::prelude::*;
+use crate::state::*;
+use crate::errors::*;
+use crate::constants::*;
+use crate::rescue::*;
+
+#[derive(Accounts)]
+pub struct AnnounceBidRescue<'info> {
+    #[account(
+        init,
+        payer = authority,
+        space = RescueRequest::SIZE,
+        seeds = [
+            RESCUE_REQUEST_SEED,
+            exchange.key().as_ref(),
+            bid.key().as_ref()
+        ],
+        bump
+    )]
+    pub rescue_request: Account<'info, RescueRequest>,
+
+    #[account(
+        seeds = [
+            VOUCHER_BID_SEED,
+            exchange.key().as_ref(),
+            bid.bidder.as_ref(),
+            bid.nft_mint.as_ref()
+        ],
+        bump = bid.bump,
+        has_one = exchange @ VoucherExchangeError::InvalidExchange,
+    )]
+    pub bid: Account<'info, VoucherBid>,
+
+    /// CHECK: RecoveryAddress PDA of the bidder, read in the handler when it exists
+    #[account(
+        seeds = [
+            RECOVERY_ADDRESS_SEED,
+            exchange.key().as_ref(),
+            bid.bidder.as_ref()
+        ],
+        bump
+    )]
+    pub recovery_address: UncheckedAccount<'info>,
+
+    #[account(
+        mut,
+        constraint = authority.key() == exchange.authority @ VoucherExchangeError::NotExchangeAuthority,
+    )]
+    pub authority: Signer<'info>,
+
+    #[account(
+        seeds = [EXCHANGE_SEED, exchange.namespace.as_bytes()],
+        bump = exchange.bump
+    )]
+    pub exchange: Account<'info, VoucherExchange>,
+
+    pub system_program: Program<'info, System>,
+}
+
+// The escrowed funds go to the bidder, or to their recovery address when set
+pub fn handler(
+    ctx: Context<AnnounceBidRescue>,
+) -> Result<()> {
+    let destination = rescue_destination(&ctx.accounts.recovery_address, ctx.accounts.bid.bidder)?;
+
+    announce_rescue(
+        &mut ctx.accounts.rescue_request,
+        ctx.accounts.exchange.key(),
+        ctx.accounts.bid.key(),
+        ctx.accounts.bid.nonce,
+        destination,
+        ctx.bumps.rescue_request,
+    )
+}
@@ -0,0 +1,75 @@
+use anchor_lang::prelude::*;
+use crate::state::*;
+use crate::errors::*;
+use crate::constants::*;
+use crate::rescue::*;
+
+#[derive(Accounts)]
+pub struct AnnounceListingRescue<'info> {
+    #[account(
+        init,
+        payer = authority,
+        space = RescueRequest::SIZE,
+        seeds = [
+            RESCUE_REQUEST_SEED,
+            exchange.key().as_ref(),
+            listing.key().as_ref()
+        ],
+        bump
+    )]
+    pub rescue_request: Account<'info, RescueRequest>,
+
+    #[account(
+        seeds = [
+            VOUCHER_LISTING_SEED,
+            exchange.key().as_ref(),
+            listing.owner.as_ref(),
+            listing.nft_mint.as_ref()
+        ],
+        bump = listing.bump,
+        has_one = exchange @ VoucherExchangeError::InvalidExchange,
+        constraint = !listing.has_auction_bids() @ VoucherExchangeError::AuctionHasBids,
+    )]
+    pub listing: Account<'info, VoucherListing>,
+
+    /// CHECK: RecoveryAddress PDA of the listing owner, read in the handler when it exists
+    #[account(
+        seeds = [
+            RECOVERY_ADDRESS_SEED,
+            exchange.key().as_ref(),
+            listing.owner.as_ref()
+        ],
+        bump
+    )]
+    pub recovery_address: UncheckedAccount<'info>,
+
+    #[account(
+        mut,
+        constraint = authority.key() == exchange.authority @ VoucherExchangeError::NotExchangeAuthority,
+    )]
+    pub authority: Signer<'info>,
+
+    #[account(
+        seeds = [EXCHANGE_SEED, exchange.namespace.as_bytes()],
+        bump = exchange.bump
+    )]
+    pub exchange: Account<'info, VoucherExchange>,
+
+    pub system_program: Program<'info, System>,
+}
+
+// The escrowed NFT goes to the listing owner, or to their recovery address when set
+pub fn handler(
+    ctx: Context<AnnounceListingRescue>,
+) -> Result<()> {
+    let destination = rescue_destination(&ctx.accounts.recovery_address, ctx.accounts.listing.owner)?;
+
+    announce_rescue(
+        &mut ctx.accounts.rescue_request,
+        ctx.accounts.exchange.key(),
+        ctx.accounts.listing.key(),
+        ctx.accounts.listing.nonce,
+        destination,
+        ctx.bumps.rescue_request,
+    )
+}
@@ -0,0 +1,58 @@
+use anchor_lang::prelude::*;
+use crate::state::*;
+use crate::errors::*;
+use crate::constants::*;
+
+// Anyone can apply a collection fee update once its delay has elapsed
+#[derive(Accounts)]
+pub struct ApplyCollectionFeeUpdate<'info> {
+    #[account(
+        mut,
+        seeds = [
+            COLLECTION_CONFIG_SEED,
+            exchange.key().as_ref(),
+            collection_config.collection_mint.as_ref()
+        ],
+        bump = collection_config.bump,
+        has_one = exchange @ VoucherExchangeError::InvalidExchange
+    )]
+    pub collection_config: Account<'info, CollectionConfig>,
+
+    #[account(
+        seeds = [EXCHANGE_SEED, exchange.namespace.as_bytes()],
+        bump = exchange.bump
+    )]
+    pub exchange: Account<'info, VoucherExchange>,
+}
+
+pub fn handler(
+    ctx: Context<ApplyCollectionFeeUpdate>,
+) -> Result<()> {
+    let collection_config = &mut ctx.accounts.collection_config;
+
+    // Check there is a proposal to apply
+    require!(
+        collection_config.has_pending_fee_update(),
+        VoucherExchangeError::NoPendingFeeUpdate
+    );
+
+    // Check the delay has elapsed
+    let now = Clock::get()?.unix_timestamp;
+    require!(
+        now >= collection_config.pending_fee_effective_at,
+        VoucherExchangeError::FeeUpdateNotReady
+    );
+
+    collection_config.maker_fee_basis_points = collection_config.pending_maker_fee_basis_points;
+    collection_config.taker_fee_basis_points = collection_config.pending_taker_fee_basis_points;
+    collection_config.clear_pending_fee_update();
+
+    msg!(
+        "Collection {} fee update applied: {} bps maker, {} bps taker",
+        collection_config.collection_mint,
+        collection_config.maker_fee_basis_points,
+        collection_config.taker_fee_basis_points
+    );
+
+    Ok(())
+}
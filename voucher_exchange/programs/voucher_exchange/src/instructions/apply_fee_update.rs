@@ -0,0 +1,48 @@
+use anchor_lang::prelude::*;
+use crate::state::*;
+use crate::errors::*;
+use crate::constants::*;
+
+// Anyone can apply a fee update once its delay has elapsed
+#[derive(Accounts)]
+pub struct ApplyFeeUpdate<'info> {
+    #[account(
+        mut,
+        seeds = [EXCHANGE_SEED, exchange.namespace.as_bytes()],
+        bump = exchange.bump
+    )]
+    pub exchange: Account<'info, VoucherExchange>,
+}
+
+pub fn handler(
+    ctx: Context<ApplyFeeUpdate>,
+) -> Result<()> {
+    let exchange = &mut ctx.accounts.exchange;
+
+    // Check there is a proposal to apply
+    require!(
+        exchange.has_pending_fee_update(),
+        VoucherExchangeError::NoPendingFeeUpdate
+    );
+
+    // Check the delay has elapsed
+    let now = Clock::get()?.unix_timestamp;
+    require!(
+        now >= exchange.pending_fee_effective_at,
+        VoucherExchangeError::FeeUpdateNotReady
+    );
+
+    exchange.maker_fee_basis_points = exchange.pending_maker_fee_basis_points;
+    exchange.taker_fee_basis_points = exchange.pending_taker_fee_basis_points;
+    exchange.fee_recipient = exchange.pending_fee_recipient;
+    exchange.clear_pending_fee_update();
+
+    msg!(
+        "Fee update applied: {} bps maker, {} bps taker to {}",
+        exchange.maker_fee_basis_points,
+        exchange.taker_fee_basis_points,
+        exchange.fee_recipient
+    );
+
+    Ok(())
+}
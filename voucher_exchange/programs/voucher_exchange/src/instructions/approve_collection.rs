@@ -0,0 +1,47 @@
+use anchor_lang::prelude::*;
+use crate::state::*;
+use crate::errors::*;
+use crate::constants::*;
+
+#[derive(Accounts)]
+#[instruction(collection_mint: Pubkey)]
+pub struct ApproveCollection<'info> {
+    #[account(
+        init_if_needed,
+        payer = authority,
+        space = ApprovedCollection::SIZE,
+        seeds = [
+            APPROVED_COLLECTION_SEED,
+            exchange.key().as_ref(),
+            collection_mint.as_ref()
+        ],
+        bump
+    )]
+    pub approved_collection: Account<'info, ApprovedCollection>,
+
+    #[account(
+        mut,
+        constraint = exchange.has_role(ExchangeRole::CollectionCurator, &authority.key()) @ VoucherExchangeError::MissingRole,
+    )]
+    pub authority: Signer<'info>,
+
+    #[account(
+        seeds = [EXCHANGE_SEED, exchange.namespace.as_bytes()],
+        bump = exchange.bump
+    )]
+    pub exchange: Account<'info, VoucherExchange>,
+
+    pub system_program: Program<'info, System>,
+}
+
+pub fn handler(
+    ctx: Context<ApproveCollection>,
+    collection_mint: Pubkey,
+) -> Result<()> {
+    let approved_collection = &mut ctx.accounts.approved_collection;
+    approved_collection.exchange = ctx.accounts.exchange.key();
+    approved_collection.collection_mint = collection_mint;
+    approved_collection.bump = ctx.bumps.approved_collection;
+
+    Ok(())
+}
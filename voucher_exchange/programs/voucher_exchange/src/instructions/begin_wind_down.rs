@@ -0,0 +1,31 @@
+use anchor_lang::prelude::*;
+use crate::state::*;
+use crate::errors::*;
+use crate::constants::*;
+
+#[derive(Accounts)]
+pub struct BeginWindDown<'info> {
+    #[account(
+        constraint = authority.key() == exchange.authority @ VoucherExchangeError::NotExchangeAuthority,
+    )]
+    pub authority: Signer<'info>,
+
+    #[account(
+        mut,
+        seeds = [EXCHANGE_SEED, exchange.namespace.as_bytes()],
+        bump = exchange.bump,
+        constraint = !exchange.winding_down @ VoucherExchangeError::ExchangeWindingDown
+    )]
+    pub exchange: Account<'info, VoucherExchange>,
+}
+
+// Winding down cannot be undone: once started, anyone can return escrowed assets
+pub fn handler(
+    ctx: Context<BeginWindDown>,
+) -> Result<()> {
+    ctx.accounts.exchange.winding_down = true;
+
+    msg!("Exchange {} is winding down", ctx.accounts.exchange.namespace);
+
+    Ok(())
+}
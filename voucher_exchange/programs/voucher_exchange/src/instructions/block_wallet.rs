@@ -0,0 +1,51 @@
+use anchor_lang::prelude::*;
+use crate::state::*;
+use crate::errors::*;
+use crate::constants::*;
+
+#[derive(Accounts)]
+#[instruction(wallet: Pubkey)]
+pub struct BlockWallet<'info> {
+    #[account(
+        init,
+        payer = authority,
+        space = BlockedWallet::SIZE,
+        seeds = [
+            BLOCKED_WALLET_SEED,
+            exchange.key().as_ref(),
+            wallet.as_ref()
+        ],
+        bump
+    )]
+    pub blocked_wallet: Account<'info, BlockedWallet>,
+
+    #[account(
+        mut,
+        constraint = authority.key() == exchange.authority @ VoucherExchangeError::NotExchangeAuthority,
+    )]
+    pub authority: Signer<'info>,
+
+    #[account(
+        seeds = [EXCHANGE_SEED, exchange.namespace.as_bytes()],
+        bump = exchange.bump
+    )]
+    pub exchange: Account<'info, VoucherExchange>,
+
+    pub system_program: Program<'info, System>,
+}
+
+// Blocked wallets can still cancel their listings and bids and claim refunds
+pub fn handler(
+    ctx: Context<BlockWallet>,
+    wallet: Pubkey,
+) -> Result<()> {
+    let blocked_wallet = &mut ctx.accounts.blocked_wallet;
+    blocked_wallet.exchange = ctx.accounts.exchange.key();
+    blocked_wallet.wallet = wallet;
+    blocked_wallet.blocked_at = Clock::get()?.unix_timestamp;
+    blocked_wallet.bump = ctx.bumps.blocked_wallet;
+
+    msg!("Wallet {} blocked", wallet);
+
+    Ok(())
+}
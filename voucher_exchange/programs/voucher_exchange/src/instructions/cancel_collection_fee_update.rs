@@ -0,0 +1,46 @@
+use anchor_lang::prelude::*;
+use crate::state::*;
+use crate::errors::*;
+use crate::constants::*;
+
+#[derive(Accounts)]
+pub struct CancelCollectionFeeUpdate<'info> {
+    #[account(
+        mut,
+        seeds = [
+            COLLECTION_CONFIG_SEED,
+            exchange.key().as_ref(),
+            collection_config.collection_mint.as_ref()
+        ],
+        bump = collection_config.bump,
+        has_one = exchange @ VoucherExchangeError::InvalidExchange
+    )]
+    pub collection_config: Account<'info, CollectionConfig>,
+
+    #[account(
+        constraint = exchange.has_role(ExchangeRole::FeeAdmin, &authority.key()) @ VoucherExchangeError::MissingRole,
+    )]
+    pub authority: Signer<'info>,
+
+    #[account(
+        seeds = [EXCHANGE_SEED, exchange.namespace.as_bytes()],
+        bump = exchange.bump
+    )]
+    pub exchange: Account<'info, VoucherExchange>,
+}
+
+pub fn handler(
+    ctx: Context<CancelCollectionFeeUpdate>,
+) -> Result<()> {
+    let collection_config = &mut ctx.accounts.collection_config;
+
+    // Check there is a proposal to cancel
+    require!(
+        collection_config.has_pending_fee_update(),
+        VoucherExchangeError::NoPendingFeeUpdate
+    );
+
+    collection_config.clear_pending_fee_update();
+
+    Ok(())
+}
@@ -0,0 +1,35 @@
+use anchor_lang::prelude::*;
+use crate::state::*;
+use crate::errors::*;
+use crate::constants::*;
+
+#[derive(Accounts)]
+pub struct CancelFeeUpdate<'info> {
+    #[account(
+        constraint = exchange.has_role(ExchangeRole::FeeAdmin, &authority.key()) @ VoucherExchangeError::MissingRole,
+    )]
+    pub authority: Signer<'info>,
+
+    #[account(
+        mut,
+        seeds = [EXCHANGE_SEED, exchange.namespace.as_bytes()],
+        bump = exchange.bump
+    )]
+    pub exchange: Account<'info, VoucherExchange>,
+}
+
+pub fn handler(
+    ctx: Context<CancelFeeUpdate>,
+) -> Result<()> {
+    let exchange = &mut ctx.accounts.exchange;
+
+    // Check there is a proposal to cancel
+    require!(
+        exchange.has_pending_fee_update(),
+        VoucherExchangeError::NoPendingFeeUpdate
+    );
+
+    exchange.clear_pending_fee_update();
+
+    Ok(())
+}
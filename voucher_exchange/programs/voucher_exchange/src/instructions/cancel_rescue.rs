@@ -0,0 +1,45 @@
+use anchor_lang::prelude::*;
+use crate::state::*;
+use crate::errors::*;
+use crate::constants::*;
+use crate::events::*;
+
+#[derive(Accounts)]
+pub struct CancelRescue<'info> {
+    #[account(
+        mut,
+        seeds = [
+            RESCUE_REQUEST_SEED,
+            exchange.key().as_ref(),
+            rescue_request.target.as_ref()
+        ],
+        bump = rescue_request.bump,
+        has_one = exchange @ VoucherExchangeError::InvalidExchange,
+        close = authority
+    )]
+    pub rescue_request: Account<'info, RescueRequest>,
+
+    #[account(
+        mut,
+        constraint = authority.key() == exchange.authority @ VoucherExchangeError::NotExchangeAuthority,
+    )]
+    pub authority: Signer<'info>,
+
+    #[account(
+        seeds = [EXCHANGE_SEED, exchange.namespace.as_bytes()],
+        bump = exchange.bump
+    )]
+    pub exchange: Account<'info, VoucherExchange>,
+}
+
+pub fn handler(
+    ctx: Context<CancelRescue>,
+) -> Result<()> {
+    // The rescue request is closed with the close = authority constraint
+    emit!(RescueCancelled {
+        exchange: ctx.accounts.exchange.key(),
+        target: ctx.accounts.rescue_request.target,
+    });
+
+    Ok(())
+}
@@ -14,10 +14,12 @@ pub struct CancelVoucherBid<'info> {
         mut,
         seeds = [
         VOUCHER_BID_SEED,
+        exchange.key().as_ref(),
         bidder.key().as_ref(),
         nft_mint.key().as_ref()
         ],
         bump = bid.bump,
+        has_one = exchange @ VoucherExchangeError::InvalidExchange,
         constraint = bid.bidder == bidder.key() @ VoucherExchangeError::NotBidder,
         close = bidder  // Close the bid account and return rent to bidder
     )]
@@ -26,6 +28,13 @@ pub struct CancelVoucherBid<'info> {
     #[account(mut)]
     pub bidder: Signer<'info>,
 
+    #[account(
+        mut,
+        seeds = [EXCHANGE_SEED, exchange.namespace.as_bytes()],
+        bump = exchange.bump
+    )]
+    pub exchange: Account<'info, VoucherExchange>,
+
     // Using InterfaceAccount for mints
     pub nft_mint: InterfaceAccount<'info, MintInterface>,
 
@@ -33,6 +42,7 @@ pub struct CancelVoucherBid<'info> {
         mut,
         seeds = [
         ESCROW_SEED,
+        exchange.key().as_ref(),
         bidder.key().as_ref(),
         nft_mint.key().as_ref()
         ],
@@ -66,6 +76,7 @@ pub fn handler(
 
     // Refund from escrow - set up seeds with proper lifetimes
     let escrow_seed = ESCROW_SEED;
+    let exchange_key = ctx.accounts.exchange.key();
     let bidder_key = ctx.accounts.bidder.key();
     let nft_mint_key = ctx.accounts.nft_mint.key();
     let escrow_bump = ctx.accounts.bid.escrow_bump;
@@ -73,6 +84,7 @@ pub fn handler(
     // Create the seeds array with the correct lifetime
     let escrow_seeds = &[
         escrow_seed,
+        exchange_key.as_ref(),
         bidder_key.as_ref(),
         nft_mint_key.as_ref(),
         &[escrow_bump],
@@ -95,7 +107,7 @@ pub fn handler(
             },
             signer_seeds,
         ),
-        ctx.accounts.bid.price,
+        ctx.accounts.bid.escrowed_amount()?,
         ctx.accounts.payment_mint.decimals,
     )?;
 
@@ -115,5 +127,11 @@ pub fn handler(
     // No need to mark bid as inactive since we're closing the account
     // ctx.accounts.bid.active = false;
 
+    // Decrement total bids in exchange
+    let exchange = &mut ctx.accounts.exchange;
+    exchange.total_bids = exchange.total_bids
+        .checked_sub(1)
+        .ok_or(VoucherExchangeError::MathOverflow)?;
+
     Ok(())
 }
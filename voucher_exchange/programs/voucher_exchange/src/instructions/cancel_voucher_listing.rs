@@ -12,12 +12,15 @@ pub struct CancelVoucherListing<'info> {
         mut,
         seeds = [
             VOUCHER_LISTING_SEED,
+            exchange.key().as_ref(),
             owner.key().as_ref(),
             nft_mint.key().as_ref()
         ],
         bump = listing.bump,
+        has_one = exchange @ VoucherExchangeError::InvalidExchange,
         constraint = listing.owner == owner.key() @ VoucherExchangeError::NotListingOwner,
         constraint = listing.active @ VoucherExchangeError::ListingNotActive,
+        constraint = !listing.has_auction_bids() @ VoucherExchangeError::AuctionHasBids,
         close = owner
     )]
     pub listing: Account<'info, VoucherListing>,
@@ -25,6 +28,13 @@ pub struct CancelVoucherListing<'info> {
     #[account(mut)]
     pub owner: Signer<'info>,
 
+    #[account(
+        mut,
+        seeds = [EXCHANGE_SEED, exchange.namespace.as_bytes()],
+        bump = exchange.bump
+    )]
+    pub exchange: Account<'info, VoucherExchange>,
+
     pub nft_mint: InterfaceAccount<'info, Mint>,
 
     #[account(
@@ -38,6 +48,7 @@ pub struct CancelVoucherListing<'info> {
         mut,
         seeds = [
             ESCROW_SEED,
+            exchange.key().as_ref(),
             nft_mint.key().as_ref()
         ],
         bump,
@@ -57,11 +68,13 @@ pub fn handler(
     let listing_bump = ctx.accounts.listing.bump;
 
     // Create longer-lived values with let bindings
+    let exchange_key = ctx.accounts.exchange.key();
     let owner_key = ctx.accounts.owner.key();
     let nft_mint_key = ctx.accounts.nft_mint.key();
 
     let listing_seeds = &[
         VOUCHER_LISTING_SEED,
+        exchange_key.as_ref(),
         owner_key.as_ref(),
         nft_mint_key.as_ref(),
         &[listing_bump],
@@ -97,6 +110,12 @@ pub fn handler(
         )
     )?;
 
+    // Decrement total listings in exchange
+    let exchange = &mut ctx.accounts.exchange;
+    exchange.total_listings = exchange.total_listings
+        .checked_sub(1)
+        .ok_or(VoucherExchangeError::MathOverflow)?;
+
     // The listing account will be automatically closed due to the close = owner constraint
     // and its lamports will be transferred to the owner
 
@@ -0,0 +1,51 @@
+use anchor_lang::prelude::*;
+use crate::state::*;
+use crate::errors::*;
+use crate::constants::*;
+
+#[derive(Accounts)]
+pub struct CloseExchange<'info> {
+    #[account(
+        mut,
+        constraint = authority.key() == exchange.authority @ VoucherExchangeError::NotExchangeAuthority,
+    )]
+    pub authority: Signer<'info>,
+
+    #[account(
+        mut,
+        seeds = [EXCHANGE_SEED, exchange.namespace.as_bytes()],
+        bump = exchange.bump,
+        constraint = exchange.winding_down @ VoucherExchangeError::ExchangeNotWindingDown,
+        constraint = exchange.total_listings == 0 && exchange.total_bids == 0 @ VoucherExchangeError::ExchangeNotEmpty,
+        constraint = exchange.fee_vault_count == 0 @ VoucherExchangeError::FeeVaultsOpen,
+        close = authority
+    )]
+    pub exchange: Account<'info, VoucherExchange>,
+
+    #[account(
+        init,
+        payer = authority,
+        space = ClosedNamespace::SIZE,
+        seeds = [CLOSED_NAMESPACE_SEED, exchange.namespace.as_bytes()],
+        bump
+    )]
+    pub closed_namespace: Account<'info, ClosedNamespace>,
+
+    pub system_program: Program<'info, System>,
+}
+
+pub fn handler(
+    ctx: Context<CloseExchange>,
+) -> Result<()> {
+    // Tombstone the namespace so the exchange PDA cannot be initialized again
+    let closed_namespace = &mut ctx.accounts.closed_namespace;
+    closed_namespace.namespace = ctx.accounts.exchange.namespace.clone();
+    closed_namespace.exchange = ctx.accounts.exchange.key();
+    closed_namespace.closed_at = Clock::get()?.unix_timestamp;
+    closed_namespace.bump = ctx.bumps.closed_namespace;
+
+    // The exchange is closed with the close = authority constraint
+    msg!("Exchange {} closed", ctx.accounts.exchange.namespace);
+
+    Ok(())
+}
@@ -0,0 +1,88 @@
+use anchor_lang::prelude::*;
+use anchor_spl::token_interface::{
+    TokenAccount, Mint, TokenInterface, CloseAccount, close_account
+};
+use crate::state::*;
+use crate::errors::*;
+use crate::constants::*;
+
+#[derive(Accounts)]
+pub struct CloseFeeVault<'info> {
+    #[account(
+        mut,
+        seeds = [
+            FEE_VAULT_SEED,
+            exchange.key().as_ref(),
+            payment_mint.key().as_ref()
+        ],
+        bump = fee_vault.bump,
+        has_one = exchange @ VoucherExchangeError::InvalidExchange,
+        close = authority
+    )]
+    pub fee_vault: Account<'info, FeeVault>,
+
+    #[account(
+        mut,
+        address = fee_vault.vault @ VoucherExchangeError::InvalidFeeAccount,
+        constraint = vault_token_account.amount == 0 @ VoucherExchangeError::FeeVaultNotEmpty
+    )]
+    pub vault_token_account: InterfaceAccount<'info, TokenAccount>,
+
+    #[account(
+        mut,
+        constraint = exchange.has_role(ExchangeRole::FeeAdmin, &authority.key()) @ VoucherExchangeError::MissingRole,
+    )]
+    pub authority: Signer<'info>,
+
+    #[account(
+        mut,
+        seeds = [EXCHANGE_SEED, exchange.namespace.as_bytes()],
+        bump = exchange.bump,
+        constraint = exchange.winding_down @ VoucherExchangeError::ExchangeNotWindingDown
+    )]
+    pub exchange: Account<'info, VoucherExchange>,
+
+    pub payment_mint: InterfaceAccount<'info, Mint>,
+
+    pub token_program: Interface<'info, TokenInterface>,
+}
+
+// Only while winding down, as trades still pay fees into the vault. Fees have to be
+// withdrawn first, so closing a vault never strands a balance.
+pub fn handler(
+    ctx: Context<CloseFeeVault>,
+) -> Result<()> {
+    let exchange_key = ctx.accounts.exchange.key();
+    let payment_mint_key = ctx.accounts.payment_mint.key();
+    let fee_vault_seeds = &[
+        FEE_VAULT_SEED,
+        exchange_key.as_ref(),
+        payment_mint_key.as_ref(),
+        &[ctx.accounts.fee_vault.bump],
+    ];
+    let signer_seeds = &[&fee_vault_seeds[..]];
+
+    // Close the vault token account and return rent to the authority
+    close_account(
+        CpiContext::new_with_signer(
+            ctx.accounts.token_program.to_account_info(),
+            CloseAccount {
+                account: ctx.accounts.vault_token_account.to_account_info(),
+                destination: ctx.accounts.authority.to_account_info(),
+                authority: ctx.accounts.fee_vault.to_account_info(),
+            },
+            signer_seeds,
+        )
+    )?;
+
+    let exchange = &mut ctx.accounts.exchange;
+    exchange.fee_vault_count = exchange
+        .fee_vault_count
+        .checked_sub(1)
+        .ok_or(VoucherExchangeError::MathOverflow)?;
+
+    // The fee vault is closed with the close = authority constraint
+    msg!("Fee vault of mint {} closed", payment_mint_key);
+
+    Ok(())
+}
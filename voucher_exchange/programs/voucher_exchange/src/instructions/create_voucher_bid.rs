@@ -3,9 +3,13 @@ use anchor_spl::token_interface::{
     TokenAccount, Mint, TokenInterface,
     TransferChecked
 };
+use anchor_spl::metadata::Metadata;
+use anchor_spl::metadata::mpl_token_metadata;
 use crate::state::*;
 use crate::errors::*;
 use crate::constants::*;
+use crate::collection::*;
+use crate::settlement::*;
 
 #[derive(Accounts)]
 #[instruction(price: u64, escrow_bump: u8)]
@@ -16,6 +20,7 @@ pub struct CreateVoucherBid<'info> {
         space = VoucherBid::SIZE,
         seeds = [
         VOUCHER_BID_SEED,
+        exchange.key().as_ref(),
         bidder.key().as_ref(),
         nft_mint.key().as_ref()
         ],
@@ -23,16 +28,70 @@ pub struct CreateVoucherBid<'info> {
     )]
     pub bid: Account<'info, VoucherBid>,
 
-    #[account(mut)]
+    #[account(
+        mut,
+        seeds = [EXCHANGE_SEED, exchange.namespace.as_bytes()],
+        bump = exchange.bump,
+        constraint = !exchange.is_paused(PAUSE_CREATE_BID) @ VoucherExchangeError::ExchangePaused,
+        constraint = !exchange.winding_down @ VoucherExchangeError::ExchangeWindingDown
+    )]
     pub exchange: Account<'info, VoucherExchange>,
 
     #[account(mut)]
     pub bidder: Signer<'info>,
 
+    /// CHECK: BlockedWallet PDA of the bidder, must not exist
+    #[account(
+        seeds = [
+            BLOCKED_WALLET_SEED,
+            exchange.key().as_ref(),
+            bidder.key().as_ref()
+        ],
+        bump,
+        constraint = bidder_blocked_wallet.data_is_empty() @ VoucherExchangeError::WalletBlocked
+    )]
+    pub bidder_blocked_wallet: UncheckedAccount<'info>,
+
     pub nft_mint: InterfaceAccount<'info, Mint>,
 
+    /// CHECK: Metaplex metadata PDA of the NFT, parsed in the handler if it exists
+    #[account(
+        seeds = [
+            mpl_token_metadata::accounts::Metadata::PREFIX,
+            token_metadata_program.key().as_ref(),
+            nft_mint.key().as_ref()
+        ],
+        seeds::program = token_metadata_program.key(),
+        bump
+    )]
+    pub nft_metadata: UncheckedAccount<'info>,
+
+    /// CHECK: Override for the NFT's verified collection, validated in the handler
+    pub collection_config: Option<UncheckedAccount<'info>>,
+
+    // Approval of the NFT's verified collection on this exchange
+    #[account(
+        seeds = [
+            APPROVED_COLLECTION_SEED,
+            exchange.key().as_ref(),
+            approved_collection.collection_mint.as_ref()
+        ],
+        bump = approved_collection.bump
+    )]
+    pub approved_collection: Account<'info, ApprovedCollection>,
+
     pub payment_mint: InterfaceAccount<'info, Mint>,
 
+    #[account(
+        seeds = [
+            PAYMENT_MINT_CONFIG_SEED,
+            exchange.key().as_ref(),
+            payment_mint.key().as_ref()
+        ],
+        bump = payment_mint_config.bump
+    )]
+    pub payment_mint_config: Account<'info, PaymentMintConfig>,
+
     #[account(
         mut,
         constraint = bidder_token_account.mint == payment_mint.key() @ VoucherExchangeError::InvalidPrice,
@@ -45,6 +104,7 @@ pub struct CreateVoucherBid<'info> {
         payer = bidder,
         seeds = [
         ESCROW_SEED,
+        exchange.key().as_ref(),
         bidder.key().as_ref(),
         nft_mint.key().as_ref()
         ],
@@ -55,6 +115,7 @@ pub struct CreateVoucherBid<'info> {
     pub escrow_account: InterfaceAccount<'info, TokenAccount>,
 
     pub token_program: Interface<'info, TokenInterface>,
+    pub token_metadata_program: Program<'info, Metadata>,
     pub system_program: Program<'info, System>,
     pub rent: Sysvar<'info, Rent>,
 }
@@ -67,29 +128,98 @@ pub fn handler(
     // Check price is valid
     require!(price > 0, VoucherExchangeError::InvalidPrice);
 
-    // Check sufficient balance
+    // Check the payment mint is accepted at this price
+    let payment_mint_config = &ctx.accounts.payment_mint_config;
     require!(
-        ctx.accounts.bidder_token_account.amount >= price,
-        VoucherExchangeError::InsufficientFunds
+        ctx.accounts.payment_mint.decimals == payment_mint_config.decimals,
+        VoucherExchangeError::InvalidPaymentMintDecimals
+    );
+    require!(
+        price >= payment_mint_config.min_price,
+        VoucherExchangeError::PriceBelowMinimum
     );
 
-    // Transfer token to escrow using transfer_checked
-    anchor_spl::token_interface::transfer_checked(
-        CpiContext::new(
-            ctx.accounts.token_program.to_account_info(),
-            TransferChecked {
-                from: ctx.accounts.bidder_token_account.to_account_info(),
-                mint: ctx.accounts.payment_mint.to_account_info(),
-                to: ctx.accounts.escrow_account.to_account_info(),
-                authority: ctx.accounts.bidder.to_account_info(),
-            },
-        ),
-        price,
-        ctx.accounts.payment_mint.decimals,
+    // Check the NFT belongs to an approved verified collection
+    let metadata = read_metadata(&ctx.accounts.nft_metadata)?;
+    require_approved_collection(metadata.as_ref(), &ctx.accounts.approved_collection)?;
+
+    // Check price is within the collection range, if the collection has an override
+    let collection_config = load_collection_config(
+        &ctx.accounts.exchange.key(),
+        metadata.as_ref(),
+        ctx.accounts.collection_config.as_deref(),
     )?;
+    if let Some(config) = &collection_config {
+        require!(config.is_price_allowed(price), VoucherExchangeError::PriceOutOfRange);
+    }
+
+    // The bidder is the maker, so escrow the maker fee at the current rate with the bid
+    let terms = SaleTerms::new(&ctx.accounts.exchange, collection_config.as_ref());
+    let fee_deposit = calculate_fee(price, terms.maker_fee_basis_points)?;
+    let escrow_amount = price.checked_add(fee_deposit).ok_or(VoucherExchangeError::MathOverflow)?;
+
+    // Bidding again on the same NFT replaces the open bid, so only the difference
+    // to what its escrow already holds is moved. A refunded bid left an empty escrow
+    // and opens a new bid.
+    let opens_bid = !ctx.accounts.bid.active;
+    let escrowed_amount = if opens_bid {
+        0
+    } else {
+        require!(!ctx.accounts.bid.requires_refund, VoucherExchangeError::BidMarkedForRefund);
+        ctx.accounts.bid.escrowed_amount()?
+    };
+
+    if escrow_amount > escrowed_amount {
+        let top_up = escrow_amount - escrowed_amount;
+
+        // Check sufficient balance
+        require!(
+            ctx.accounts.bidder_token_account.amount >= top_up,
+            VoucherExchangeError::InsufficientFunds
+        );
+
+        // Transfer token to escrow using transfer_checked
+        anchor_spl::token_interface::transfer_checked(
+            CpiContext::new(
+                ctx.accounts.token_program.to_account_info(),
+                TransferChecked {
+                    from: ctx.accounts.bidder_token_account.to_account_info(),
+                    mint: ctx.accounts.payment_mint.to_account_info(),
+                    to: ctx.accounts.escrow_account.to_account_info(),
+                    authority: ctx.accounts.bidder.to_account_info(),
+                },
+            ),
+            top_up,
+            ctx.accounts.payment_mint.decimals,
+        )?;
+    } else if escrowed_amount > escrow_amount {
+        // Return the excess of a lowered bid from escrow
+        let exchange_key = ctx.accounts.exchange.key();
+        let bidder_key = ctx.accounts.bidder.key();
+        let nft_mint_key = ctx.accounts.nft_mint.key();
+        let escrow_seeds = &[
+            ESCROW_SEED,
+            exchange_key.as_ref(),
+            bidder_key.as_ref(),
+            nft_mint_key.as_ref(),
+            &[ctx.bumps.escrow_account],
+        ];
+        let source = PaymentSource {
+            token_program: ctx.accounts.token_program.to_account_info(),
+            mint: &ctx.accounts.payment_mint,
+            from: ctx.accounts.escrow_account.to_account_info(),
+            authority: ctx.accounts.escrow_account.to_account_info(),
+            signer_seeds: &[&escrow_seeds[..]],
+        };
+        source.pay(
+            ctx.accounts.bidder_token_account.to_account_info(),
+            escrowed_amount - escrow_amount,
+        )?;
+    }
 
     // Create new bid
     let bid = &mut ctx.accounts.bid;
+    bid.exchange = ctx.accounts.exchange.key();
     bid.bidder = ctx.accounts.bidder.key();
     bid.nft_mint = ctx.accounts.nft_mint.key();
     bid.price = price;
@@ -99,10 +229,17 @@ pub fn handler(
     bid.requires_refund = false;  // Initially doesn't require refund
     bid.bump = ctx.bumps.bid;
     bid.escrow_bump = escrow_bump;
+    bid.fee_deposit = fee_deposit;
+    bid.version = BID_VERSION;
 
-    // Increment total bids
-    let exchange = &mut ctx.accounts.exchange;
-    exchange.total_bids = exchange.total_bids.checked_add(1).unwrap();
+    // Number and count the bid when it opens, a replaced bid keeps its nonce and is already counted
+    if opens_bid {
+        let exchange = &mut ctx.accounts.exchange;
+        ctx.accounts.bid.nonce = exchange.next_escrow_nonce()?;
+        exchange.total_bids = exchange.total_bids
+            .checked_add(1)
+            .ok_or(VoucherExchangeError::MathOverflow)?;
+    }
 
     Ok(())
 }
@@ -2,12 +2,15 @@ use anchor_lang::prelude::*;
 use anchor_spl::token_interface::{
     TokenAccount, Mint, TokenInterface, TransferChecked, transfer_checked
 };
+use anchor_spl::metadata::Metadata;
+use anchor_spl::metadata::mpl_token_metadata;
 use crate::state::*;
 use crate::errors::*;
 use crate::constants::*;
+use crate::collection::*;
 
 #[derive(Accounts)]
-#[instruction(price: u64)]
+#[instruction(price: u64, payout_splits: Vec<PayoutSplit>)]
 pub struct CreateVoucherListing<'info> {
     #[account(
         init,
@@ -15,6 +18,7 @@ pub struct CreateVoucherListing<'info> {
         space = VoucherListing::SIZE,
         seeds = [
             VOUCHER_LISTING_SEED,
+            exchange.key().as_ref(),
             owner.key().as_ref(),
             nft_mint.key().as_ref()
         ],
@@ -22,14 +26,58 @@ pub struct CreateVoucherListing<'info> {
     )]
     pub listing: Account<'info, VoucherListing>,
 
-    #[account(mut)]
+    #[account(
+        mut,
+        seeds = [EXCHANGE_SEED, exchange.namespace.as_bytes()],
+        bump = exchange.bump,
+        constraint = !exchange.is_paused(PAUSE_CREATE_LISTING) @ VoucherExchangeError::ExchangePaused,
+        constraint = !exchange.winding_down @ VoucherExchangeError::ExchangeWindingDown
+    )]
     pub exchange: Account<'info, VoucherExchange>,
 
     #[account(mut)]
     pub owner: Signer<'info>,
 
+    /// CHECK: BlockedWallet PDA of the owner, must not exist
+    #[account(
+        seeds = [
+            BLOCKED_WALLET_SEED,
+            exchange.key().as_ref(),
+            owner.key().as_ref()
+        ],
+        bump,
+        constraint = owner_blocked_wallet.data_is_empty() @ VoucherExchangeError::WalletBlocked
+    )]
+    pub owner_blocked_wallet: UncheckedAccount<'info>,
+
     pub nft_mint: InterfaceAccount<'info, Mint>,
 
+    /// CHECK: Metaplex metadata PDA of the NFT, parsed in the handler if it exists
+    #[account(
+        seeds = [
+            mpl_token_metadata::accounts::Metadata::PREFIX,
+            token_metadata_program.key().as_ref(),
+            nft_mint.key().as_ref()
+        ],
+        seeds::program = token_metadata_program.key(),
+        bump
+    )]
+    pub nft_metadata: UncheckedAccount<'info>,
+
+    /// CHECK: Override for the NFT's verified collection, validated in the handler
+    pub collection_config: Option<UncheckedAccount<'info>>,
+
+    // Approval of the NFT's verified collection on this exchange
+    #[account(
+        seeds = [
+            APPROVED_COLLECTION_SEED,
+            exchange.key().as_ref(),
+            approved_collection.collection_mint.as_ref()
+        ],
+        bump = approved_collection.bump
+    )]
+    pub approved_collection: Account<'info, ApprovedCollection>,
+
     #[account(
         mut,
         constraint = owner_nft_account.mint == nft_mint.key() @ VoucherExchangeError::NotNFTOwner,
@@ -43,6 +91,7 @@ pub struct CreateVoucherListing<'info> {
         payer = owner,
         seeds = [
             ESCROW_SEED,
+            exchange.key().as_ref(),
             nft_mint.key().as_ref()
         ],
         bump,
@@ -52,20 +101,148 @@ pub struct CreateVoucherListing<'info> {
     pub escrow_nft_account: InterfaceAccount<'info, TokenAccount>,
 
     pub payment_mint: InterfaceAccount<'info, Mint>,
+
+    #[account(
+        seeds = [
+            PAYMENT_MINT_CONFIG_SEED,
+            exchange.key().as_ref(),
+            payment_mint.key().as_ref()
+        ],
+        bump = payment_mint_config.bump
+    )]
+    pub payment_mint_config: Account<'info, PaymentMintConfig>,
+
     pub token_program: Interface<'info, TokenInterface>,
+    pub token_metadata_program: Program<'info, Metadata>,
     pub system_program: Program<'info, System>,
     pub rent: Sysvar<'info, Rent>,
 }
 
+#[derive(AnchorSerialize, AnchorDeserialize)]
+pub struct EnglishAuctionParams {
+    pub min_increment: u64,
+    pub end_time: i64,
+}
+
 pub fn handler(
     ctx: Context<CreateVoucherListing>,
     price: u64,
+    payout_splits: Vec<PayoutSplit>,
+    expires_at: Option<i64>,
+    reserved_buyer: Option<Pubkey>,
+    dutch_auction: Option<DutchAuction>,
+    english_auction: Option<EnglishAuctionParams>,
 ) -> Result<()> {
     // Check price is valid
     require!(price > 0, VoucherExchangeError::InvalidPrice);
 
+    // Check the English auction, if any, is open-ended bidding from the listing price as reserve
+    if let Some(auction) = &english_auction {
+        require!(
+            auction.min_increment > 0 && auction.end_time > Clock::get()?.unix_timestamp,
+            VoucherExchangeError::InvalidEnglishAuction
+        );
+        require!(
+            expires_at.is_none() && reserved_buyer.is_none() && dutch_auction.is_none(),
+            VoucherExchangeError::InvalidEnglishAuction
+        );
+    }
+
+    // Check the Dutch auction, if any, falls from the listing price over a valid schedule
+    // that has not ended and lasts at most MAX_DUTCH_AUCTION_DURATION
+    if let Some(auction) = &dutch_auction {
+        require!(
+            auction.end_price > 0 && auction.end_price < price,
+            VoucherExchangeError::InvalidDutchAuction
+        );
+        let now = Clock::get()?.unix_timestamp;
+        require!(
+            auction.end_time > now && auction.start_time < auction.end_time,
+            VoucherExchangeError::InvalidDutchAuction
+        );
+        let duration = auction.end_time
+            .checked_sub(auction.start_time)
+            .ok_or(VoucherExchangeError::InvalidDutchAuction)?;
+        require!(
+            duration <= MAX_DUTCH_AUCTION_DURATION,
+            VoucherExchangeError::InvalidDutchAuction
+        );
+        let latest_start = now
+            .checked_add(MAX_DUTCH_AUCTION_DURATION)
+            .ok_or(VoucherExchangeError::MathOverflow)?;
+        require!(
+            auction.start_time <= latest_start,
+            VoucherExchangeError::InvalidDutchAuction
+        );
+        if let PriceDecay::Stepped { interval } = auction.decay {
+            require!(
+                interval > 0 && interval <= duration,
+                VoucherExchangeError::InvalidDutchAuction
+            );
+        }
+    }
+    // Lowest price the listing can sell at
+    let floor_price = dutch_auction.map_or(price, |auction| auction.end_price);
+
+    // Check expiry, if any, is in the future
+    if let Some(expires_at) = expires_at {
+        require!(
+            expires_at > Clock::get()?.unix_timestamp,
+            VoucherExchangeError::InvalidExpiry
+        );
+    }
+
+    // Check the payment mint is accepted at this price
+    let payment_mint_config = &ctx.accounts.payment_mint_config;
+    require!(
+        ctx.accounts.payment_mint.decimals == payment_mint_config.decimals,
+        VoucherExchangeError::InvalidPaymentMintDecimals
+    );
+    require!(
+        floor_price >= payment_mint_config.min_price,
+        VoucherExchangeError::PriceBelowMinimum
+    );
+
+    // Check payout splits, if any, cover exactly the whole proceeds
+    if !payout_splits.is_empty() {
+        require!(
+            payout_splits.len() <= MAX_PAYOUT_SPLITS,
+            VoucherExchangeError::InvalidPayoutSplits
+        );
+        require!(
+            payout_splits.iter().all(|split| split.basis_points > 0),
+            VoucherExchangeError::InvalidPayoutSplits
+        );
+        let total_basis_points: u32 = payout_splits
+            .iter()
+            .map(|split| split.basis_points as u32)
+            .sum();
+        require!(
+            total_basis_points == BASIS_POINTS_DIVISOR as u32,
+            VoucherExchangeError::InvalidPayoutSplits
+        );
+    }
+
+    // Check the NFT belongs to an approved verified collection
+    let metadata = read_metadata(&ctx.accounts.nft_metadata)?;
+    require_approved_collection(metadata.as_ref(), &ctx.accounts.approved_collection)?;
+
+    // Check price is within the collection range, if the collection has an override
+    let collection_config = load_collection_config(
+        &ctx.accounts.exchange.key(),
+        metadata.as_ref(),
+        ctx.accounts.collection_config.as_deref(),
+    )?;
+    if let Some(config) = &collection_config {
+        require!(
+            config.is_price_allowed(price) && config.is_price_allowed(floor_price),
+            VoucherExchangeError::PriceOutOfRange
+        );
+    }
+
     // Create new listing
     let listing = &mut ctx.accounts.listing;
+    listing.exchange = ctx.accounts.exchange.key();
     listing.owner = ctx.accounts.owner.key();
     listing.nft_mint = ctx.accounts.nft_mint.key();
     listing.nft_account = ctx.accounts.escrow_nft_account.key(); // Store escrow account instead
@@ -73,6 +250,19 @@ pub fn handler(
     listing.payment_mint = ctx.accounts.payment_mint.key();
     listing.active = true;
     listing.bump = ctx.bumps.listing;
+    listing.payout_splits = payout_splits;
+    listing.version = LISTING_VERSION;
+    listing.expires_at = expires_at.unwrap_or(0);
+    listing.reserved_buyer = reserved_buyer;
+    listing.dutch_auction = dutch_auction;
+    listing.english_auction = english_auction.map(|auction| EnglishAuction {
+        min_increment: auction.min_increment,
+        end_time: auction.end_time,
+        top_bidder: Pubkey::default(),
+        top_bid: 0,
+        fee_deposit: 0,
+    });
+    listing.nonce = ctx.accounts.exchange.next_escrow_nonce()?;
     // Removed: listing.escrow_bump = ctx.bumps.escrow_nft_account;
 
     // Transfer NFT to the escrow account
@@ -92,7 +282,9 @@ pub fn handler(
 
     // Increment total listings
     let exchange = &mut ctx.accounts.exchange;
-    exchange.total_listings = exchange.total_listings.checked_add(1).unwrap();
+    exchange.total_listings = exchange.total_listings
+        .checked_add(1)
+        .ok_or(VoucherExchangeError::MathOverflow)?;
 
     Ok(())
 }
@@ -0,0 +1,153 @@
+use anchor_lang::prelude::*;
+use anchor_spl::token_interface::{
+    TokenAccount, Mint, TokenInterface, TransferChecked, transfer_checked, CloseAccount, close_account
+};
+use crate::state::*;
+use crate::errors::*;
+use crate::constants::*;
+use crate::events::*;
+use crate::rescue::*;
+
+#[derive(Accounts)]
+pub struct ExecuteBidRescue<'info> {
+    #[account(
+        mut,
+        seeds = [
+            RESCUE_REQUEST_SEED,
+            exchange.key().as_ref(),
+            bid.key().as_ref()
+        ],
+        bump = rescue_request.bump,
+        has_one = exchange @ VoucherExchangeError::InvalidExchange,
+        close = authority
+    )]
+    pub rescue_request: Account<'info, RescueRequest>,
+
+    #[account(
+        mut,
+        seeds = [
+            VOUCHER_BID_SEED,
+            exchange.key().as_ref(),
+            bid.bidder.as_ref(),
+            bid.nft_mint.as_ref()
+        ],
+        bump = bid.bump,
+        has_one = exchange @ VoucherExchangeError::InvalidExchange,
+        close = destination
+    )]
+    pub bid: Account<'info, VoucherBid>,
+
+    /// CHECK: Only receives the rescued rent, checked against the announced destination
+    #[account(
+        mut,
+        address = rescue_request.destination @ VoucherExchangeError::InvalidRescueDestination
+    )]
+    pub destination: UncheckedAccount<'info>,
+
+    #[account(
+        mut,
+        constraint = destination_token_account.mint == payment_mint.key() @ VoucherExchangeError::InvalidPaymentMint,
+        constraint = destination_token_account.owner == rescue_request.destination @ VoucherExchangeError::InvalidRescueDestination
+    )]
+    pub destination_token_account: InterfaceAccount<'info, TokenAccount>,
+
+    #[account(
+        address = bid.payment_mint @ VoucherExchangeError::InvalidPaymentMint
+    )]
+    pub payment_mint: InterfaceAccount<'info, Mint>,
+
+    #[account(
+        mut,
+        seeds = [
+            ESCROW_SEED,
+            exchange.key().as_ref(),
+            bid.bidder.as_ref(),
+            bid.nft_mint.as_ref()
+        ],
+        bump = bid.escrow_bump
+    )]
+    pub escrow_account: InterfaceAccount<'info, TokenAccount>,
+
+    #[account(
+        mut,
+        constraint = authority.key() == exchange.authority @ VoucherExchangeError::NotExchangeAuthority,
+    )]
+    pub authority: Signer<'info>,
+
+    #[account(
+        mut,
+        seeds = [EXCHANGE_SEED, exchange.namespace.as_bytes()],
+        bump = exchange.bump
+    )]
+    pub exchange: Account<'info, VoucherExchange>,
+
+    pub token_program: Interface<'info, TokenInterface>,
+}
+
+// Sends the whole escrow balance, which also covers bids already refunded or settled
+// whose escrow was left behind
+pub fn handler(
+    ctx: Context<ExecuteBidRescue>,
+) -> Result<()> {
+    require_rescue_executable(&ctx.accounts.rescue_request, ctx.accounts.bid.nonce)?;
+
+    let exchange_key = ctx.accounts.exchange.key();
+    let bidder_key = ctx.accounts.bid.bidder;
+    let nft_mint_key = ctx.accounts.bid.nft_mint;
+    let escrow_seeds = &[
+        ESCROW_SEED,
+        exchange_key.as_ref(),
+        bidder_key.as_ref(),
+        nft_mint_key.as_ref(),
+        &[ctx.accounts.bid.escrow_bump],
+    ];
+    let signer_seeds = &[&escrow_seeds[..]];
+
+    // Send the escrowed funds to the announced destination
+    let amount = ctx.accounts.escrow_account.amount;
+    transfer_checked(
+        CpiContext::new_with_signer(
+            ctx.accounts.token_program.to_account_info(),
+            TransferChecked {
+                from: ctx.accounts.escrow_account.to_account_info(),
+                mint: ctx.accounts.payment_mint.to_account_info(),
+                to: ctx.accounts.destination_token_account.to_account_info(),
+                authority: ctx.accounts.escrow_account.to_account_info(),
+            },
+            signer_seeds,
+        ),
+        amount,
+        ctx.accounts.payment_mint.decimals,
+    )?;
+
+    // Close the escrow token account, returning its rent with the bid's
+    close_account(
+        CpiContext::new_with_signer(
+            ctx.accounts.token_program.to_account_info(),
+            CloseAccount {
+                account: ctx.accounts.escrow_account.to_account_info(),
+                destination: ctx.accounts.destination.to_account_info(),
+                authority: ctx.accounts.escrow_account.to_account_info(),
+            },
+            signer_seeds,
+        )
+    )?;
+
+    // Refunded bids were already taken off the open bid count
+    if ctx.accounts.bid.active {
+        let exchange = &mut ctx.accounts.exchange;
+        exchange.total_bids = exchange.total_bids
+            .checked_sub(1)
+            .ok_or(VoucherExchangeError::MathOverflow)?;
+    }
+
+    emit!(RescueExecuted {
+        exchange: exchange_key,
+        target: ctx.accounts.bid.key(),
+        destination: ctx.accounts.rescue_request.destination,
+        mint: ctx.accounts.payment_mint.key(),
+        amount,
+    });
+
+    Ok(())
+}
@@ -0,0 +1,147 @@
+use anchor_lang::prelude::*;
+use anchor_spl::token_interface::{
+    TokenAccount, Mint, TokenInterface, TransferChecked, transfer_checked, CloseAccount, close_account
+};
+use crate::state::*;
+use crate::errors::*;
+use crate::constants::*;
+use crate::events::*;
+use crate::rescue::*;
+
+#[derive(Accounts)]
+pub struct ExecuteListingRescue<'info> {
+    #[account(
+        mut,
+        seeds = [
+            RESCUE_REQUEST_SEED,
+            exchange.key().as_ref(),
+            listing.key().as_ref()
+        ],
+        bump = rescue_request.bump,
+        has_one = exchange @ VoucherExchangeError::InvalidExchange,
+        close = authority
+    )]
+    pub rescue_request: Account<'info, RescueRequest>,
+
+    #[account(
+        mut,
+        seeds = [
+            VOUCHER_LISTING_SEED,
+            exchange.key().as_ref(),
+            listing.owner.as_ref(),
+            nft_mint.key().as_ref()
+        ],
+        bump = listing.bump,
+        has_one = exchange @ VoucherExchangeError::InvalidExchange,
+        constraint = !listing.has_auction_bids() @ VoucherExchangeError::AuctionHasBids,
+        close = destination
+    )]
+    pub listing: Account<'info, VoucherListing>,
+
+    /// CHECK: Only receives the rescued rent, checked against the announced destination
+    #[account(
+        mut,
+        address = rescue_request.destination @ VoucherExchangeError::InvalidRescueDestination
+    )]
+    pub destination: UncheckedAccount<'info>,
+
+    #[account(
+        mut,
+        constraint = destination_nft_account.mint == nft_mint.key() @ VoucherExchangeError::InvalidNFTAccount,
+        constraint = destination_nft_account.owner == rescue_request.destination @ VoucherExchangeError::InvalidRescueDestination
+    )]
+    pub destination_nft_account: InterfaceAccount<'info, TokenAccount>,
+
+    pub nft_mint: InterfaceAccount<'info, Mint>,
+
+    #[account(
+        mut,
+        seeds = [
+            ESCROW_SEED,
+            exchange.key().as_ref(),
+            nft_mint.key().as_ref()
+        ],
+        bump,
+        constraint = escrow_nft_account.owner == listing.key() @ VoucherExchangeError::InvalidEscrowOwner
+    )]
+    pub escrow_nft_account: InterfaceAccount<'info, TokenAccount>,
+
+    #[account(
+        mut,
+        constraint = authority.key() == exchange.authority @ VoucherExchangeError::NotExchangeAuthority,
+    )]
+    pub authority: Signer<'info>,
+
+    #[account(
+        mut,
+        seeds = [EXCHANGE_SEED, exchange.namespace.as_bytes()],
+        bump = exchange.bump
+    )]
+    pub exchange: Account<'info, VoucherExchange>,
+
+    pub token_program: Interface<'info, TokenInterface>,
+}
+
+pub fn handler(
+    ctx: Context<ExecuteListingRescue>,
+) -> Result<()> {
+    require_rescue_executable(&ctx.accounts.rescue_request, ctx.accounts.listing.nonce)?;
+
+    let exchange_key = ctx.accounts.exchange.key();
+    let owner_key = ctx.accounts.listing.owner;
+    let nft_mint_key = ctx.accounts.nft_mint.key();
+    let listing_seeds = &[
+        VOUCHER_LISTING_SEED,
+        exchange_key.as_ref(),
+        owner_key.as_ref(),
+        nft_mint_key.as_ref(),
+        &[ctx.accounts.listing.bump],
+    ];
+    let listing_signer = &[&listing_seeds[..]];
+
+    // Send the escrowed NFT to the announced destination
+    let amount = ctx.accounts.escrow_nft_account.amount;
+    transfer_checked(
+        CpiContext::new_with_signer(
+            ctx.accounts.token_program.to_account_info(),
+            TransferChecked {
+                from: ctx.accounts.escrow_nft_account.to_account_info(),
+                to: ctx.accounts.destination_nft_account.to_account_info(),
+                authority: ctx.accounts.listing.to_account_info(),
+                mint: ctx.accounts.nft_mint.to_account_info(),
+            },
+            listing_signer,
+        ),
+        amount,
+        ctx.accounts.nft_mint.decimals,
+    )?;
+
+    // Close the escrow token account, returning its rent with the listing's
+    close_account(
+        CpiContext::new_with_signer(
+            ctx.accounts.token_program.to_account_info(),
+            CloseAccount {
+                account: ctx.accounts.escrow_nft_account.to_account_info(),
+                destination: ctx.accounts.destination.to_account_info(),
+                authority: ctx.accounts.listing.to_account_info(),
+            },
+            listing_signer,
+        )
+    )?;
+
+    // Decrement total listings in exchange
+    let exchange = &mut ctx.accounts.exchange;
+    exchange.total_listings = exchange.total_listings
+        .checked_sub(1)
+        .ok_or(VoucherExchangeError::MathOverflow)?;
+
+    emit!(RescueExecuted {
+        exchange: exchange_key,
+        target: ctx.accounts.listing.key(),
+        destination: ctx.accounts.rescue_request.destination,
+        mint: nft_mint_key,
+        amount,
+    });
+
+    Ok(())
+}
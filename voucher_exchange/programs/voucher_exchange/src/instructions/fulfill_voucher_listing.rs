@@ -5,9 +5,13 @@ use anchor_spl::token_interface::{
 };
 use anchor_spl::token::{Token, Mint, TokenAccount}; // Add standard Token and Mint imports
 use anchor_spl::associated_token::AssociatedToken;
+use anchor_spl::metadata::Metadata;
+use anchor_spl::metadata::mpl_token_metadata;
 use crate::state::*;
 use crate::errors::*;
 use crate::constants::*;
+use crate::settlement::*;
+use crate::collection::*;
 
 #[derive(Accounts)]
 pub struct FulfillVoucherListing<'info> {
@@ -15,11 +19,15 @@ pub struct FulfillVoucherListing<'info> {
         mut,
         seeds = [
             VOUCHER_LISTING_SEED,
+            exchange.key().as_ref(),
             owner.key().as_ref(),
             nft_mint.key().as_ref()
         ],
         bump = listing.bump,
-        constraint = listing.active == true @ VoucherExchangeError::ListingNotActive,
+        has_one = exchange @ VoucherExchangeError::InvalidExchange,
+        constraint = listing.active @ VoucherExchangeError::ListingNotActive,
+        constraint = listing.is_buyer_allowed(&buyer.key()) @ VoucherExchangeError::NotReservedBuyer,
+        constraint = listing.english_auction.is_none() @ VoucherExchangeError::AuctionListing,
         close = owner  // Add this to close the listing account and return rent to owner
     )]
     pub listing: Account<'info, VoucherListing>,
@@ -31,10 +39,49 @@ pub struct FulfillVoucherListing<'info> {
     #[account(mut, constraint = owner.key() == listing.owner @ VoucherExchangeError::NotListingOwner)]
     pub owner: AccountInfo<'info>,
 
+    /// CHECK: BlockedWallet PDA of the buyer, must not exist
+    #[account(
+        seeds = [
+            BLOCKED_WALLET_SEED,
+            exchange.key().as_ref(),
+            buyer.key().as_ref()
+        ],
+        bump,
+        constraint = buyer_blocked_wallet.data_is_empty() @ VoucherExchangeError::WalletBlocked
+    )]
+    pub buyer_blocked_wallet: UncheckedAccount<'info>,
+
+    /// CHECK: BlockedWallet PDA of the owner, must not exist
+    #[account(
+        seeds = [
+            BLOCKED_WALLET_SEED,
+            exchange.key().as_ref(),
+            owner.key().as_ref()
+        ],
+        bump,
+        constraint = owner_blocked_wallet.data_is_empty() @ VoucherExchangeError::WalletBlocked
+    )]
+    pub owner_blocked_wallet: UncheckedAccount<'info>,
+
     // Changed from InterfaceAccount<'info, Mint> to Account<'info, Mint>
     #[account(mut)]
     pub nft_mint: Account<'info, Mint>,
 
+    /// CHECK: Metaplex metadata PDA of the NFT, parsed in the handler if it exists
+    #[account(
+        seeds = [
+            mpl_token_metadata::accounts::Metadata::PREFIX,
+            token_metadata_program.key().as_ref(),
+            nft_mint.key().as_ref()
+        ],
+        seeds::program = token_metadata_program.key(),
+        bump
+    )]
+    pub nft_metadata: UncheckedAccount<'info>,
+
+    /// CHECK: Override for the NFT's verified collection, validated in the handler
+    pub collection_config: Option<UncheckedAccount<'info>>,
+
     #[account(
         mut,
         constraint = escrow_nft_account.mint == nft_mint.key() @ VoucherExchangeError::NotNFTOwner,
@@ -50,7 +97,10 @@ pub struct FulfillVoucherListing<'info> {
     )]
     pub buyer_nft_account: Account<'info, TokenAccount>,
 
-    #[account(mut)]
+    #[account(
+        mut,
+        constraint = payment_mint.key() == listing.payment_mint @ VoucherExchangeError::InvalidPaymentMint
+    )]
     pub payment_mint: InterfaceAccount<'info, MintInterface>,
 
     #[account(
@@ -67,7 +117,46 @@ pub struct FulfillVoucherListing<'info> {
     )]
     pub owner_payment_account: InterfaceAccount<'info, TokenAccountInterface>,
 
+    #[account(
+        mut,
+        seeds = [
+            FEE_VAULT_SEED,
+            exchange.key().as_ref(),
+            payment_mint.key().as_ref()
+        ],
+        bump = fee_vault.bump,
+        has_one = exchange @ VoucherExchangeError::InvalidExchange
+    )]
+    pub fee_vault: Account<'info, FeeVault>,
+
+    #[account(
+        mut,
+        address = fee_vault.vault @ VoucherExchangeError::InvalidFeeAccount
+    )]
+    pub fee_vault_token_account: InterfaceAccount<'info, TokenAccountInterface>,
+
+    // Optional referrer approved on this exchange, paid a share of the fee
+    #[account(mut)]
+    pub referrer: Option<Account<'info, Referrer>>,
+
     #[account(mut)]
+    pub referrer_payment_account: Option<InterfaceAccount<'info, TokenAccountInterface>>,
+
+    // Optional OPT holder discount, applied to the buyer's fee
+    #[account(
+        seeds = [FEE_DISCOUNT_SEED, exchange.key().as_ref()],
+        bump = fee_discount_config.bump
+    )]
+    pub fee_discount_config: Option<Account<'info, FeeDiscountConfig>>,
+
+    pub buyer_opt_account: Option<InterfaceAccount<'info, TokenAccountInterface>>,
+
+    #[account(
+        mut,
+        seeds = [EXCHANGE_SEED, exchange.namespace.as_bytes()],
+        bump = exchange.bump,
+        constraint = !exchange.is_paused(PAUSE_FULFILL_LISTING) @ VoucherExchangeError::ExchangePaused
+    )]
     pub exchange: Account<'info, VoucherExchange>,
 
     pub token_program: Interface<'info, TokenInterface>,
@@ -75,50 +164,102 @@ pub struct FulfillVoucherListing<'info> {
     // Use the standard Token Program directly
     pub token_nft_program: Program<'info, Token>,
 
+    pub token_metadata_program: Program<'info, Metadata>,
     pub associated_token_program: Program<'info, AssociatedToken>,
     pub system_program: Program<'info, System>
 }
 
-pub fn handler(ctx: Context<FulfillVoucherListing>) -> Result<()> {
-    // Check balance
-    let price = ctx.accounts.listing.price;
+// Remaining accounts: payment token accounts of the verified creators, see `pay_royalties`,
+// followed by one payment token account per payout split of the listing.
+// The lister is the maker and the buyer the taker: the taker fee is paid on top
+// of the price and the maker fee comes out of the seller proceeds.
+// `max_price` caps the price paid, guarding the buyer against a Dutch auction price
+// read at a different time or a listing repriced before the purchase lands.
+pub fn handler<'info>(
+    ctx: Context<'_, '_, 'info, 'info, FulfillVoucherListing<'info>>,
+    max_price: u64,
+) -> Result<()> {
+    let now = Clock::get()?.unix_timestamp;
+
+    // Check listing has not expired
     require!(
-        ctx.accounts.buyer_payment_account.amount >= price,
-        VoucherExchangeError::InsufficientFunds
+        !ctx.accounts.listing.is_expired(now),
+        VoucherExchangeError::ListingExpired
     );
 
+    // Resolve the current price and check it is within the buyer's maximum
+    let price = ctx.accounts.listing.current_price(now)?;
+    require!(price <= max_price, VoucherExchangeError::PriceAboveMaximum);
+
     // Check NFT amount
     require!(
         ctx.accounts.escrow_nft_account.amount == 1,
         VoucherExchangeError::InsufficientNFTAmount
     );
 
-    let payment_token_program = ctx.accounts.token_program.to_account_info();
+    // Resolve fee and royalty terms, preferring the collection override
+    // and applying the buyer's OPT holder discount to the taker fee
+    let metadata = read_metadata(&ctx.accounts.nft_metadata)?;
+    let collection_config = load_collection_config(
+        &ctx.accounts.exchange.key(),
+        metadata.as_ref(),
+        ctx.accounts.collection_config.as_deref(),
+    )?;
+    let mut terms = SaleTerms::new(&ctx.accounts.exchange, collection_config.as_ref());
+    terms.apply_taker_discount(fee_discount_basis_points(
+        &ctx.accounts.buyer.key(),
+        ctx.accounts.fee_discount_config.as_deref(),
+        ctx.accounts.buyer_opt_account.as_ref(),
+    )?);
 
-    // 1. Transfer full payment from buyer to seller (no fees)
-    transfer_checked(
-        CpiContext::new(
-            payment_token_program,
-            TransferChecked {
-                from: ctx.accounts.buyer_payment_account.to_account_info(),
-                mint: ctx.accounts.payment_mint.to_account_info(),
-                to: ctx.accounts.owner_payment_account.to_account_info(),
-                authority: ctx.accounts.buyer.to_account_info(),
-            },
-        ),
+    let sale = Sale {
+        nft_mint: ctx.accounts.nft_mint.key(),
+        seller: ctx.accounts.owner.key(),
+        buyer: ctx.accounts.buyer.key(),
+        buyer_is_maker: false,
         price,
-        ctx.accounts.payment_mint.decimals,
-    )?;
+        terms,
+        metadata: metadata.as_ref(),
+        buyer_fee_deposit: None,
+    };
+
+    // Check balance covers the price and the taker fee
+    require!(
+        ctx.accounts.buyer_payment_account.amount >= price.checked_add(sale.buyer_fee()?).ok_or(VoucherExchangeError::MathOverflow)?,
+        VoucherExchangeError::InsufficientFunds
+    );
+
+    let source = PaymentSource {
+        token_program: ctx.accounts.token_program.to_account_info(),
+        mint: &ctx.accounts.payment_mint,
+        from: ctx.accounts.buyer_payment_account.to_account_info(),
+        authority: ctx.accounts.buyer.to_account_info(),
+        signer_seeds: &[],
+    };
+
+    // 1. Transfer fees, royalties and the seller proceeds from the buyer
+    settle_sale(&source, &sale, SaleAccounts {
+        exchange: &mut ctx.accounts.exchange,
+        fee_vault: &mut ctx.accounts.fee_vault,
+        fee_vault_token_account: ctx.accounts.fee_vault_token_account.to_account_info(),
+        referrer: ctx.accounts.referrer.as_mut(),
+        referrer_payment_account: ctx.accounts.referrer_payment_account.as_ref(),
+        seller_payment_account: ctx.accounts.owner_payment_account.to_account_info(),
+        payout_splits: &ctx.accounts.listing.payout_splits,
+        remaining_accounts: ctx.remaining_accounts,
+    })?;
 
     let nft_token_program = ctx.accounts.token_nft_program.to_account_info();
 
     // 2. Transfer NFT from escrow to buyer
+    let exchange_key = ctx.accounts.exchange.key();
     let owner_key = ctx.accounts.owner.key();
     let nft_mint_key = ctx.accounts.nft_mint.key();
     let bump = ctx.accounts.listing.bump;
 
     let listing_seeds = &[
         VOUCHER_LISTING_SEED,
+        exchange_key.as_ref(),
         owner_key.as_ref(),
         nft_mint_key.as_ref(),
         &[bump],
@@ -156,8 +297,9 @@ pub fn handler(ctx: Context<FulfillVoucherListing>) -> Result<()> {
 
     // Decrement total listings in exchange
     let exchange = &mut ctx.accounts.exchange;
-    exchange.total_listings = exchange.total_listings.checked_sub(1).unwrap_or(0);
-
+    exchange.total_listings = exchange.total_listings
+        .checked_sub(1)
+        .ok_or(VoucherExchangeError::MathOverflow)?;
 
     Ok(())
 }
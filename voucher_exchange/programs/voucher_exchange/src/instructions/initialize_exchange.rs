@@ -1,19 +1,29 @@
 use anchor_lang::prelude::*;
 use anchor_spl::token_interface::{TokenInterface};
 use crate::state::*;
+use crate::errors::*;
 use crate::constants::*;
 
 #[derive(Accounts)]
+#[instruction(namespace: String)]
 pub struct InitializeExchange<'info> {
     #[account(
         init,
         payer = authority,
         space = VoucherExchange::SIZE,
-        seeds = [EXCHANGE_SEED],
+        seeds = [EXCHANGE_SEED, namespace.as_bytes()],
         bump
     )]
     pub exchange: Account<'info, VoucherExchange>,
 
+    /// CHECK: Tombstone left by close_exchange, whose namespace cannot be reused
+    #[account(
+        seeds = [CLOSED_NAMESPACE_SEED, namespace.as_bytes()],
+        bump,
+        constraint = closed_namespace.data_is_empty() @ VoucherExchangeError::NamespaceClosed
+    )]
+    pub closed_namespace: UncheckedAccount<'info>,
+
     #[account(mut)]
     pub authority: Signer<'info>,
 
@@ -23,13 +33,55 @@ pub struct InitializeExchange<'info> {
 }
 
 pub fn handler(
-    ctx: Context<InitializeExchange>
+    ctx: Context<InitializeExchange>,
+    namespace: String,
+    maker_fee_basis_points: u16,
+    taker_fee_basis_points: u16,
+    fee_recipient: Pubkey,
+    fee_update_delay: i64,
 ) -> Result<()> {
+    // Check namespace fits in a PDA seed
+    require!(
+        !namespace.is_empty() && namespace.len() <= MAX_NAMESPACE_LEN,
+        VoucherExchangeError::InvalidNamespace
+    );
+
+    // Check fees are within the allowed range
+    require!(
+        maker_fee_basis_points <= MAX_FEE_BASIS_POINTS && taker_fee_basis_points <= MAX_FEE_BASIS_POINTS,
+        VoucherExchangeError::FeeTooHigh
+    );
+
+    // Check fee update delay leaves sellers time to react to a proposal
+    require!(
+        fee_update_delay >= MIN_FEE_UPDATE_DELAY,
+        VoucherExchangeError::InvalidFeeUpdateDelay
+    );
+
     let exchange = &mut ctx.accounts.exchange;
+    exchange.namespace = namespace;
     exchange.authority = ctx.accounts.authority.key();
+    exchange.pending_authority = Pubkey::default();
+    exchange.fee_admin = Pubkey::default();
+    exchange.refund_operator = Pubkey::default();
+    exchange.pauser = Pubkey::default();
+    exchange.collection_curator = Pubkey::default();
     exchange.total_listings = 0;
     exchange.total_bids = 0;
+    exchange.maker_fee_basis_points = maker_fee_basis_points;
+    exchange.taker_fee_basis_points = taker_fee_basis_points;
+    exchange.fee_recipient = fee_recipient;
+    exchange.fee_update_delay = fee_update_delay;
+    exchange.clear_pending_fee_update();
+    exchange.royalty_policy = RoyaltyPolicy::Enforced;
+    exchange.royalty_cap_basis_points = 0;
+    exchange.fee_burn_basis_points = 0;
+    exchange.total_burned = 0;
+    exchange.paused_operations = 0;
     exchange.bump = ctx.bumps.exchange;
+    exchange.version = EXCHANGE_VERSION;
+    exchange.winding_down = false;
+    exchange.fee_vault_count = 0;
 
     Ok(())
 }
\ No newline at end of file
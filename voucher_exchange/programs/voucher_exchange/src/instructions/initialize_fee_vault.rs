@@ -0,0 +1,72 @@
+use anchor_lang::prelude::*;
+use anchor_spl::associated_token::AssociatedToken;
+use anchor_spl::token_interface::{TokenAccount, Mint, TokenInterface};
+use crate::state::*;
+use crate::errors::*;
+use crate::constants::*;
+
+#[derive(Accounts)]
+pub struct InitializeFeeVault<'info> {
+    #[account(
+        init,
+        payer = authority,
+        space = FeeVault::SIZE,
+        seeds = [
+            FEE_VAULT_SEED,
+            exchange.key().as_ref(),
+            payment_mint.key().as_ref()
+        ],
+        bump
+    )]
+    pub fee_vault: Account<'info, FeeVault>,
+
+    // The vault address is predictable, so it may already have been created by anyone
+    #[account(
+        init_if_needed,
+        payer = authority,
+        associated_token::mint = payment_mint,
+        associated_token::authority = fee_vault,
+        associated_token::token_program = token_program
+    )]
+    pub vault_token_account: InterfaceAccount<'info, TokenAccount>,
+
+    #[account(
+        mut,
+        constraint = exchange.has_role(ExchangeRole::FeeAdmin, &authority.key()) @ VoucherExchangeError::MissingRole,
+    )]
+    pub authority: Signer<'info>,
+
+    #[account(
+        mut,
+        seeds = [EXCHANGE_SEED, exchange.namespace.as_bytes()],
+        bump = exchange.bump
+    )]
+    pub exchange: Account<'info, VoucherExchange>,
+
+    pub payment_mint: InterfaceAccount<'info, Mint>,
+
+    pub token_program: Interface<'info, TokenInterface>,
+    pub associated_token_program: Program<'info, AssociatedToken>,
+    pub system_program: Program<'info, System>,
+}
+
+pub fn handler(
+    ctx: Context<InitializeFeeVault>,
+) -> Result<()> {
+    let fee_vault = &mut ctx.accounts.fee_vault;
+    fee_vault.exchange = ctx.accounts.exchange.key();
+    fee_vault.payment_mint = ctx.accounts.payment_mint.key();
+    fee_vault.vault = ctx.accounts.vault_token_account.key();
+    fee_vault.lifetime_fees = 0;
+    fee_vault.unclaimed_fees = 0;
+    fee_vault.total_withdrawn = 0;
+    fee_vault.bump = ctx.bumps.fee_vault;
+
+    let exchange = &mut ctx.accounts.exchange;
+    exchange.fee_vault_count = exchange
+        .fee_vault_count
+        .checked_add(1)
+        .ok_or(VoucherExchangeError::MathOverflow)?;
+
+    Ok(())
+}
@@ -7,11 +7,14 @@ use anchor_spl::token_interface::{Mint};
 #[derive(Accounts)]
 pub struct MarkBidForRefund<'info> {
     #[account(
-        constraint = authority.key() == exchange.authority.key() @ VoucherExchangeError::NotExchangeAuthority,
+        constraint = exchange.has_role(ExchangeRole::RefundOperator, &authority.key()) @ VoucherExchangeError::MissingRole,
     )]
     pub authority: Signer<'info>,
 
-    #[account(mut)]
+    #[account(
+        seeds = [EXCHANGE_SEED, exchange.namespace.as_bytes()],
+        bump = exchange.bump
+    )]
     pub exchange: Account<'info, VoucherExchange>,
 
     pub nft_mint: InterfaceAccount<'info, Mint>,
@@ -24,12 +27,14 @@ pub struct MarkBidForRefund<'info> {
         mut,
         seeds = [
         VOUCHER_BID_SEED,
+        exchange.key().as_ref(),
         bidder.key().as_ref(),
         nft_mint.key().as_ref()
         ],
         bump = bid.bump,
+        has_one = exchange @ VoucherExchangeError::InvalidExchange,
         constraint = bid.nft_mint == nft_mint.key() @ VoucherExchangeError::NotBidder,
-        constraint = bid.active == true @ VoucherExchangeError::BidNotActive,
+        constraint = bid.active @ VoucherExchangeError::BidNotActive,
     )]
     pub bid: Account<'info, VoucherBid>,
 
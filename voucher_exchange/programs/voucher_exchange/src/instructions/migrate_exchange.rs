@@ -0,0 +1,44 @@
+use anchor_lang::prelude::*;
+use crate::state::*;
+use crate::errors::*;
+use crate::constants::*;
+use crate::migration::*;
+
+#[derive(Accounts)]
+pub struct MigrateExchange<'info> {
+    /// CHECK: Owned by this program, its layout is identified by its size in the handler
+    #[account(mut, owner = crate::ID)]
+    pub exchange: UncheckedAccount<'info>,
+
+    #[account(mut)]
+    pub payer: Signer<'info>,
+
+    pub system_program: Program<'info, System>,
+}
+
+// Anyone can migrate an exchange: the upgrade only fills in new fields and the payer
+// covers the extra rent. Exchanges from before versioning sit at the un-namespaced PDA
+// and are rejected.
+pub fn handler(
+    ctx: Context<MigrateExchange>,
+) -> Result<()> {
+    migrate_account::<VoucherExchange>(
+        &ctx.accounts.exchange.to_account_info(),
+        &ctx.accounts.payer.to_account_info(),
+        &ctx.accounts.system_program.to_account_info(),
+        VoucherExchange::SIZE,
+        |allocated_size, data| {
+            // Every versioned exchange was allocated at the current size
+            require_eq!(allocated_size, VoucherExchange::SIZE, VoucherExchangeError::UnknownAccountLayout);
+
+            let mut exchange = VoucherExchange::try_deserialize(&mut &data[..])?;
+            require_upgradable(exchange.version, 1..=EXCHANGE_VERSION, EXCHANGE_VERSION)?;
+            exchange.version = EXCHANGE_VERSION;
+            Ok(exchange)
+        },
+    )?;
+
+    msg!("Exchange {} migrated to version {}", ctx.accounts.exchange.key(), EXCHANGE_VERSION);
+
+    Ok(())
+}
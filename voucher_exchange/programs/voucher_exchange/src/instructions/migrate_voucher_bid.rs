@@ -0,0 +1,44 @@
+use anchor_lang::prelude::*;
+use crate::state::*;
+use crate::errors::*;
+use crate::constants::*;
+use crate::migration::*;
+
+#[derive(Accounts)]
+pub struct MigrateVoucherBid<'info> {
+    /// CHECK: Owned by this program, its layout is identified by its size in the handler
+    #[account(mut, owner = crate::ID)]
+    pub bid: UncheckedAccount<'info>,
+
+    #[account(mut)]
+    pub payer: Signer<'info>,
+
+    pub system_program: Program<'info, System>,
+}
+
+// Anyone can migrate a bid: the upgrade only fills in new fields and the payer
+// covers the extra rent. Bids from before versioning sit at the un-namespaced PDA
+// and are unwound by return_legacy_bid instead.
+pub fn handler(
+    ctx: Context<MigrateVoucherBid>,
+) -> Result<()> {
+    migrate_account::<VoucherBid>(
+        &ctx.accounts.bid.to_account_info(),
+        &ctx.accounts.payer.to_account_info(),
+        &ctx.accounts.system_program.to_account_info(),
+        VoucherBid::SIZE,
+        |allocated_size, data| {
+            // Every versioned bid was allocated at the current size
+            require_eq!(allocated_size, VoucherBid::SIZE, VoucherExchangeError::UnknownAccountLayout);
+
+            let mut bid = VoucherBid::try_deserialize(&mut &data[..])?;
+            require_upgradable(bid.version, 1..=BID_VERSION, BID_VERSION)?;
+            bid.version = BID_VERSION;
+            Ok(bid)
+        },
+    )?;
+
+    msg!("Bid {} migrated to version {}", ctx.accounts.bid.key(), BID_VERSION);
+
+    Ok(())
+}
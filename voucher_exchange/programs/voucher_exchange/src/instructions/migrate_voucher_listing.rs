@@ -0,0 +1,44 @@
+use anchor_lang::prelude::*;
+use crate::state::*;
+use crate::errors::*;
+use crate::constants::*;
+use crate::migration::*;
+
+#[derive(Accounts)]
+pub struct MigrateVoucherListing<'info> {
+    /// CHECK: Owned by this program, its layout is identified by its size in the handler
+    #[account(mut, owner = crate::ID)]
+    pub listing: UncheckedAccount<'info>,
+
+    #[account(mut)]
+    pub payer: Signer<'info>,
+
+    pub system_program: Program<'info, System>,
+}
+
+// Anyone can migrate a listing: the upgrade only fills in new fields and the payer
+// covers the extra rent. Listings from before versioning sit at the un-namespaced PDA
+// and are unwound by return_legacy_listing instead.
+pub fn handler(
+    ctx: Context<MigrateVoucherListing>,
+) -> Result<()> {
+    migrate_account::<VoucherListing>(
+        &ctx.accounts.listing.to_account_info(),
+        &ctx.accounts.payer.to_account_info(),
+        &ctx.accounts.system_program.to_account_info(),
+        VoucherListing::SIZE,
+        |allocated_size, data| {
+            // Every versioned listing was allocated at the current size
+            require_eq!(allocated_size, VoucherListing::SIZE, VoucherExchangeError::UnknownAccountLayout);
+
+            let mut listing = VoucherListing::try_deserialize(&mut &data[..])?;
+            require_upgradable(listing.version, 1..=LISTING_VERSION, LISTING_VERSION)?;
+            listing.version = LISTING_VERSION;
+            Ok(listing)
+        },
+    )?;
+
+    msg!("Listing {} migrated to version {}", ctx.accounts.listing.key(), LISTING_VERSION);
+
+    Ok(())
+}
@@ -0,0 +1,56 @@
+use anchor_lang::prelude::*;
+use crate::state::*;
+use crate::errors::*;
+use crate::constants::*;
+use crate::migration::*;
+use crate::legacy::*;
+
+#[derive(Accounts)]
+pub struct MigrateVoucherState<'info> {
+    /// CHECK: Owned by this program, its layout is identified by its size in the handler
+    #[account(mut, owner = crate::ID)]
+    pub voucher_state: UncheckedAccount<'info>,
+
+    #[account(mut)]
+    pub payer: Signer<'info>,
+
+    pub system_program: Program<'info, System>,
+}
+
+// Anyone can migrate a voucher state: the upgrade only fills in new fields and the payer
+// covers the extra rent
+pub fn handler(
+    ctx: Context<MigrateVoucherState>,
+) -> Result<()> {
+    migrate_account::<VoucherState>(
+        &ctx.accounts.voucher_state.to_account_info(),
+        &ctx.accounts.payer.to_account_info(),
+        &ctx.accounts.system_program.to_account_info(),
+        VoucherState::SIZE,
+        |allocated_size, data| match allocated_size {
+            // Voucher states from before versioning carry no version byte
+            LegacyVoucherState::SIZE => {
+                let legacy = LegacyVoucherState::deserialize(&mut &data[8..LegacyVoucherState::SIZE])?;
+                Ok(VoucherState {
+                    nft_mint: legacy.nft_mint,
+                    sold: legacy.sold,
+                    latest_sale_timestamp: legacy.latest_sale_timestamp,
+                    bump: legacy.bump,
+                    version: VOUCHER_STATE_VERSION,
+                    reserved: [0; 32],
+                })
+            }
+            VoucherState::SIZE => {
+                let mut voucher_state = VoucherState::try_deserialize(&mut &data[..])?;
+                require_upgradable(voucher_state.version, 1..=VOUCHER_STATE_VERSION, VOUCHER_STATE_VERSION)?;
+                voucher_state.version = VOUCHER_STATE_VERSION;
+                Ok(voucher_state)
+            }
+            _ => err!(VoucherExchangeError::UnknownAccountLayout),
+        },
+    )?;
+
+    msg!("Voucher state {} migrated to version {}", ctx.accounts.voucher_state.key(), VOUCHER_STATE_VERSION);
+
+    Ok(())
+}
@@ -1,3 +1,5 @@
+#![allow(ambiguous_glob_reexports)]
+
 pub mod initialize_exchange;
 pub mod create_voucher_listing;
 pub mod create_voucher_bid;
@@ -7,6 +9,55 @@ pub mod cancel_voucher_listing;
 pub mod cancel_voucher_bid;
 pub mod mark_bid_for_refund;
 pub mod refund_bid;
+pub mod propose_fee_update;
+pub mod cancel_fee_update;
+pub mod apply_fee_update;
+pub mod set_royalty_policy;
+pub mod set_collection_config;
+pub mod remove_collection_config;
+pub mod initialize_fee_vault;
+pub mod withdraw_fees;
+pub mod set_referrer;
+pub mod remove_referrer;
+pub mod set_fee_discount_tiers;
+pub mod set_fee_burn_rate;
+pub mod propose_authority;
+pub mod accept_authority;
+pub mod set_role;
+pub mod revoke_role;
+pub mod set_paused_operations;
+pub mod set_payment_mint_config;
+pub mod remove_payment_mint_config;
+pub mod approve_collection;
+pub mod revoke_collection;
+pub mod block_wallet;
+pub mod unblock_wallet;
+pub mod migrate_exchange;
+pub mod migrate_voucher_listing;
+pub mod migrate_voucher_bid;
+pub mod migrate_voucher_state;
+pub mod set_recovery_address;
+pub mod remove_recovery_address;
+pub mod announce_listing_rescue;
+pub mod announce_bid_rescue;
+pub mod cancel_rescue;
+pub mod execute_listing_rescue;
+pub mod execute_bid_rescue;
+pub mod begin_wind_down;
+pub mod return_listing;
+pub mod return_bid;
+pub mod close_exchange;
+pub mod expire_voucher_listing;
+pub mod update_voucher_listing;
+pub mod place_auction_bid;
+pub mod settle_auction;
+pub mod propose_collection_fee_update;
+pub mod cancel_collection_fee_update;
+pub mod apply_collection_fee_update;
+pub mod return_legacy_listing;
+pub mod return_legacy_bid;
+pub mod close_fee_vault;
+pub mod refund_auction_bid;
 
 pub use initialize_exchange::*;
 pub use create_voucher_listing::*;
@@ -16,4 +67,53 @@ pub use fulfill_voucher_listing::*;
 pub use cancel_voucher_listing::*;
 pub use cancel_voucher_bid::*;
 pub use mark_bid_for_refund::*;
-pub use refund_bid::*;
\ No newline at end of file
+pub use refund_bid::*;
+pub use propose_fee_update::*;
+pub use cancel_fee_update::*;
+pub use apply_fee_update::*;
+pub use set_royalty_policy::*;
+pub use set_collection_config::*;
+pub use remove_collection_config::*;
+pub use initialize_fee_vault::*;
+pub use withdraw_fees::*;
+pub use set_referrer::*;
+pub use remove_referrer::*;
+pub use set_fee_discount_tiers::*;
+pub use set_fee_burn_rate::*;
+pub use propose_authority::*;
+pub use accept_authority::*;
+pub use set_role::*;
+pub use revoke_role::*;
+pub use set_paused_operations::*;
+pub use set_payment_mint_config::*;
+pub use remove_payment_mint_config::*;
+pub use approve_collection::*;
+pub use revoke_collection::*;
+pub use block_wallet::*;
+pub use unblock_wallet::*;
+pub use migrate_exchange::*;
+pub use migrate_voucher_listing::*;
+pub use migrate_voucher_bid::*;
+pub use migrate_voucher_state::*;
+pub use set_recovery_address::*;
+pub use remove_recovery_address::*;
+pub use announce_listing_rescue::*;
+pub use announce_bid_rescue::*;
+pub use cancel_rescue::*;
+pub use execute_listing_rescue::*;
+pub use execute_bid_rescue::*;
+pub use begin_wind_down::*;
+pub use return_listing::*;
+pub use return_bid::*;
+pub use close_exchange::*;
+pub use expire_voucher_listing::*;
+pub use update_voucher_listing::*;
+pub use place_auction_bid::*;
+pub use settle_auction::*;
+pub use propose_collection_fee_update::*;
+pub use cancel_collection_fee_update::*;
+pub use apply_collection_fee_update::*;
+pub use return_legacy_listing::*;
+pub use return_legacy_bid::*;
+pub use close_fee_vault::*;
+pub use refund_auction_bid::*;
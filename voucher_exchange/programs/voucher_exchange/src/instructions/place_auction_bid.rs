@@ -0,0 +1,229 @@
+use anchor_lang::prelude::*;
+use anchor_spl::token_interface::{
+    TokenAccount, Mint, TokenInterface, TransferChecked, transfer_checked
+};
+use anchor_spl::metadata::Metadata;
+use anchor_spl::metadata::mpl_token_metadata;
+use crate::state::*;
+use crate::errors::*;
+use crate::constants::*;
+use crate::collection::*;
+use crate::settlement::*;
+use crate::events::*;
+
+#[derive(Accounts)]
+pub struct PlaceAuctionBid<'info> {
+    #[account(
+        mut,
+        seeds = [
+            VOUCHER_LISTING_SEED,
+            exchange.key().as_ref(),
+            listing.owner.as_ref(),
+            nft_mint.key().as_ref()
+        ],
+        bump = listing.bump,
+        has_one = exchange @ VoucherExchangeError::InvalidExchange,
+        constraint = listing.active @ VoucherExchangeError::ListingNotActive,
+        constraint = listing.english_auction.is_some() @ VoucherExchangeError::NotAnAuction,
+        constraint = listing.owner != bidder.key() @ VoucherExchangeError::SellerCannotBid
+    )]
+    pub listing: Account<'info, VoucherListing>,
+
+    #[account(
+        seeds = [EXCHANGE_SEED, exchange.namespace.as_bytes()],
+        bump = exchange.bump,
+        constraint = !exchange.is_paused(PAUSE_CREATE_BID) @ VoucherExchangeError::ExchangePaused,
+        constraint = !exchange.winding_down @ VoucherExchangeError::ExchangeWindingDown
+    )]
+    pub exchange: Account<'info, VoucherExchange>,
+
+    #[account(mut)]
+    pub bidder: Signer<'info>,
+
+    /// CHECK: BlockedWallet PDA of the bidder, must not exist
+    #[account(
+        seeds = [
+            BLOCKED_WALLET_SEED,
+            exchange.key().as_ref(),
+            bidder.key().as_ref()
+        ],
+        bump,
+        constraint = bidder_blocked_wallet.data_is_empty() @ VoucherExchangeError::WalletBlocked
+    )]
+    pub bidder_blocked_wallet: UncheckedAccount<'info>,
+
+    pub nft_mint: InterfaceAccount<'info, Mint>,
+
+    /// CHECK: Metaplex metadata PDA of the NFT, parsed in the handler if it exists
+    #[account(
+        seeds = [
+            mpl_token_metadata::accounts::Metadata::PREFIX,
+            token_metadata_program.key().as_ref(),
+            nft_mint.key().as_ref()
+        ],
+        seeds::program = token_metadata_program.key(),
+        bump
+    )]
+    pub nft_metadata: UncheckedAccount<'info>,
+
+    /// CHECK: Override for the NFT's verified collection, validated in the handler
+    pub collection_config: Option<UncheckedAccount<'info>>,
+
+    #[account(
+        constraint = payment_mint.key() == listing.payment_mint @ VoucherExchangeError::InvalidPaymentMint
+    )]
+    pub payment_mint: InterfaceAccount<'info, Mint>,
+
+    #[account(
+        mut,
+        constraint = bidder_token_account.mint == payment_mint.key() @ VoucherExchangeError::InvalidPaymentMint,
+        constraint = bidder_token_account.owner == bidder.key() @ VoucherExchangeError::NotBidder
+    )]
+    pub bidder_token_account: InterfaceAccount<'info, TokenAccount>,
+
+    // Bid escrow of the auction, keyed by the listing in place of a bidder,
+    // holding the top bid and its fee deposit
+    #[account(
+        init_if_needed,
+        payer = bidder,
+        seeds = [
+            ESCROW_SEED,
+            exchange.key().as_ref(),
+            listing.key().as_ref(),
+            nft_mint.key().as_ref()
+        ],
+        bump,
+        token::mint = payment_mint,
+        token::authority = escrow_account
+    )]
+    pub escrow_account: InterfaceAccount<'info, TokenAccount>,
+
+    // Receives the outbid top bid, required once the auction has bids
+    #[account(mut)]
+    pub previous_bidder_token_account: Option<InterfaceAccount<'info, TokenAccount>>,
+
+    pub token_program: Interface<'info, TokenInterface>,
+    pub token_metadata_program: Program<'info, Metadata>,
+    pub system_program: Program<'info, System>,
+}
+
+// The bidder is the taker, so the taker fee at the current rate is escrowed on top
+// of the bid. The previous top bid and its deposit are refunded in full, and a bid
+// placed within AUCTION_EXTENSION_WINDOW of the end pushes the end back to a full window.
+pub fn handler(
+    ctx: Context<PlaceAuctionBid>,
+    amount: u64,
+) -> Result<()> {
+    let now = Clock::get()?.unix_timestamp;
+    let mut auction = ctx.accounts.listing.english_auction.ok_or(VoucherExchangeError::NotAnAuction)?;
+
+    // Check bidding is open and the bid beats the reserve or the top bid by the increment
+    require!(now < auction.end_time, VoucherExchangeError::AuctionEnded);
+    require!(
+        amount >= auction.min_bid(ctx.accounts.listing.price)?,
+        VoucherExchangeError::BidTooLow
+    );
+
+    // Check price is within the collection range, if the collection has an override
+    let metadata = read_metadata(&ctx.accounts.nft_metadata)?;
+    let collection_config = load_collection_config(
+        &ctx.accounts.exchange.key(),
+        metadata.as_ref(),
+        ctx.accounts.collection_config.as_deref(),
+    )?;
+    if let Some(config) = &collection_config {
+        require!(config.is_price_allowed(amount), VoucherExchangeError::PriceOutOfRange);
+    }
+
+    let terms = SaleTerms::new(&ctx.accounts.exchange, collection_config.as_ref());
+    let fee_deposit = calculate_fee(amount, terms.taker_fee_basis_points)?;
+    let escrow_amount = amount.checked_add(fee_deposit).ok_or(VoucherExchangeError::MathOverflow)?;
+
+    // Check sufficient balance
+    require!(
+        ctx.accounts.bidder_token_account.amount >= escrow_amount,
+        VoucherExchangeError::InsufficientFunds
+    );
+
+    // 1. Refund the previous top bid and its fee deposit from escrow
+    if auction.has_bids() {
+        let previous_bidder_token_account = ctx.accounts.previous_bidder_token_account
+            .as_ref()
+            .ok_or(VoucherExchangeError::NotTopBidder)?;
+        require!(
+            previous_bidder_token_account.owner == auction.top_bidder,
+            VoucherExchangeError::NotTopBidder
+        );
+        require!(
+            previous_bidder_token_account.mint == ctx.accounts.payment_mint.key(),
+            VoucherExchangeError::InvalidPaymentMint
+        );
+
+        let exchange_key = ctx.accounts.exchange.key();
+        let listing_key = ctx.accounts.listing.key();
+        let nft_mint_key = ctx.accounts.nft_mint.key();
+        let escrow_seeds = &[
+            ESCROW_SEED,
+            exchange_key.as_ref(),
+            listing_key.as_ref(),
+            nft_mint_key.as_ref(),
+            &[ctx.bumps.escrow_account],
+        ];
+
+        let source = PaymentSource {
+            token_program: ctx.accounts.token_program.to_account_info(),
+            mint: &ctx.accounts.payment_mint,
+            from: ctx.accounts.escrow_account.to_account_info(),
+            authority: ctx.accounts.escrow_account.to_account_info(),
+            signer_seeds: &[&escrow_seeds[..]],
+        };
+        let refund = auction.escrowed_amount()?;
+        source.pay(previous_bidder_token_account.to_account_info(), refund)?;
+
+        emit!(AuctionBidRefunded {
+            exchange: exchange_key,
+            listing: listing_key,
+            nft_mint: nft_mint_key,
+            bidder: auction.top_bidder,
+            amount: refund,
+        });
+    }
+
+    // 2. Escrow the new bid and its fee deposit
+    transfer_checked(
+        CpiContext::new(
+            ctx.accounts.token_program.to_account_info(),
+            TransferChecked {
+                from: ctx.accounts.bidder_token_account.to_account_info(),
+                mint: ctx.accounts.payment_mint.to_account_info(),
+                to: ctx.accounts.escrow_account.to_account_info(),
+                authority: ctx.accounts.bidder.to_account_info(),
+            },
+        ),
+        escrow_amount,
+        ctx.accounts.payment_mint.decimals,
+    )?;
+
+    // 3. Record the new top bid, extending the auction when it lands near the end
+    auction.top_bidder = ctx.accounts.bidder.key();
+    auction.top_bid = amount;
+    auction.fee_deposit = fee_deposit;
+    let extended_end_time = now
+        .checked_add(AUCTION_EXTENSION_WINDOW)
+        .ok_or(VoucherExchangeError::MathOverflow)?;
+    if auction.end_time < extended_end_time {
+        auction.end_time = extended_end_time;
+    }
+    ctx.accounts.listing.english_auction = Some(auction);
+
+    emit!(AuctionBidPlaced {
+        exchange: ctx.accounts.exchange.key(),
+        listing: ctx.accounts.listing.key(),
+        nft_mint: ctx.accounts.nft_mint.key(),
+        bidder: ctx.accounts.bidder.key(),
+        amount,
+        end_time: auction.end_time,
+    });
+
+    Ok(())
+}
@@ -0,0 +1,62 @@
+use anchor_lang::prelude::*;
+use crate::state::*;
+use crate::errors::*;
+use crate::constants::*;
+
+#[derive(Accounts)]
+pub struct ProposeCollectionFeeUpdate<'info> {
+    #[account(
+        mut,
+        seeds = [
+            COLLECTION_CONFIG_SEED,
+            exchange.key().as_ref(),
+            collection_config.collection_mint.as_ref()
+        ],
+        bump = collection_config.bump,
+        has_one = exchange @ VoucherExchangeError::InvalidExchange
+    )]
+    pub collection_config: Account<'info, CollectionConfig>,
+
+    #[account(
+        constraint = exchange.has_role(ExchangeRole::FeeAdmin, &authority.key()) @ VoucherExchangeError::MissingRole,
+    )]
+    pub authority: Signer<'info>,
+
+    #[account(
+        seeds = [EXCHANGE_SEED, exchange.namespace.as_bytes()],
+        bump = exchange.bump
+    )]
+    pub exchange: Account<'info, VoucherExchange>,
+}
+
+pub fn handler(
+    ctx: Context<ProposeCollectionFeeUpdate>,
+    maker_fee_basis_points: u16,
+    taker_fee_basis_points: u16,
+) -> Result<()> {
+    // Check fees are within the allowed range
+    require!(
+        maker_fee_basis_points <= MAX_FEE_BASIS_POINTS && taker_fee_basis_points <= MAX_FEE_BASIS_POINTS,
+        VoucherExchangeError::FeeTooHigh
+    );
+
+    let now = Clock::get()?.unix_timestamp;
+
+    // Replace any previous proposal and restart the exchange fee update delay
+    let collection_config = &mut ctx.accounts.collection_config;
+    collection_config.pending_maker_fee_basis_points = maker_fee_basis_points;
+    collection_config.pending_taker_fee_basis_points = taker_fee_basis_points;
+    collection_config.pending_fee_effective_at = now
+        .checked_add(ctx.accounts.exchange.fee_update_delay)
+        .ok_or(VoucherExchangeError::MathOverflow)?;
+
+    msg!(
+        "Collection {} fee update to {} bps maker, {} bps taker proposed, effective at {}",
+        collection_config.collection_mint,
+        maker_fee_basis_points,
+        taker_fee_basis_points,
+        collection_config.pending_fee_effective_at
+    );
+
+    Ok(())
+}
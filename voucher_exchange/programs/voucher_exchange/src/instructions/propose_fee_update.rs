@@ -0,0 +1,53 @@
+use anchor_lang::prelude::*;
+use crate::state::*;
+use crate::errors::*;
+use crate::constants::*;
+
+#[derive(Accounts)]
+pub struct ProposeFeeUpdate<'info> {
+    #[account(
+        constraint = exchange.has_role(ExchangeRole::FeeAdmin, &authority.key()) @ VoucherExchangeError::MissingRole,
+    )]
+    pub authority: Signer<'info>,
+
+    #[account(
+        mut,
+        seeds = [EXCHANGE_SEED, exchange.namespace.as_bytes()],
+        bump = exchange.bump
+    )]
+    pub exchange: Account<'info, VoucherExchange>,
+}
+
+pub fn handler(
+    ctx: Context<ProposeFeeUpdate>,
+    maker_fee_basis_points: u16,
+    taker_fee_basis_points: u16,
+    fee_recipient: Pubkey,
+) -> Result<()> {
+    // Check fees are within the allowed range
+    require!(
+        maker_fee_basis_points <= MAX_FEE_BASIS_POINTS && taker_fee_basis_points <= MAX_FEE_BASIS_POINTS,
+        VoucherExchangeError::FeeTooHigh
+    );
+
+    let now = Clock::get()?.unix_timestamp;
+
+    // Replace any previous proposal and restart the delay
+    let exchange = &mut ctx.accounts.exchange;
+    exchange.pending_maker_fee_basis_points = maker_fee_basis_points;
+    exchange.pending_taker_fee_basis_points = taker_fee_basis_points;
+    exchange.pending_fee_recipient = fee_recipient;
+    exchange.pending_fee_effective_at = now
+        .checked_add(exchange.fee_update_delay)
+        .ok_or(VoucherExchangeError::MathOverflow)?;
+
+    msg!(
+        "Fee update to {} bps maker, {} bps taker for {} proposed, effective at {}",
+        maker_fee_basis_points,
+        taker_fee_basis_points,
+        fee_recipient,
+        exchange.pending_fee_effective_at
+    );
+
+    Ok(())
+}
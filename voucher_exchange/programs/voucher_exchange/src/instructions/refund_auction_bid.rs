@@ -0,0 +1,171 @@
+use anchor_lang::prelude::*;
+use anchor_spl::token_interface::{
+    TokenAccount, Mint, TokenInterface, CloseAccount, close_account
+};
+use crate::state::*;
+use crate::errors::*;
+use crate::constants::*;
+use crate::settlement::*;
+use crate::events::*;
+
+#[derive(Accounts)]
+pub struct RefundAuctionBid<'info> {
+    #[account(
+        mut,
+        seeds = [
+            VOUCHER_LISTING_SEED,
+            exchange.key().as_ref(),
+            listing.owner.as_ref(),
+            nft_mint.key().as_ref()
+        ],
+        bump = listing.bump,
+        has_one = exchange @ VoucherExchangeError::InvalidExchange,
+        constraint = listing.active @ VoucherExchangeError::ListingNotActive,
+        constraint = listing.has_auction_bids() @ VoucherExchangeError::AuctionHasNoBids
+    )]
+    pub listing: Account<'info, VoucherListing>,
+
+    // Not checked against the pause flags, so top bids can leave a paused exchange
+    #[account(
+        seeds = [EXCHANGE_SEED, exchange.namespace.as_bytes()],
+        bump = exchange.bump
+    )]
+    pub exchange: Account<'info, VoucherExchange>,
+
+    // The refund operator when settlement is blocked, or anyone once the grace period has passed
+    pub authority: Signer<'info>,
+
+    /// CHECK: Top bidder receiving the escrow rent, checked against the auction
+    #[account(
+        mut,
+        constraint = top_bidder.key() == listing.auction_top_bidder() @ VoucherExchangeError::NotTopBidder
+    )]
+    pub top_bidder: UncheckedAccount<'info>,
+
+    #[account(
+        mut,
+        constraint = top_bidder_token_account.mint == payment_mint.key() @ VoucherExchangeError::InvalidPaymentMint,
+        constraint = top_bidder_token_account.owner == top_bidder.key() @ VoucherExchangeError::NotTopBidder
+    )]
+    pub top_bidder_token_account: InterfaceAccount<'info, TokenAccount>,
+
+    /// CHECK: BlockedWallet PDA of the listing owner, read in the handler
+    #[account(
+        seeds = [
+            BLOCKED_WALLET_SEED,
+            exchange.key().as_ref(),
+            listing.owner.as_ref()
+        ],
+        bump
+    )]
+    pub owner_blocked_wallet: UncheckedAccount<'info>,
+
+    /// CHECK: BlockedWallet PDA of the top bidder, read in the handler
+    #[account(
+        seeds = [
+            BLOCKED_WALLET_SEED,
+            exchange.key().as_ref(),
+            top_bidder.key().as_ref()
+        ],
+        bump
+    )]
+    pub top_bidder_blocked_wallet: UncheckedAccount<'info>,
+
+    pub nft_mint: InterfaceAccount<'info, Mint>,
+
+    #[account(
+        constraint = payment_mint.key() == listing.payment_mint @ VoucherExchangeError::InvalidPaymentMint
+    )]
+    pub payment_mint: InterfaceAccount<'info, Mint>,
+
+    // Bid escrow of the auction holding the top bid and its fee deposit
+    #[account(
+        mut,
+        seeds = [
+            ESCROW_SEED,
+            exchange.key().as_ref(),
+            listing.key().as_ref(),
+            nft_mint.key().as_ref()
+        ],
+        bump
+    )]
+    pub escrow_account: InterfaceAccount<'info, TokenAccount>,
+
+    pub token_program: Interface<'info, TokenInterface>,
+}
+
+// Gives the top bidder a way out of an ended auction that cannot settle. The refund
+// operator can refund as soon as the auction ends while settlement is paused or the seller
+// or top bidder is blocked; otherwise anyone can once AUCTION_SETTLEMENT_GRACE has passed.
+// The top bid and its deposit go back to the bidder and the auction is left without bids.
+pub fn handler(
+    ctx: Context<RefundAuctionBid>,
+) -> Result<()> {
+    let mut auction = ctx.accounts.listing.english_auction.ok_or(VoucherExchangeError::NotAnAuction)?;
+    let now = Clock::get()?.unix_timestamp;
+
+    // Check bidding is over, a running auction keeps its top bidder
+    require!(now >= auction.end_time, VoucherExchangeError::AuctionNotEnded);
+
+    // Check settlement is blocked for the refund operator, or the grace period has passed
+    let exchange = &ctx.accounts.exchange;
+    let settlement_blocked = exchange.is_paused(PAUSE_FULFILL_LISTING)
+        || !ctx.accounts.owner_blocked_wallet.data_is_empty()
+        || !ctx.accounts.top_bidder_blocked_wallet.data_is_empty();
+    let is_refund_operator = exchange.has_role(ExchangeRole::RefundOperator, &ctx.accounts.authority.key());
+    if !(settlement_blocked && is_refund_operator) {
+        let refundable_at = auction.end_time
+            .checked_add(AUCTION_SETTLEMENT_GRACE)
+            .ok_or(VoucherExchangeError::MathOverflow)?;
+        require!(now >= refundable_at, VoucherExchangeError::AuctionRefundNotAvailable);
+    }
+
+    let exchange_key = ctx.accounts.exchange.key();
+    let listing_key = ctx.accounts.listing.key();
+    let nft_mint_key = ctx.accounts.nft_mint.key();
+    let escrow_seeds = &[
+        ESCROW_SEED,
+        exchange_key.as_ref(),
+        listing_key.as_ref(),
+        nft_mint_key.as_ref(),
+        &[ctx.bumps.escrow_account],
+    ];
+    let signer_seeds = &[&escrow_seeds[..]];
+
+    // 1. Refund the top bid and its fee deposit to the top bidder
+    let refund = auction.escrowed_amount()?;
+    let source = PaymentSource {
+        token_program: ctx.accounts.token_program.to_account_info(),
+        mint: &ctx.accounts.payment_mint,
+        from: ctx.accounts.escrow_account.to_account_info(),
+        authority: ctx.accounts.escrow_account.to_account_info(),
+        signer_seeds,
+    };
+    source.pay(ctx.accounts.top_bidder_token_account.to_account_info(), refund)?;
+
+    // 2. Close the emptied auction escrow and return rent to the top bidder
+    close_account(
+        CpiContext::new_with_signer(
+            ctx.accounts.token_program.to_account_info(),
+            CloseAccount {
+                account: ctx.accounts.escrow_account.to_account_info(),
+                destination: ctx.accounts.top_bidder.to_account_info(),
+                authority: ctx.accounts.escrow_account.to_account_info(),
+            },
+            signer_seeds,
+        )
+    )?;
+
+    // 3. Leave the auction without bids, so the seller can cancel it
+    emit!(AuctionBidRefunded {
+        exchange: exchange_key,
+        listing: listing_key,
+        nft_mint: nft_mint_key,
+        bidder: auction.top_bidder,
+        amount: refund,
+    });
+    auction.clear_top_bid();
+    ctx.accounts.listing.english_auction = Some(auction);
+
+    Ok(())
+}
@@ -14,12 +14,14 @@ pub struct RefundBid<'info> {
         mut,
         seeds = [
         VOUCHER_BID_SEED,
+        exchange.key().as_ref(),
         bidder.key().as_ref(),
         nft_mint.key().as_ref(),
         ],
         bump = bid.bump,
-        constraint = bid.active == true @ VoucherExchangeError::BidNotActive,
-        constraint = bid.requires_refund == true @ VoucherExchangeError::BidNotRequiresRefund,
+        has_one = exchange @ VoucherExchangeError::InvalidExchange,
+        constraint = bid.active @ VoucherExchangeError::BidNotActive,
+        constraint = bid.requires_refund @ VoucherExchangeError::BidNotRequiresRefund,
     )]
     pub bid: Account<'info, VoucherBid>,
 
@@ -30,6 +32,13 @@ pub struct RefundBid<'info> {
     )]
     pub bidder: Signer<'info>,
 
+    #[account(
+        mut,
+        seeds = [EXCHANGE_SEED, exchange.namespace.as_bytes()],
+        bump = exchange.bump
+    )]
+    pub exchange: Account<'info, VoucherExchange>,
+
     // The NFT mint is now part of the account derivation
     pub nft_mint: InterfaceAccount<'info, Mint>,
 
@@ -37,6 +46,7 @@ pub struct RefundBid<'info> {
         mut,
         seeds = [
         ESCROW_SEED,
+        exchange.key().as_ref(),
         bidder.key().as_ref(),
         nft_mint.key().as_ref(),
         ],
@@ -71,6 +81,7 @@ pub fn handler(
 
     // Refund from escrow
     let escrow_seed = ESCROW_SEED;
+    let exchange_key = ctx.accounts.exchange.key();
     let bidder_key = ctx.accounts.bidder.key();
     let nft_mint_key = ctx.accounts.nft_mint.key();
     let escrow_bump = ctx.accounts.bid.escrow_bump;
@@ -78,6 +89,7 @@ pub fn handler(
     // Create the seeds array with the correct lifetime
     let escrow_seeds = &[
         escrow_seed,
+        exchange_key.as_ref(),
         bidder_key.as_ref(),
         nft_mint_key.as_ref(),
         &[escrow_bump],
@@ -98,7 +110,7 @@ pub fn handler(
             },
             signer_seeds,
         ),
-        ctx.accounts.bid.price,
+        ctx.accounts.bid.escrowed_amount()?,
         ctx.accounts.payment_mint.decimals,
     )?;
 
@@ -106,5 +118,11 @@ pub fn handler(
     ctx.accounts.bid.active = false;
     ctx.accounts.bid.requires_refund = false;
 
+    // Refunded bids no longer count as open
+    let exchange = &mut ctx.accounts.exchange;
+    exchange.total_bids = exchange.total_bids
+        .checked_sub(1)
+        .ok_or(VoucherExchangeError::MathOverflow)?;
+
     Ok(())
 }
\ No newline at end of file
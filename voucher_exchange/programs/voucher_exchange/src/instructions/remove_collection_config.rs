@@ -0,0 +1,44 @@
+use anchor_lang::prelude::*;
+use crate::state::*;
+use crate::errors::*;
+use crate::constants::*;
+
+#[derive(Accounts)]
+pub struct RemoveCollectionConfig<'info> {
+    #[account(
+        mut,
+        seeds = [
+            COLLECTION_CONFIG_SEED,
+            exchange.key().as_ref(),
+            collection_config.collection_mint.as_ref()
+        ],
+        bump = collection_config.bump,
+        has_one = exchange @ VoucherExchangeError::InvalidExchange,
+        constraint = collection_config.maker_fee_basis_points == exchange.maker_fee_basis_points
+            && collection_config.taker_fee_basis_points == exchange.taker_fee_basis_points
+            @ VoucherExchangeError::CollectionFeesDiffer,
+        close = authority
+    )]
+    pub collection_config: Account<'info, CollectionConfig>,
+
+    #[account(
+        mut,
+        constraint = exchange.has_role(ExchangeRole::CollectionCurator, &authority.key()) @ VoucherExchangeError::MissingRole,
+    )]
+    pub authority: Signer<'info>,
+
+    #[account(
+        seeds = [EXCHANGE_SEED, exchange.namespace.as_bytes()],
+        bump = exchange.bump
+    )]
+    pub exchange: Account<'info, VoucherExchange>,
+}
+
+pub fn handler(
+    _ctx: Context<RemoveCollectionConfig>,
+) -> Result<()> {
+    // The config account is closed by the close = authority constraint,
+    // so the collection falls back to the exchange-wide terms. Its fees already
+    // match the exchange fees, so open listings and bids pay the same after removal.
+    Ok(())
+}
@@ -0,0 +1,39 @@
+use anchor_lang::prelude::*;
+use crate::state::*;
+use crate::errors::*;
+use crate::constants::*;
+
+#[derive(Accounts)]
+pub struct RemovePaymentMintConfig<'info> {
+    #[account(
+        mut,
+        seeds = [
+            PAYMENT_MINT_CONFIG_SEED,
+            exchange.key().as_ref(),
+            payment_mint_config.mint.as_ref()
+        ],
+        bump = payment_mint_config.bump,
+        close = authority
+    )]
+    pub payment_mint_config: Account<'info, PaymentMintConfig>,
+
+    #[account(
+        mut,
+        constraint = exchange.has_role(ExchangeRole::FeeAdmin, &authority.key()) @ VoucherExchangeError::MissingRole,
+    )]
+    pub authority: Signer<'info>,
+
+    #[account(
+        seeds = [EXCHANGE_SEED, exchange.namespace.as_bytes()],
+        bump = exchange.bump
+    )]
+    pub exchange: Account<'info, VoucherExchange>,
+}
+
+pub fn handler(
+    _ctx: Context<RemovePaymentMintConfig>,
+) -> Result<()> {
+    // The config account is closed by the close = authority constraint,
+    // which stops new listings and bids in the mint; open ones still settle
+    Ok(())
+}
@@ -0,0 +1,34 @@
+use anchor_lang::prelude::*;
+use crate::state::*;
+use crate::constants::*;
+
+#[derive(Accounts)]
+pub struct RemoveRecoveryAddress<'info> {
+    #[account(
+        mut,
+        seeds = [
+            RECOVERY_ADDRESS_SEED,
+            exchange.key().as_ref(),
+            wallet.key().as_ref()
+        ],
+        bump = recovery_address.bump,
+        close = wallet
+    )]
+    pub recovery_address: Account<'info, RecoveryAddress>,
+
+    #[account(mut)]
+    pub wallet: Signer<'info>,
+
+    #[account(
+        seeds = [EXCHANGE_SEED, exchange.namespace.as_bytes()],
+        bump = exchange.bump
+    )]
+    pub exchange: Account<'info, VoucherExchange>,
+}
+
+pub fn handler(
+    _ctx: Context<RemoveRecoveryAddress>,
+) -> Result<()> {
+    // The recovery address is removed by closing the account with the close = wallet constraint
+    Ok(())
+}
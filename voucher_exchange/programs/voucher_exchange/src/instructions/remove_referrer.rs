@@ -0,0 +1,39 @@
+use anchor_lang::prelude::*;
+use crate::state::*;
+use crate::errors::*;
+use crate::constants::*;
+
+#[derive(Accounts)]
+pub struct RemoveReferrer<'info> {
+    #[account(
+        mut,
+        seeds = [
+            REFERRER_SEED,
+            exchange.key().as_ref(),
+            referrer.wallet.as_ref()
+        ],
+        bump = referrer.bump,
+        close = authority
+    )]
+    pub referrer: Account<'info, Referrer>,
+
+    #[account(
+        mut,
+        constraint = exchange.has_role(ExchangeRole::FeeAdmin, &authority.key()) @ VoucherExchangeError::MissingRole,
+    )]
+    pub authority: Signer<'info>,
+
+    #[account(
+        seeds = [EXCHANGE_SEED, exchange.namespace.as_bytes()],
+        bump = exchange.bump
+    )]
+    pub exchange: Account<'info, VoucherExchange>,
+}
+
+pub fn handler(
+    _ctx: Context<RemoveReferrer>,
+) -> Result<()> {
+    // The referrer account is closed by the close = authority constraint,
+    // which revokes its approval
+    Ok(())
+}
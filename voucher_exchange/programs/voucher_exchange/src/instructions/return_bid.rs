@@ -0,0 +1,120 @@
+use anchor_lang::prelude::*;
+use anchor_spl::token_interface::{
+    TokenAccount, Mint, TokenInterface, TransferChecked, transfer_checked, CloseAccount, close_account
+};
+use crate::state::*;
+use crate::errors::*;
+use crate::constants::*;
+
+#[derive(Accounts)]
+pub struct ReturnBid<'info> {
+    #[account(
+        mut,
+        seeds = [
+            VOUCHER_BID_SEED,
+            exchange.key().as_ref(),
+            bidder.key().as_ref(),
+            bid.nft_mint.as_ref()
+        ],
+        bump = bid.bump,
+        has_one = exchange @ VoucherExchangeError::InvalidExchange,
+        has_one = bidder @ VoucherExchangeError::NotBidder,
+        close = bidder
+    )]
+    pub bid: Account<'info, VoucherBid>,
+
+    /// CHECK: Bidder receiving the rent, checked through has_one on the bid
+    #[account(mut)]
+    pub bidder: UncheckedAccount<'info>,
+
+    #[account(
+        mut,
+        constraint = bidder_token_account.mint == payment_mint.key() @ VoucherExchangeError::InvalidPaymentMint,
+        constraint = bidder_token_account.owner == bidder.key() @ VoucherExchangeError::NotBidder
+    )]
+    pub bidder_token_account: InterfaceAccount<'info, TokenAccount>,
+
+    #[account(
+        address = bid.payment_mint @ VoucherExchangeError::InvalidPaymentMint
+    )]
+    pub payment_mint: InterfaceAccount<'info, Mint>,
+
+    #[account(
+        mut,
+        seeds = [
+            ESCROW_SEED,
+            exchange.key().as_ref(),
+            bidder.key().as_ref(),
+            bid.nft_mint.as_ref()
+        ],
+        bump = bid.escrow_bump
+    )]
+    pub escrow_account: InterfaceAccount<'info, TokenAccount>,
+
+    #[account(
+        mut,
+        seeds = [EXCHANGE_SEED, exchange.namespace.as_bytes()],
+        bump = exchange.bump,
+        constraint = exchange.winding_down @ VoucherExchangeError::ExchangeNotWindingDown
+    )]
+    pub exchange: Account<'info, VoucherExchange>,
+
+    pub token_program: Interface<'info, TokenInterface>,
+}
+
+// Permissionless: anyone can return bid funds to the bidder once the exchange winds down.
+// Refunded bids are closed as well, along with their emptied escrow.
+pub fn handler(
+    ctx: Context<ReturnBid>,
+) -> Result<()> {
+    let exchange_key = ctx.accounts.exchange.key();
+    let bidder_key = ctx.accounts.bidder.key();
+    let nft_mint_key = ctx.accounts.bid.nft_mint;
+    let escrow_seeds = &[
+        ESCROW_SEED,
+        exchange_key.as_ref(),
+        bidder_key.as_ref(),
+        nft_mint_key.as_ref(),
+        &[ctx.accounts.bid.escrow_bump],
+    ];
+    let signer_seeds = &[&escrow_seeds[..]];
+
+    // Transfer funds from escrow back to the bidder
+    transfer_checked(
+        CpiContext::new_with_signer(
+            ctx.accounts.token_program.to_account_info(),
+            TransferChecked {
+                from: ctx.accounts.escrow_account.to_account_info(),
+                mint: ctx.accounts.payment_mint.to_account_info(),
+                to: ctx.accounts.bidder_token_account.to_account_info(),
+                authority: ctx.accounts.escrow_account.to_account_info(),
+            },
+            signer_seeds,
+        ),
+        ctx.accounts.escrow_account.amount,
+        ctx.accounts.payment_mint.decimals,
+    )?;
+
+    // Close the escrow token account and return rent to the bidder
+    close_account(
+        CpiContext::new_with_signer(
+            ctx.accounts.token_program.to_account_info(),
+            CloseAccount {
+                account: ctx.accounts.escrow_account.to_account_info(),
+                destination: ctx.accounts.bidder.to_account_info(),
+                authority: ctx.accounts.escrow_account.to_account_info(),
+            },
+            signer_seeds,
+        )
+    )?;
+
+    // Refunded bids were already taken off the open bid count
+    if ctx.accounts.bid.active {
+        let exchange = &mut ctx.accounts.exchange;
+        exchange.total_bids = exchange.total_bids
+            .checked_sub(1)
+            .ok_or(VoucherExchangeError::MathOverflow)?;
+    }
+
+    Ok(())
+}
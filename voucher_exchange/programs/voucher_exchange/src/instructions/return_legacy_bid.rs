@@ -0,0 +1,108 @@
+use anchor_lang::prelude::*;
+use anchor_spl::token_interface::{
+    TokenAccount, Mint, TokenInterface, TransferChecked, transfer_checked, CloseAccount, close_account
+};
+use crate::errors::*;
+use crate::constants::*;
+use crate::legacy::*;
+
+#[derive(Accounts)]
+pub struct ReturnLegacyBid<'info> {
+    /// CHECK: Bid in the pre-namespace layout at its un-namespaced PDA, decoded in the handler
+    #[account(
+        mut,
+        seeds = [
+            VOUCHER_BID_SEED,
+            bidder.key().as_ref(),
+            nft_mint.key().as_ref()
+        ],
+        bump
+    )]
+    pub bid: UncheckedAccount<'info>,
+
+    /// CHECK: Bidder receiving the funds and the rent, checked against the bid in the handler
+    #[account(mut)]
+    pub bidder: UncheckedAccount<'info>,
+
+    /// CHECK: Only used to derive the bid and escrow PDAs, checked against the bid in the handler
+    pub nft_mint: UncheckedAccount<'info>,
+
+    #[account(
+        mut,
+        constraint = bidder_token_account.mint == payment_mint.key() @ VoucherExchangeError::InvalidPaymentMint,
+        constraint = bidder_token_account.owner == bidder.key() @ VoucherExchangeError::NotBidder
+    )]
+    pub bidder_token_account: InterfaceAccount<'info, TokenAccount>,
+
+    pub payment_mint: InterfaceAccount<'info, Mint>,
+
+    #[account(
+        mut,
+        seeds = [
+            ESCROW_SEED,
+            bidder.key().as_ref(),
+            nft_mint.key().as_ref()
+        ],
+        bump
+    )]
+    pub escrow_account: InterfaceAccount<'info, TokenAccount>,
+
+    pub token_program: Interface<'info, TokenInterface>,
+}
+
+// Permissionless: bids created before exchanges were namespaced can no longer be
+// accepted, so anyone can return their funds to the bidder and close them
+pub fn handler(
+    ctx: Context<ReturnLegacyBid>,
+) -> Result<()> {
+    let bid = LegacyVoucherBid::load(&ctx.accounts.bid)?;
+    require_keys_eq!(bid.bidder, ctx.accounts.bidder.key(), VoucherExchangeError::NotBidder);
+    require_keys_eq!(bid.nft_mint, ctx.accounts.nft_mint.key(), VoucherExchangeError::InvalidNFTAccount);
+    require_keys_eq!(bid.payment_mint, ctx.accounts.payment_mint.key(), VoucherExchangeError::InvalidPaymentMint);
+    require_keys_eq!(bid.escrow_account, ctx.accounts.escrow_account.key(), VoucherExchangeError::InvalidEscrowOwner);
+
+    let bidder_key = ctx.accounts.bidder.key();
+    let nft_mint_key = ctx.accounts.nft_mint.key();
+    let escrow_seeds = &[
+        ESCROW_SEED,
+        bidder_key.as_ref(),
+        nft_mint_key.as_ref(),
+        &[ctx.bumps.escrow_account],
+    ];
+    let signer_seeds = &[&escrow_seeds[..]];
+
+    // Transfer funds from escrow back to the bidder
+    transfer_checked(
+        CpiContext::new_with_signer(
+            ctx.accounts.token_program.to_account_info(),
+            TransferChecked {
+                from: ctx.accounts.escrow_account.to_account_info(),
+                mint: ctx.accounts.payment_mint.to_account_info(),
+                to: ctx.accounts.bidder_token_account.to_account_info(),
+                authority: ctx.accounts.escrow_account.to_account_info(),
+            },
+            signer_seeds,
+        ),
+        ctx.accounts.escrow_account.amount,
+        ctx.accounts.payment_mint.decimals,
+    )?;
+
+    // Close the escrow token account and return rent to the bidder
+    close_account(
+        CpiContext::new_with_signer(
+            ctx.accounts.token_program.to_account_info(),
+            CloseAccount {
+                account: ctx.accounts.escrow_account.to_account_info(),
+                destination: ctx.accounts.bidder.to_account_info(),
+                authority: ctx.accounts.escrow_account.to_account_info(),
+            },
+            signer_seeds,
+        )
+    )?;
+
+    // Close the bid and return rent to the bidder
+    close_legacy_account(
+        &ctx.accounts.bid.to_account_info(),
+        &ctx.accounts.bidder.to_account_info(),
+    )
+}
@@ -0,0 +1,103 @@
+use anchor_lang::prelude::*;
+use anchor_spl::token_interface::{
+    TokenAccount, Mint, TokenInterface, TransferChecked, transfer_checked, CloseAccount, close_account
+};
+use crate::errors::*;
+use crate::constants::*;
+use crate::legacy::*;
+
+#[derive(Accounts)]
+pub struct ReturnLegacyListing<'info> {
+    /// CHECK: Listing in the pre-namespace layout at its un-namespaced PDA, decoded in the handler
+    #[account(
+        mut,
+        seeds = [
+            VOUCHER_LISTING_SEED,
+            owner.key().as_ref(),
+            nft_mint.key().as_ref()
+        ],
+        bump
+    )]
+    pub listing: UncheckedAccount<'info>,
+
+    /// CHECK: Listing owner receiving the NFT and the rent, checked against the listing in the handler
+    #[account(mut)]
+    pub owner: UncheckedAccount<'info>,
+
+    #[account(
+        mut,
+        constraint = owner_nft_account.mint == nft_mint.key() @ VoucherExchangeError::InvalidNFTAccount,
+        constraint = owner_nft_account.owner == owner.key() @ VoucherExchangeError::NotNFTOwner
+    )]
+    pub owner_nft_account: InterfaceAccount<'info, TokenAccount>,
+
+    pub nft_mint: InterfaceAccount<'info, Mint>,
+
+    #[account(
+        mut,
+        seeds = [
+            ESCROW_SEED,
+            nft_mint.key().as_ref()
+        ],
+        bump,
+        constraint = escrow_nft_account.owner == listing.key() @ VoucherExchangeError::InvalidEscrowOwner
+    )]
+    pub escrow_nft_account: InterfaceAccount<'info, TokenAccount>,
+
+    pub token_program: Interface<'info, TokenInterface>,
+}
+
+// Permissionless: listings created before exchanges were namespaced can no longer be
+// traded, so anyone can return their NFT to the owner and close them
+pub fn handler(
+    ctx: Context<ReturnLegacyListing>,
+) -> Result<()> {
+    let listing = LegacyVoucherListing::load(&ctx.accounts.listing)?;
+    require_keys_eq!(listing.owner, ctx.accounts.owner.key(), VoucherExchangeError::NotListingOwner);
+    require_keys_eq!(listing.nft_mint, ctx.accounts.nft_mint.key(), VoucherExchangeError::InvalidNFTAccount);
+
+    let owner_key = ctx.accounts.owner.key();
+    let nft_mint_key = ctx.accounts.nft_mint.key();
+    let listing_seeds = &[
+        VOUCHER_LISTING_SEED,
+        owner_key.as_ref(),
+        nft_mint_key.as_ref(),
+        &[ctx.bumps.listing],
+    ];
+    let listing_signer = &[&listing_seeds[..]];
+
+    // Transfer NFT from escrow back to owner
+    transfer_checked(
+        CpiContext::new_with_signer(
+            ctx.accounts.token_program.to_account_info(),
+            TransferChecked {
+                from: ctx.accounts.escrow_nft_account.to_account_info(),
+                to: ctx.accounts.owner_nft_account.to_account_info(),
+                authority: ctx.accounts.listing.to_account_info(),
+                mint: ctx.accounts.nft_mint.to_account_info(),
+            },
+            listing_signer,
+        ),
+        ctx.accounts.escrow_nft_account.amount,
+        ctx.accounts.nft_mint.decimals,
+    )?;
+
+    // Close the escrow token account and return rent to the owner
+    close_account(
+        CpiContext::new_with_signer(
+            ctx.accounts.token_program.to_account_info(),
+            CloseAccount {
+                account: ctx.accounts.escrow_nft_account.to_account_info(),
+                destination: ctx.accounts.owner.to_account_info(),
+                authority: ctx.accounts.listing.to_account_info(),
+            },
+            listing_signer,
+        )
+    )?;
+
+    // Close the listing and return rent to the owner
+    close_legacy_account(
+        &ctx.accounts.listing.to_account_info(),
+        &ctx.accounts.owner.to_account_info(),
+    )
+}
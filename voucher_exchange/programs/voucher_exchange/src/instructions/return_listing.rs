@@ -0,0 +1,115 @@
+use anchor_lang::prelude::*;
+use anchor_spl::token_interface::{
+    TokenAccount, Mint, TokenInterface, TransferChecked, transfer_checked, CloseAccount, close_account
+};
+use crate::state::*;
+use crate::errors::*;
+use crate::constants::*;
+
+#[derive(Accounts)]
+pub struct ReturnListing<'info> {
+    #[account(
+        mut,
+        seeds = [
+            VOUCHER_LISTING_SEED,
+            exchange.key().as_ref(),
+            owner.key().as_ref(),
+            nft_mint.key().as_ref()
+        ],
+        bump = listing.bump,
+        has_one = exchange @ VoucherExchangeError::InvalidExchange,
+        has_one = owner @ VoucherExchangeError::NotListingOwner,
+        constraint = !listing.has_auction_bids() @ VoucherExchangeError::AuctionHasBids,
+        close = owner
+    )]
+    pub listing: Account<'info, VoucherListing>,
+
+    /// CHECK: Listing owner receiving the rent, checked through has_one on the listing
+    #[account(mut)]
+    pub owner: UncheckedAccount<'info>,
+
+    #[account(
+        mut,
+        constraint = owner_nft_account.mint == nft_mint.key() @ VoucherExchangeError::InvalidNFTAccount,
+        constraint = owner_nft_account.owner == owner.key() @ VoucherExchangeError::NotNFTOwner
+    )]
+    pub owner_nft_account: InterfaceAccount<'info, TokenAccount>,
+
+    pub nft_mint: InterfaceAccount<'info, Mint>,
+
+    #[account(
+        mut,
+        seeds = [
+            ESCROW_SEED,
+            exchange.key().as_ref(),
+            nft_mint.key().as_ref()
+        ],
+        bump,
+        constraint = escrow_nft_account.owner == listing.key() @ VoucherExchangeError::InvalidEscrowOwner
+    )]
+    pub escrow_nft_account: InterfaceAccount<'info, TokenAccount>,
+
+    #[account(
+        mut,
+        seeds = [EXCHANGE_SEED, exchange.namespace.as_bytes()],
+        bump = exchange.bump,
+        constraint = exchange.winding_down @ VoucherExchangeError::ExchangeNotWindingDown
+    )]
+    pub exchange: Account<'info, VoucherExchange>,
+
+    pub token_program: Interface<'info, TokenInterface>,
+}
+
+// Permissionless: anyone can return a listed NFT to its owner once the exchange winds down
+pub fn handler(
+    ctx: Context<ReturnListing>,
+) -> Result<()> {
+    let exchange_key = ctx.accounts.exchange.key();
+    let owner_key = ctx.accounts.owner.key();
+    let nft_mint_key = ctx.accounts.nft_mint.key();
+    let listing_seeds = &[
+        VOUCHER_LISTING_SEED,
+        exchange_key.as_ref(),
+        owner_key.as_ref(),
+        nft_mint_key.as_ref(),
+        &[ctx.accounts.listing.bump],
+    ];
+    let listing_signer = &[&listing_seeds[..]];
+
+    // Transfer NFT from escrow back to owner
+    transfer_checked(
+        CpiContext::new_with_signer(
+            ctx.accounts.token_program.to_account_info(),
+            TransferChecked {
+                from: ctx.accounts.escrow_nft_account.to_account_info(),
+                to: ctx.accounts.owner_nft_account.to_account_info(),
+                authority: ctx.accounts.listing.to_account_info(),
+                mint: ctx.accounts.nft_mint.to_account_info(),
+            },
+            listing_signer,
+        ),
+        ctx.accounts.escrow_nft_account.amount,
+        ctx.accounts.nft_mint.decimals,
+    )?;
+
+    // Close the escrow token account and return rent to the owner
+    close_account(
+        CpiContext::new_with_signer(
+            ctx.accounts.token_program.to_account_info(),
+            CloseAccount {
+                account: ctx.accounts.escrow_nft_account.to_account_info(),
+                destination: ctx.accounts.owner.to_account_info(),
+                authority: ctx.accounts.listing.to_account_info(),
+            },
+            listing_signer,
+        )
+    )?;
+
+    // Decrement total listings in exchange
+    let exchange = &mut ctx.accounts.exchange;
+    exchange.total_listings = exchange.total_listings
+        .checked_sub(1)
+        .ok_or(VoucherExchangeError::MathOverflow)?;
+
+    Ok(())
+}
@@ -0,0 +1,39 @@
+use anchor_lang::prelude::*;
+use crate::state::*;
+use crate::errors::*;
+use crate::constants::*;
+
+#[derive(Accounts)]
+pub struct RevokeCollection<'info> {
+    #[account(
+        mut,
+        seeds = [
+            APPROVED_COLLECTION_SEED,
+            exchange.key().as_ref(),
+            approved_collection.collection_mint.as_ref()
+        ],
+        bump = approved_collection.bump,
+        close = authority
+    )]
+    pub approved_collection: Account<'info, ApprovedCollection>,
+
+    #[account(
+        mut,
+        constraint = exchange.has_role(ExchangeRole::CollectionCurator, &authority.key()) @ VoucherExchangeError::MissingRole,
+    )]
+    pub authority: Signer<'info>,
+
+    #[account(
+        seeds = [EXCHANGE_SEED, exchange.namespace.as_bytes()],
+        bump = exchange.bump
+    )]
+    pub exchange: Account<'info, VoucherExchange>,
+}
+
+pub fn handler(
+    _ctx: Context<RevokeCollection>,
+) -> Result<()> {
+    // The approval is closed by the close = authority constraint, which stops
+    // new listings and bids for the collection; open ones still settle
+    Ok(())
+}
@@ -0,0 +1,31 @@
+use anchor_lang::prelude::*;
+use crate::state::*;
+use crate::errors::*;
+use crate::constants::*;
+
+#[derive(Accounts)]
+pub struct RevokeRole<'info> {
+    #[account(
+        constraint = authority.key() == exchange.authority @ VoucherExchangeError::NotExchangeAuthority,
+    )]
+    pub authority: Signer<'info>,
+
+    #[account(
+        mut,
+        seeds = [EXCHANGE_SEED, exchange.namespace.as_bytes()],
+        bump = exchange.bump
+    )]
+    pub exchange: Account<'info, VoucherExchange>,
+}
+
+// Leaves the role to the authority alone
+pub fn handler(
+    ctx: Context<RevokeRole>,
+    role: ExchangeRole,
+) -> Result<()> {
+    ctx.accounts.exchange.set_role_holder(role, Pubkey::default());
+
+    msg!("Role {:?} revoked", role);
+
+    Ok(())
+}
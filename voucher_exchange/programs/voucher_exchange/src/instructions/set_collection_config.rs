@@ -0,0 +1,81 @@
+use anchor_lang::prelude::*;
+use crate::state::*;
+use crate::errors::*;
+use crate::constants::*;
+
+#[derive(Accounts)]
+#[instruction(collection_mint: Pubkey)]
+pub struct SetCollectionConfig<'info> {
+    #[account(
+        init_if_needed,
+        payer = authority,
+        space = CollectionConfig::SIZE,
+        seeds = [
+            COLLECTION_CONFIG_SEED,
+            exchange.key().as_ref(),
+            collection_mint.as_ref()
+        ],
+        bump
+    )]
+    pub collection_config: Account<'info, CollectionConfig>,
+
+    #[account(
+        mut,
+        constraint = exchange.has_role(ExchangeRole::CollectionCurator, &authority.key()) @ VoucherExchangeError::MissingRole,
+    )]
+    pub authority: Signer<'info>,
+
+    #[account(
+        seeds = [EXCHANGE_SEED, exchange.namespace.as_bytes()],
+        bump = exchange.bump
+    )]
+    pub exchange: Account<'info, VoucherExchange>,
+
+    pub system_program: Program<'info, System>,
+}
+
+#[derive(AnchorSerialize, AnchorDeserialize)]
+pub struct CollectionConfigParams {
+    pub royalty_policy: RoyaltyPolicy,
+    pub royalty_cap_basis_points: u16,
+    pub min_price: u64,
+    pub max_price: u64,
+}
+
+// Fees are not part of the override parameters: a new override starts at the exchange
+// fees and changes to them go through `propose_collection_fee_update` and the fee delay.
+pub fn handler(
+    ctx: Context<SetCollectionConfig>,
+    collection_mint: Pubkey,
+    params: CollectionConfigParams,
+) -> Result<()> {
+    // Check cap is a valid share of the price
+    require!(
+        params.royalty_cap_basis_points <= BASIS_POINTS_DIVISOR,
+        VoucherExchangeError::RoyaltyCapTooHigh
+    );
+
+    // Check price range is not inverted
+    require!(
+        params.max_price == 0 || params.min_price <= params.max_price,
+        VoucherExchangeError::InvalidPrice
+    );
+
+    let collection_config = &mut ctx.accounts.collection_config;
+    if collection_config.exchange == Pubkey::default() {
+        // New override, keep charging the current exchange fees
+        let exchange = &ctx.accounts.exchange;
+        collection_config.exchange = exchange.key();
+        collection_config.collection_mint = collection_mint;
+        collection_config.maker_fee_basis_points = exchange.maker_fee_basis_points;
+        collection_config.taker_fee_basis_points = exchange.taker_fee_basis_points;
+        collection_config.clear_pending_fee_update();
+    }
+    collection_config.royalty_policy = params.royalty_policy;
+    collection_config.royalty_cap_basis_points = params.royalty_cap_basis_points;
+    collection_config.min_price = params.min_price;
+    collection_config.max_price = params.max_price;
+    collection_config.bump = ctx.bumps.collection_config;
+
+    Ok(())
+}
@@ -0,0 +1,34 @@
+use anchor_lang::prelude::*;
+use crate::state::*;
+use crate::errors::*;
+use crate::constants::*;
+
+#[derive(Accounts)]
+pub struct SetFeeBurnRate<'info> {
+    #[account(
+        constraint = exchange.has_role(ExchangeRole::FeeAdmin, &authority.key()) @ VoucherExchangeError::MissingRole,
+    )]
+    pub authority: Signer<'info>,
+
+    #[account(
+        mut,
+        seeds = [EXCHANGE_SEED, exchange.namespace.as_bytes()],
+        bump = exchange.bump
+    )]
+    pub exchange: Account<'info, VoucherExchange>,
+}
+
+pub fn handler(
+    ctx: Context<SetFeeBurnRate>,
+    fee_burn_basis_points: u16,
+) -> Result<()> {
+    // Check burn rate is a valid share of the fee
+    require!(
+        fee_burn_basis_points <= BASIS_POINTS_DIVISOR,
+        VoucherExchangeError::FeeBurnTooHigh
+    );
+
+    ctx.accounts.exchange.fee_burn_basis_points = fee_burn_basis_points;
+
+    Ok(())
+}
@@ -0,0 +1,58 @@
+use anchor_lang::prelude::*;
+use crate::state::*;
+use crate::errors::*;
+use crate::constants::*;
+
+#[derive(Accounts)]
+pub struct SetFeeDiscountTiers<'info> {
+    #[account(
+        init_if_needed,
+        payer = authority,
+        space = FeeDiscountConfig::SIZE,
+        seeds = [FEE_DISCOUNT_SEED, exchange.key().as_ref()],
+        bump
+    )]
+    pub fee_discount_config: Account<'info, FeeDiscountConfig>,
+
+    #[account(
+        mut,
+        constraint = exchange.has_role(ExchangeRole::FeeAdmin, &authority.key()) @ VoucherExchangeError::MissingRole,
+    )]
+    pub authority: Signer<'info>,
+
+    #[account(
+        seeds = [EXCHANGE_SEED, exchange.namespace.as_bytes()],
+        bump = exchange.bump
+    )]
+    pub exchange: Account<'info, VoucherExchange>,
+
+    pub system_program: Program<'info, System>,
+}
+
+pub fn handler(
+    ctx: Context<SetFeeDiscountTiers>,
+    tiers: Vec<FeeDiscountTier>,
+) -> Result<()> {
+    // Check the table fits in the account
+    require!(
+        tiers.len() <= MAX_FEE_DISCOUNT_TIERS,
+        VoucherExchangeError::InvalidFeeDiscountTiers
+    );
+
+    // Check tiers are sorted by balance and discounts are within the fee range
+    require!(
+        tiers.windows(2).all(|pair| pair[0].min_balance < pair[1].min_balance),
+        VoucherExchangeError::InvalidFeeDiscountTiers
+    );
+    require!(
+        tiers.iter().all(|tier| tier.discount_basis_points <= MAX_FEE_BASIS_POINTS),
+        VoucherExchangeError::InvalidFeeDiscountTiers
+    );
+
+    let fee_discount_config = &mut ctx.accounts.fee_discount_config;
+    fee_discount_config.exchange = ctx.accounts.exchange.key();
+    fee_discount_config.tiers = tiers;
+    fee_discount_config.bump = ctx.bumps.fee_discount_config;
+
+    Ok(())
+}
@@ -0,0 +1,38 @@
+use anchor_lang::prelude::*;
+use crate::state::*;
+use crate::errors::*;
+use crate::constants::*;
+
+#[derive(Accounts)]
+pub struct SetPausedOperations<'info> {
+    #[account(
+        constraint = exchange.has_role(ExchangeRole::Pauser, &authority.key()) @ VoucherExchangeError::MissingRole,
+    )]
+    pub authority: Signer<'info>,
+
+    #[account(
+        mut,
+        seeds = [EXCHANGE_SEED, exchange.namespace.as_bytes()],
+        bump = exchange.bump
+    )]
+    pub exchange: Account<'info, VoucherExchange>,
+}
+
+// Replaces the whole set of paused operations; 0 resumes everything.
+// Cancellations and refunds are never paused so users can always recover assets.
+pub fn handler(
+    ctx: Context<SetPausedOperations>,
+    paused_operations: u8,
+) -> Result<()> {
+    // Check only known operations are flagged
+    require!(
+        paused_operations & !PAUSE_ALL == 0,
+        VoucherExchangeError::InvalidPauseFlags
+    );
+
+    ctx.accounts.exchange.paused_operations = paused_operations;
+
+    msg!("Paused operations set to {:#06b}", paused_operations);
+
+    Ok(())
+}
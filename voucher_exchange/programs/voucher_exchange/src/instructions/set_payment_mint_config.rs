@@ -0,0 +1,60 @@
+use anchor_lang::prelude::*;
+use anchor_spl::token_interface::Mint;
+use crate::state::*;
+use crate::errors::*;
+use crate::constants::*;
+
+#[derive(Accounts)]
+pub struct SetPaymentMintConfig<'info> {
+    #[account(
+        init_if_needed,
+        payer = authority,
+        space = PaymentMintConfig::SIZE,
+        seeds = [
+            PAYMENT_MINT_CONFIG_SEED,
+            exchange.key().as_ref(),
+            payment_mint.key().as_ref()
+        ],
+        bump
+    )]
+    pub payment_mint_config: Account<'info, PaymentMintConfig>,
+
+    pub payment_mint: InterfaceAccount<'info, Mint>,
+
+    #[account(
+        mut,
+        constraint = exchange.has_role(ExchangeRole::FeeAdmin, &authority.key()) @ VoucherExchangeError::MissingRole,
+    )]
+    pub authority: Signer<'info>,
+
+    #[account(
+        seeds = [EXCHANGE_SEED, exchange.namespace.as_bytes()],
+        bump = exchange.bump
+    )]
+    pub exchange: Account<'info, VoucherExchange>,
+
+    pub system_program: Program<'info, System>,
+}
+
+// `decimals` is what the caller expects the mint to use, guarding against
+// allowlisting the wrong mint
+pub fn handler(
+    ctx: Context<SetPaymentMintConfig>,
+    decimals: u8,
+    min_price: u64,
+) -> Result<()> {
+    // Check the mint matches the expected decimals
+    require!(
+        ctx.accounts.payment_mint.decimals == decimals,
+        VoucherExchangeError::InvalidPaymentMintDecimals
+    );
+
+    let payment_mint_config = &mut ctx.accounts.payment_mint_config;
+    payment_mint_config.exchange = ctx.accounts.exchange.key();
+    payment_mint_config.mint = ctx.accounts.payment_mint.key();
+    payment_mint_config.decimals = decimals;
+    payment_mint_config.min_price = min_price;
+    payment_mint_config.bump = ctx.bumps.payment_mint_config;
+
+    Ok(())
+}
@@ -0,0 +1,46 @@
+use anchor_lang::prelude::*;
+use crate::state::*;
+use crate::constants::*;
+
+#[derive(Accounts)]
+pub struct SetRecoveryAddress<'info> {
+    #[account(
+        init_if_needed,
+        payer = wallet,
+        space = RecoveryAddress::SIZE,
+        seeds = [
+            RECOVERY_ADDRESS_SEED,
+            exchange.key().as_ref(),
+            wallet.key().as_ref()
+        ],
+        bump
+    )]
+    pub recovery_address: Account<'info, RecoveryAddress>,
+
+    #[account(mut)]
+    pub wallet: Signer<'info>,
+
+    #[account(
+        seeds = [EXCHANGE_SEED, exchange.namespace.as_bytes()],
+        bump = exchange.bump
+    )]
+    pub exchange: Account<'info, VoucherExchange>,
+
+    pub system_program: Program<'info, System>,
+}
+
+// Only rescues announced after this call send the wallet's assets to the new address
+pub fn handler(
+    ctx: Context<SetRecoveryAddress>,
+    recovery: Pubkey,
+) -> Result<()> {
+    let recovery_address = &mut ctx.accounts.recovery_address;
+    recovery_address.exchange = ctx.accounts.exchange.key();
+    recovery_address.wallet = ctx.accounts.wallet.key();
+    recovery_address.recovery = recovery;
+    recovery_address.bump = ctx.bumps.recovery_address;
+
+    msg!("Recovery address of {} set to {}", recovery_address.wallet, recovery);
+
+    Ok(())
+}
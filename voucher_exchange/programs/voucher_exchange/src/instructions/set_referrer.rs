@@ -0,0 +1,57 @@
+use anchor_lang::prelude::*;
+use crate::state::*;
+use crate::errors::*;
+use crate::constants::*;
+
+#[derive(Accounts)]
+#[instruction(wallet: Pubkey)]
+pub struct SetReferrer<'info> {
+    #[account(
+        init_if_needed,
+        payer = authority,
+        space = Referrer::SIZE,
+        seeds = [
+            REFERRER_SEED,
+            exchange.key().as_ref(),
+            wallet.as_ref()
+        ],
+        bump
+    )]
+    pub referrer: Account<'info, Referrer>,
+
+    #[account(
+        mut,
+        constraint = exchange.has_role(ExchangeRole::FeeAdmin, &authority.key()) @ VoucherExchangeError::MissingRole,
+    )]
+    pub authority: Signer<'info>,
+
+    #[account(
+        seeds = [EXCHANGE_SEED, exchange.namespace.as_bytes()],
+        bump = exchange.bump
+    )]
+    pub exchange: Account<'info, VoucherExchange>,
+
+    pub system_program: Program<'info, System>,
+}
+
+pub fn handler(
+    ctx: Context<SetReferrer>,
+    wallet: Pubkey,
+    fee_share_basis_points: u16,
+    paused: bool,
+) -> Result<()> {
+    // Check share is within the referrer cap
+    require!(
+        fee_share_basis_points <= MAX_REFERRER_SHARE_BASIS_POINTS,
+        VoucherExchangeError::ReferrerShareTooHigh
+    );
+
+    let referrer = &mut ctx.accounts.referrer;
+    referrer.exchange = ctx.accounts.exchange.key();
+    referrer.wallet = wallet;
+    referrer.fee_share_basis_points = fee_share_basis_points;
+    referrer.paused = paused;
+    referrer.bump = ctx.bumps.referrer;
+
+    Ok(())
+}
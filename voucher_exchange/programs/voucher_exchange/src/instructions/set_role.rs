@@ -0,0 +1,32 @@
+use anchor_lang::prelude::*;
+use crate::state::*;
+use crate::errors::*;
+use crate::constants::*;
+
+#[derive(Accounts)]
+pub struct SetRole<'info> {
+    #[account(
+        constraint = authority.key() == exchange.authority @ VoucherExchangeError::NotExchangeAuthority,
+    )]
+    pub authority: Signer<'info>,
+
+    #[account(
+        mut,
+        seeds = [EXCHANGE_SEED, exchange.namespace.as_bytes()],
+        bump = exchange.bump
+    )]
+    pub exchange: Account<'info, VoucherExchange>,
+}
+
+// Replaces any previous holder of the role
+pub fn handler(
+    ctx: Context<SetRole>,
+    role: ExchangeRole,
+    holder: Pubkey,
+) -> Result<()> {
+    ctx.accounts.exchange.set_role_holder(role, holder);
+
+    msg!("Role {:?} granted to {}", role, holder);
+
+    Ok(())
+}
@@ -0,0 +1,37 @@
+use anchor_lang::prelude::*;
+use crate::state::*;
+use crate::errors::*;
+use crate::constants::*;
+
+#[derive(Accounts)]
+pub struct SetRoyaltyPolicy<'info> {
+    #[account(
+        constraint = exchange.has_role(ExchangeRole::FeeAdmin, &authority.key()) @ VoucherExchangeError::MissingRole,
+    )]
+    pub authority: Signer<'info>,
+
+    #[account(
+        mut,
+        seeds = [EXCHANGE_SEED, exchange.namespace.as_bytes()],
+        bump = exchange.bump
+    )]
+    pub exchange: Account<'info, VoucherExchange>,
+}
+
+pub fn handler(
+    ctx: Context<SetRoyaltyPolicy>,
+    royalty_policy: RoyaltyPolicy,
+    royalty_cap_basis_points: u16,
+) -> Result<()> {
+    // Check cap is a valid share of the price
+    require!(
+        royalty_cap_basis_points <= BASIS_POINTS_DIVISOR,
+        VoucherExchangeError::RoyaltyCapTooHigh
+    );
+
+    let exchange = &mut ctx.accounts.exchange;
+    exchange.royalty_policy = royalty_policy;
+    exchange.royalty_cap_basis_points = royalty_cap_basis_points;
+
+    Ok(())
+}
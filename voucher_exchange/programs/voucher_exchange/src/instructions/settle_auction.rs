@@ -0,0 +1,318 @@
+use anchor_lang::prelude::*;
+use anchor_spl::token_interface::{
+    TokenAccount as TokenAccountInterface, Mint as MintInterface, TokenInterface,
+    TransferChecked, transfer_checked, CloseAccount, close_account
+};
+use anchor_spl::token::{Token, Mint, TokenAccount};
+use anchor_spl::metadata::Metadata;
+use anchor_spl::metadata::mpl_token_metadata;
+use crate::state::*;
+use crate::errors::*;
+use crate::constants::*;
+use crate::settlement::*;
+use crate::collection::*;
+
+#[derive(Accounts)]
+pub struct SettleAuction<'info> {
+    #[account(
+        mut,
+        seeds = [
+            VOUCHER_LISTING_SEED,
+            exchange.key().as_ref(),
+            owner.key().as_ref(),
+            nft_mint.key().as_ref()
+        ],
+        bump = listing.bump,
+        has_one = exchange @ VoucherExchangeError::InvalidExchange,
+        has_one = owner @ VoucherExchangeError::NotListingOwner,
+        constraint = listing.active @ VoucherExchangeError::ListingNotActive,
+        constraint = listing.english_auction.is_some() @ VoucherExchangeError::NotAnAuction,
+        close = owner
+    )]
+    pub listing: Account<'info, VoucherListing>,
+
+    /// CHECK: Listing owner receiving the rent, checked through has_one on the listing
+    #[account(mut)]
+    pub owner: UncheckedAccount<'info>,
+
+    /// CHECK: Top bidder receiving the escrow rent, checked against the auction in the handler
+    #[account(mut)]
+    pub winner: UncheckedAccount<'info>,
+
+    /// CHECK: BlockedWallet PDA of the owner, must not exist
+    #[account(
+        seeds = [
+            BLOCKED_WALLET_SEED,
+            exchange.key().as_ref(),
+            owner.key().as_ref()
+        ],
+        bump,
+        constraint = owner_blocked_wallet.data_is_empty() @ VoucherExchangeError::WalletBlocked
+    )]
+    pub owner_blocked_wallet: UncheckedAccount<'info>,
+
+    /// CHECK: BlockedWallet PDA of the winner, must not exist
+    #[account(
+        seeds = [
+            BLOCKED_WALLET_SEED,
+            exchange.key().as_ref(),
+            winner.key().as_ref()
+        ],
+        bump,
+        constraint = winner_blocked_wallet.data_is_empty() @ VoucherExchangeError::WalletBlocked
+    )]
+    pub winner_blocked_wallet: UncheckedAccount<'info>,
+
+    // Anyone can settle an ended auction
+    pub cranker: Signer<'info>,
+
+    #[account(mut)]
+    pub nft_mint: Account<'info, Mint>,
+
+    /// CHECK: Metaplex metadata PDA of the NFT, parsed in the handler if it exists
+    #[account(
+        seeds = [
+            mpl_token_metadata::accounts::Metadata::PREFIX,
+            token_metadata_program.key().as_ref(),
+            nft_mint.key().as_ref()
+        ],
+        seeds::program = token_metadata_program.key(),
+        bump
+    )]
+    pub nft_metadata: UncheckedAccount<'info>,
+
+    /// CHECK: Override for the NFT's verified collection, validated in the handler
+    pub collection_config: Option<UncheckedAccount<'info>>,
+
+    #[account(
+        mut,
+        constraint = escrow_nft_account.mint == nft_mint.key() @ VoucherExchangeError::InvalidNFTAccount,
+        constraint = escrow_nft_account.owner == listing.key() @ VoucherExchangeError::InvalidEscrowOwner,
+        constraint = escrow_nft_account.key() == listing.nft_account @ VoucherExchangeError::InvalidNFTAccount
+    )]
+    pub escrow_nft_account: Account<'info, TokenAccount>,
+
+    #[account(
+        mut,
+        constraint = winner_nft_account.mint == nft_mint.key() @ VoucherExchangeError::InvalidNFTAccount,
+        constraint = winner_nft_account.owner == winner.key() @ VoucherExchangeError::NotTopBidder
+    )]
+    pub winner_nft_account: Account<'info, TokenAccount>,
+
+    #[account(
+        mut,
+        constraint = payment_mint.key() == listing.payment_mint @ VoucherExchangeError::InvalidPaymentMint
+    )]
+    pub payment_mint: InterfaceAccount<'info, MintInterface>,
+
+    // Bid escrow of the auction holding the top bid and its fee deposit
+    #[account(
+        mut,
+        seeds = [
+            ESCROW_SEED,
+            exchange.key().as_ref(),
+            listing.key().as_ref(),
+            nft_mint.key().as_ref()
+        ],
+        bump
+    )]
+    pub escrow_account: InterfaceAccount<'info, TokenAccountInterface>,
+
+    // Receives the part of the taker fee deposit that is not charged
+    #[account(
+        mut,
+        constraint = winner_payment_account.mint == payment_mint.key() @ VoucherExchangeError::InvalidPaymentMint,
+        constraint = winner_payment_account.owner == winner.key() @ VoucherExchangeError::NotTopBidder
+    )]
+    pub winner_payment_account: InterfaceAccount<'info, TokenAccountInterface>,
+
+    #[account(
+        mut,
+        constraint = owner_payment_account.mint == payment_mint.key() @ VoucherExchangeError::InvalidPaymentMint,
+        constraint = owner_payment_account.owner == owner.key() @ VoucherExchangeError::NotListingOwner
+    )]
+    pub owner_payment_account: InterfaceAccount<'info, TokenAccountInterface>,
+
+    #[account(
+        mut,
+        seeds = [
+            FEE_VAULT_SEED,
+            exchange.key().as_ref(),
+            payment_mint.key().as_ref()
+        ],
+        bump = fee_vault.bump,
+        has_one = exchange @ VoucherExchangeError::InvalidExchange
+    )]
+    pub fee_vault: Account<'info, FeeVault>,
+
+    #[account(
+        mut,
+        address = fee_vault.vault @ VoucherExchangeError::InvalidFeeAccount
+    )]
+    pub fee_vault_token_account: InterfaceAccount<'info, TokenAccountInterface>,
+
+    #[account(
+        mut,
+        seeds = [EXCHANGE_SEED, exchange.namespace.as_bytes()],
+        bump = exchange.bump,
+        constraint = !exchange.is_paused(PAUSE_FULFILL_LISTING) @ VoucherExchangeError::ExchangePaused
+    )]
+    pub exchange: Account<'info, VoucherExchange>,
+
+    pub token_program: Interface<'info, TokenInterface>,
+    pub token_nft_program: Program<'info, Token>,
+    pub token_metadata_program: Program<'info, Metadata>,
+}
+
+// Remaining accounts: payment token accounts of the verified creators, see `pay_royalties`,
+// followed by one payment token account per payout split of the listing.
+// The lister is the maker and the top bidder the taker: the taker fee is taken from the
+// fee deposit escrowed with the top bid and the maker fee comes out of the seller proceeds.
+// Settlement is permissionless, so no referrer or OPT holder discount applies. Auctions
+// with a blocked seller or winner cannot settle; their top bid leaves through refund_auction_bid.
+pub fn handler<'info>(
+    ctx: Context<'_, '_, 'info, 'info, SettleAuction<'info>>,
+) -> Result<()> {
+    let now = Clock::get()?.unix_timestamp;
+    let auction = ctx.accounts.listing.english_auction.ok_or(VoucherExchangeError::NotAnAuction)?;
+
+    // Check bidding is over and there is a winner to settle with
+    require!(now >= auction.end_time, VoucherExchangeError::AuctionNotEnded);
+    require!(auction.has_bids(), VoucherExchangeError::AuctionHasNoBids);
+    require!(
+        ctx.accounts.winner.key() == auction.top_bidder,
+        VoucherExchangeError::NotTopBidder
+    );
+
+    // Check NFT amount in escrow
+    require!(
+        ctx.accounts.escrow_nft_account.amount == 1,
+        VoucherExchangeError::InsufficientNFTAmount
+    );
+
+    let price = auction.top_bid;
+    let exchange_key = ctx.accounts.exchange.key();
+    let listing_key = ctx.accounts.listing.key();
+    let owner_key = ctx.accounts.owner.key();
+    let winner_key = ctx.accounts.winner.key();
+    let nft_mint_key = ctx.accounts.nft_mint.key();
+
+    let payment_token_program = ctx.accounts.token_program.to_account_info();
+
+    let escrow_seeds = &[
+        ESCROW_SEED,
+        exchange_key.as_ref(),
+        listing_key.as_ref(),
+        nft_mint_key.as_ref(),
+        &[ctx.bumps.escrow_account],
+    ];
+
+    let escrow_signer_seeds = &[&escrow_seeds[..]];
+
+    // Resolve fee and royalty terms, preferring the collection override
+    let metadata = read_metadata(&ctx.accounts.nft_metadata)?;
+    let collection_config = load_collection_config(
+        &ctx.accounts.exchange.key(),
+        metadata.as_ref(),
+        ctx.accounts.collection_config.as_deref(),
+    )?;
+    let terms = SaleTerms::new(&ctx.accounts.exchange, collection_config.as_ref());
+
+    let source = PaymentSource {
+        token_program: payment_token_program.clone(),
+        mint: &ctx.accounts.payment_mint,
+        from: ctx.accounts.escrow_account.to_account_info(),
+        authority: ctx.accounts.escrow_account.to_account_info(),
+        signer_seeds: escrow_signer_seeds,
+    };
+
+    // The taker fee never exceeds what the top bidder deposited
+    let sale = Sale {
+        nft_mint: nft_mint_key,
+        seller: owner_key,
+        buyer: winner_key,
+        buyer_is_maker: false,
+        price,
+        terms,
+        metadata: metadata.as_ref(),
+        buyer_fee_deposit: Some(auction.fee_deposit),
+    };
+
+    // 1. Transfer fees, royalties and the seller proceeds from escrow
+    let taker_fee = settle_sale(&source, &sale, SaleAccounts {
+        exchange: &mut ctx.accounts.exchange,
+        fee_vault: &mut ctx.accounts.fee_vault,
+        fee_vault_token_account: ctx.accounts.fee_vault_token_account.to_account_info(),
+        referrer: None,
+        referrer_payment_account: None,
+        seller_payment_account: ctx.accounts.owner_payment_account.to_account_info(),
+        payout_splits: &ctx.accounts.listing.payout_splits,
+        remaining_accounts: ctx.remaining_accounts,
+    })?;
+
+    // 2. Refund the unused taker fee deposit to the winner
+    let deposit_refund = auction.fee_deposit.checked_sub(taker_fee).ok_or(VoucherExchangeError::MathOverflow)?;
+    source.pay(ctx.accounts.winner_payment_account.to_account_info(), deposit_refund)?;
+
+    // Close the auction escrow and return rent to the winner
+    close_account(
+        CpiContext::new_with_signer(
+            payment_token_program,
+            CloseAccount {
+                account: ctx.accounts.escrow_account.to_account_info(),
+                destination: ctx.accounts.winner.to_account_info(),
+                authority: ctx.accounts.escrow_account.to_account_info(),
+            },
+            escrow_signer_seeds,
+        )
+    )?;
+
+    let listing_seeds = &[
+        VOUCHER_LISTING_SEED,
+        exchange_key.as_ref(),
+        owner_key.as_ref(),
+        nft_mint_key.as_ref(),
+        &[ctx.accounts.listing.bump],
+    ];
+
+    let listing_signer_seeds = &[&listing_seeds[..]];
+
+    let nft_token_program = ctx.accounts.token_nft_program.to_account_info();
+
+    // 3. Transfer NFT from escrow to the winner
+    transfer_checked(
+        CpiContext::new_with_signer(
+            nft_token_program.clone(),
+            TransferChecked {
+                from: ctx.accounts.escrow_nft_account.to_account_info(),
+                mint: ctx.accounts.nft_mint.to_account_info(),
+                to: ctx.accounts.winner_nft_account.to_account_info(),
+                authority: ctx.accounts.listing.to_account_info(),
+            },
+            listing_signer_seeds,
+        ),
+        1,
+        ctx.accounts.nft_mint.decimals,
+    )?;
+
+    // 4. Close the escrow NFT account and send rent back to the owner
+    close_account(
+        CpiContext::new_with_signer(
+            nft_token_program,
+            CloseAccount {
+                account: ctx.accounts.escrow_nft_account.to_account_info(),
+                destination: ctx.accounts.owner.to_account_info(), // Rent goes back to the owner
+                authority: ctx.accounts.listing.to_account_info(),
+            },
+            listing_signer_seeds,
+        )
+    )?;
+
+    // Decrement total listings in exchange
+    let exchange = &mut ctx.accounts.exchange;
+    exchange.total_listings = exchange.total_listings
+        .checked_sub(1)
+        .ok_or(VoucherExchangeError::MathOverflow)?;
+
+    Ok(())
+}
@@ -0,0 +1,38 @@
+use anchor_lang::prelude::*;
+use crate::state::*;
+use crate::errors::*;
+use crate::constants::*;
+
+#[derive(Accounts)]
+pub struct UnblockWallet<'info> {
+    #[account(
+        mut,
+        seeds = [
+            BLOCKED_WALLET_SEED,
+            exchange.key().as_ref(),
+            blocked_wallet.wallet.as_ref()
+        ],
+        bump = blocked_wallet.bump,
+        close = authority
+    )]
+    pub blocked_wallet: Account<'info, BlockedWallet>,
+
+    #[account(
+        mut,
+        constraint = authority.key() == exchange.authority @ VoucherExchangeError::NotExchangeAuthority,
+    )]
+    pub authority: Signer<'info>,
+
+    #[account(
+        seeds = [EXCHANGE_SEED, exchange.namespace.as_bytes()],
+        bump = exchange.bump
+    )]
+    pub exchange: Account<'info, VoucherExchange>,
+}
+
+pub fn handler(
+    _ctx: Context<UnblockWallet>,
+) -> Result<()> {
+    // The block is lifted by closing the account with the close = authority constraint
+    Ok(())
+}
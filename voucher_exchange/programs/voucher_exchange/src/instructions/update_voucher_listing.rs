@@ -0,0 +1,132 @@
+use anchor_lang::prelude::*;
+use anchor_spl::token_interface::Mint;
+use anchor_spl::metadata::Metadata;
+use anchor_spl::metadata::mpl_token_metadata;
+use crate::state::*;
+use crate::errors::*;
+use crate::constants::*;
+use crate::collection::*;
+
+#[derive(Accounts)]
+pub struct UpdateVoucherListing<'info> {
+    #[account(
+        mut,
+        seeds = [
+            VOUCHER_LISTING_SEED,
+            exchange.key().as_ref(),
+            owner.key().as_ref(),
+            nft_mint.key().as_ref()
+        ],
+        bump = listing.bump,
+        has_one = exchange @ VoucherExchangeError::InvalidExchange,
+        constraint = listing.owner == owner.key() @ VoucherExchangeError::NotListingOwner,
+        constraint = listing.active @ VoucherExchangeError::ListingNotActive,
+        constraint = listing.english_auction.is_none() @ VoucherExchangeError::AuctionListing,
+        constraint = listing.dutch_auction.is_none() @ VoucherExchangeError::DutchAuctionListing
+    )]
+    pub listing: Account<'info, VoucherListing>,
+
+    #[account(
+        seeds = [EXCHANGE_SEED, exchange.namespace.as_bytes()],
+        bump = exchange.bump,
+        constraint = !exchange.is_paused(PAUSE_UPDATE_LISTING) @ VoucherExchangeError::ExchangePaused,
+        constraint = !exchange.winding_down @ VoucherExchangeError::ExchangeWindingDown
+    )]
+    pub exchange: Account<'info, VoucherExchange>,
+
+    pub owner: Signer<'info>,
+
+    /// CHECK: BlockedWallet PDA of the owner, must not exist
+    #[account(
+        seeds = [
+            BLOCKED_WALLET_SEED,
+            exchange.key().as_ref(),
+            owner.key().as_ref()
+        ],
+        bump,
+        constraint = owner_blocked_wallet.data_is_empty() @ VoucherExchangeError::WalletBlocked
+    )]
+    pub owner_blocked_wallet: UncheckedAccount<'info>,
+
+    pub nft_mint: InterfaceAccount<'info, Mint>,
+
+    /// CHECK: Metaplex metadata PDA of the NFT, parsed in the handler if it exists
+    #[account(
+        seeds = [
+            mpl_token_metadata::accounts::Metadata::PREFIX,
+            token_metadata_program.key().as_ref(),
+            nft_mint.key().as_ref()
+        ],
+        seeds::program = token_metadata_program.key(),
+        bump
+    )]
+    pub nft_metadata: UncheckedAccount<'info>,
+
+    /// CHECK: Override for the NFT's verified collection, validated in the handler
+    pub collection_config: Option<UncheckedAccount<'info>>,
+
+    // New payment mint, which may be the current one
+    pub payment_mint: InterfaceAccount<'info, Mint>,
+
+    #[account(
+        seeds = [
+            PAYMENT_MINT_CONFIG_SEED,
+            exchange.key().as_ref(),
+            payment_mint.key().as_ref()
+        ],
+        bump = payment_mint_config.bump
+    )]
+    pub payment_mint_config: Account<'info, PaymentMintConfig>,
+
+    pub token_metadata_program: Program<'info, Metadata>,
+}
+
+// Replaces the price, payment mint and expiry of a fixed-price listing while the NFT stays
+// in escrow. Passing no expiry removes it.
+pub fn handler(
+    ctx: Context<UpdateVoucherListing>,
+    price: u64,
+    expires_at: Option<i64>,
+) -> Result<()> {
+    // Check price is valid
+    require!(price > 0, VoucherExchangeError::InvalidPrice);
+
+    // Check expiry, if any, is in the future
+    if let Some(expires_at) = expires_at {
+        require!(
+            expires_at > Clock::get()?.unix_timestamp,
+            VoucherExchangeError::InvalidExpiry
+        );
+    }
+
+    // Check the payment mint is accepted at this price
+    let payment_mint_config = &ctx.accounts.payment_mint_config;
+    require!(
+        ctx.accounts.payment_mint.decimals == payment_mint_config.decimals,
+        VoucherExchangeError::InvalidPaymentMintDecimals
+    );
+    require!(
+        price >= payment_mint_config.min_price,
+        VoucherExchangeError::PriceBelowMinimum
+    );
+
+    // Check price is within the collection range, if the collection has an override
+    let metadata = read_metadata(&ctx.accounts.nft_metadata)?;
+    let collection_config = load_collection_config(
+        &ctx.accounts.exchange.key(),
+        metadata.as_ref(),
+        ctx.accounts.collection_config.as_deref(),
+    )?;
+    if let Some(config) = &collection_config {
+        require!(config.is_price_allowed(price), VoucherExchangeError::PriceOutOfRange);
+    }
+
+    let listing = &mut ctx.accounts.listing;
+    listing.price = price;
+    listing.payment_mint = ctx.accounts.payment_mint.key();
+    listing.expires_at = expires_at.unwrap_or(0);
+
+    msg!("Listing {} updated to {} of {}", listing.key(), price, listing.payment_mint);
+
+    Ok(())
+}
@@ -0,0 +1,99 @@
+use anchor_lang::prelude::*;
+use anchor_spl::token_interface::{
+    TokenAccount, Mint, TokenInterface, TransferChecked, transfer_checked
+};
+use crate::state::*;
+use crate::errors::*;
+use crate::constants::*;
+
+#[derive(Accounts)]
+pub struct WithdrawFees<'info> {
+    #[account(
+        mut,
+        seeds = [
+            FEE_VAULT_SEED,
+            exchange.key().as_ref(),
+            payment_mint.key().as_ref()
+        ],
+        bump = fee_vault.bump,
+        has_one = exchange @ VoucherExchangeError::InvalidExchange
+    )]
+    pub fee_vault: Account<'info, FeeVault>,
+
+    #[account(
+        mut,
+        address = fee_vault.vault @ VoucherExchangeError::InvalidFeeAccount
+    )]
+    pub vault_token_account: InterfaceAccount<'info, TokenAccount>,
+
+    #[account(
+        mut,
+        constraint = treasury_token_account.mint == payment_mint.key() @ VoucherExchangeError::InvalidFeeAccount,
+        constraint = treasury_token_account.owner == exchange.fee_recipient @ VoucherExchangeError::InvalidFeeAccount,
+    )]
+    pub treasury_token_account: InterfaceAccount<'info, TokenAccount>,
+
+    #[account(
+        constraint = exchange.has_role(ExchangeRole::FeeAdmin, &authority.key()) @ VoucherExchangeError::MissingRole,
+    )]
+    pub authority: Signer<'info>,
+
+    #[account(
+        seeds = [EXCHANGE_SEED, exchange.namespace.as_bytes()],
+        bump = exchange.bump
+    )]
+    pub exchange: Account<'info, VoucherExchange>,
+
+    pub payment_mint: InterfaceAccount<'info, Mint>,
+
+    pub token_program: Interface<'info, TokenInterface>,
+}
+
+pub fn handler(
+    ctx: Context<WithdrawFees>,
+) -> Result<()> {
+    // Sweep the whole vault balance
+    let amount = ctx.accounts.vault_token_account.amount;
+    require!(amount > 0, VoucherExchangeError::NoFeesToWithdraw);
+
+    let exchange_key = ctx.accounts.exchange.key();
+    let payment_mint_key = ctx.accounts.payment_mint.key();
+    let fee_vault_seeds = &[
+        FEE_VAULT_SEED,
+        exchange_key.as_ref(),
+        payment_mint_key.as_ref(),
+        &[ctx.accounts.fee_vault.bump],
+    ];
+    let signer_seeds = &[&fee_vault_seeds[..]];
+
+    // Transfer collected fees from the vault to the treasury
+    transfer_checked(
+        CpiContext::new_with_signer(
+            ctx.accounts.token_program.to_account_info(),
+            TransferChecked {
+                from: ctx.accounts.vault_token_account.to_account_info(),
+                mint: ctx.accounts.payment_mint.to_account_info(),
+                to: ctx.accounts.treasury_token_account.to_account_info(),
+                authority: ctx.accounts.fee_vault.to_account_info(),
+            },
+            signer_seeds,
+        ),
+        amount,
+        ctx.accounts.payment_mint.decimals,
+    )?;
+
+    // Update fee accounting
+    let fee_vault = &mut ctx.accounts.fee_vault;
+    fee_vault.unclaimed_fees = 0;
+    fee_vault.total_withdrawn = fee_vault.total_withdrawn
+        .checked_add(amount)
+        .ok_or(VoucherExchangeError::MathOverflow)?;
+
+    msg!(
+        "Withdrew {} fees of mint {} to treasury",
+        amount,
+        payment_mint_key
+    );
+
+    Ok(())
+}
@@ -0,0 +1,115 @@
+use anchor_lang::prelude::*;
+use anchor_lang::Discriminator;
+use crate::state::*;
+use crate::errors::*;
+
+// Layouts of the accounts created before exchanges were namespaced and versioned. Listings
+// and bids sit at the old un-namespaced PDAs and can only be unwound by the return_legacy_*
+// instructions, while voucher states are upgraded in place by migrate_voucher_state.
+
+#[derive(AnchorSerialize, AnchorDeserialize)]
+pub struct LegacyVoucherListing {
+    pub owner: Pubkey,
+    pub nft_mint: Pubkey,
+    pub nft_account: Pubkey,
+    pub price: u64,
+    pub payment_mint: Pubkey,
+    pub active: bool,
+    pub bump: u8,
+}
+
+#[derive(AnchorSerialize, AnchorDeserialize)]
+pub struct LegacyVoucherBid {
+    pub bidder: Pubkey,
+    pub nft_mint: Pubkey,
+    pub price: u64,
+    pub payment_mint: Pubkey,
+    pub escrow_account: Pubkey,
+    pub active: bool,
+    pub requires_refund: bool,
+    pub bump: u8,
+    pub escrow_bump: u8,
+}
+
+#[derive(AnchorSerialize, AnchorDeserialize)]
+pub struct LegacyVoucherState {
+    pub nft_mint: Pubkey,
+    pub sold: bool,
+    pub latest_sale_timestamp: i64,
+    pub bump: u8,
+}
+
+impl LegacyVoucherListing {
+    pub const SIZE: usize = 8 +      // discriminator
+        32 +                         // owner
+        32 +                         // nft_mint
+        32 +                         // nft_account
+        8 +                          // price
+        32 +                         // payment_mint
+        1 +                          // active
+        1;                           // bump
+
+    pub fn load(account: &AccountInfo) -> Result<Self> {
+        load_legacy(account, VoucherListing::DISCRIMINATOR, Self::SIZE)
+    }
+}
+
+impl LegacyVoucherBid {
+    pub const SIZE: usize = 8 +      // discriminator
+        32 +                         // bidder
+        32 +                         // nft_mint
+        8 +                          // price
+        32 +                         // payment_mint
+        32 +                         // escrow_account
+        1 +                          // active
+        1 +                          // requires_refund
+        1 +                          // bump
+        1;                           // escrow_bump
+
+    pub fn load(account: &AccountInfo) -> Result<Self> {
+        load_legacy(account, VoucherBid::DISCRIMINATOR, Self::SIZE)
+    }
+}
+
+impl LegacyVoucherState {
+    pub const SIZE: usize = 8 +      // discriminator
+        32 +                         // nft_mint
+        1 +                          // sold
+        8 +                          // latest_sale_timestamp
+        1;                           // bump
+}
+
+// Decodes a legacy account, identified by its discriminator and exact size
+fn load_legacy<T: AnchorDeserialize>(
+    account: &AccountInfo,
+    discriminator: &[u8],
+    size: usize,
+) -> Result<T> {
+    require_keys_eq!(*account.owner, crate::ID, ErrorCode::AccountOwnedByWrongProgram);
+
+    let data = account.try_borrow_data()?;
+    require!(
+        data.starts_with(discriminator),
+        ErrorCode::AccountDiscriminatorMismatch
+    );
+    require!(data.len() == size, VoucherExchangeError::UnknownAccountLayout);
+
+    T::deserialize(&mut &data[discriminator.len()..])
+        .map_err(|_| error!(ErrorCode::AccountDidNotDeserialize))
+}
+
+// Closes a legacy account, moving its rent to `destination`
+pub fn close_legacy_account<'info>(
+    account: &AccountInfo<'info>,
+    destination: &AccountInfo<'info>,
+) -> Result<()> {
+    let lamports = destination
+        .lamports()
+        .checked_add(account.lamports())
+        .ok_or(VoucherExchangeError::MathOverflow)?;
+    **destination.try_borrow_mut_lamports()? = lamports;
+    **account.try_borrow_mut_lamports()? = 0;
+
+    account.assign(&anchor_lang::system_program::ID);
+    account.realloc(0, false).map_err(Into::into)
+}
@@ -4,8 +4,15 @@ pub mod state;
 pub mod errors;
 pub mod instructions;
 pub mod constants;
+pub mod settlement;
+pub mod collection;
+pub mod events;
+pub mod migration;
+pub mod rescue;
+pub mod legacy;
 
 use instructions::*;
+use state::{RoyaltyPolicy, FeeDiscountTier, PayoutSplit, ExchangeRole, DutchAuction};
 
 declare_id!("CnupugmHFWwcaq1wnK9xfUibxX2LDaPbjQp9aA7trHjL");
 
@@ -14,16 +21,41 @@ pub mod voucher_exchange {
     use super::*;
 
     pub fn initialize_exchange(
-        ctx: Context<InitializeExchange>
+        ctx: Context<InitializeExchange>,
+        namespace: String,
+        maker_fee_basis_points: u16,
+        taker_fee_basis_points: u16,
+        fee_recipient: Pubkey,
+        fee_update_delay: i64,
     ) -> Result<()> {
-        instructions::initialize_exchange::handler(ctx)
+        instructions::initialize_exchange::handler(
+            ctx,
+            namespace,
+            maker_fee_basis_points,
+            taker_fee_basis_points,
+            fee_recipient,
+            fee_update_delay,
+        )
     }
 
     pub fn create_voucher_listing(
         ctx: Context<CreateVoucherListing>,
         price: u64,
+        payout_splits: Vec<PayoutSplit>,
+        expires_at: Option<i64>,
+        reserved_buyer: Option<Pubkey>,
+        dutch_auction: Option<DutchAuction>,
+        english_auction: Option<EnglishAuctionParams>,
     ) -> Result<()> {
-        instructions::create_voucher_listing::handler(ctx, price)
+        instructions::create_voucher_listing::handler(
+            ctx,
+            price,
+            payout_splits,
+            expires_at,
+            reserved_buyer,
+            dutch_auction,
+            english_auction,
+        )
     }
 
     pub fn create_voucher_bid(
@@ -34,16 +66,17 @@ pub mod voucher_exchange {
         instructions::create_voucher_bid::handler(ctx, price, escrow_bump)
     }
 
-    pub fn accept_voucher_bid(
-        ctx: Context<AcceptVoucherBid>
+    pub fn accept_voucher_bid<'info>(
+        ctx: Context<'_, '_, 'info, 'info, AcceptVoucherBid<'info>>
     ) -> Result<()> {
         instructions::accept_voucher_bid::handler(ctx)
     }
 
-    pub fn fulfill_voucher_listing(
-        ctx: Context<FulfillVoucherListing>
+    pub fn fulfill_voucher_listing<'info>(
+        ctx: Context<'_, '_, 'info, 'info, FulfillVoucherListing<'info>>,
+        max_price: u64,
     ) -> Result<()> {
-        instructions::fulfill_voucher_listing::handler(ctx)
+        instructions::fulfill_voucher_listing::handler(ctx, max_price)
     }
 
     pub fn cancel_voucher_listing(
@@ -69,4 +102,325 @@ pub mod voucher_exchange {
     ) -> Result<()> {
         instructions::refund_bid::handler(ctx)
     }
+
+    pub fn propose_fee_update(
+        ctx: Context<ProposeFeeUpdate>,
+        maker_fee_basis_points: u16,
+        taker_fee_basis_points: u16,
+        fee_recipient: Pubkey,
+    ) -> Result<()> {
+        instructions::propose_fee_update::handler(ctx, maker_fee_basis_points, taker_fee_basis_points, fee_recipient)
+    }
+
+    pub fn cancel_fee_update(
+        ctx: Context<CancelFeeUpdate>
+    ) -> Result<()> {
+        instructions::cancel_fee_update::handler(ctx)
+    }
+
+    pub fn apply_fee_update(
+        ctx: Context<ApplyFeeUpdate>
+    ) -> Result<()> {
+        instructions::apply_fee_update::handler(ctx)
+    }
+
+    pub fn set_royalty_policy(
+        ctx: Context<SetRoyaltyPolicy>,
+        royalty_policy: RoyaltyPolicy,
+        royalty_cap_basis_points: u16,
+    ) -> Result<()> {
+        instructions::set_royalty_policy::handler(ctx, royalty_policy, royalty_cap_basis_points)
+    }
+
+    pub fn set_collection_config(
+        ctx: Context<SetCollectionConfig>,
+        collection_mint: Pubkey,
+        params: CollectionConfigParams,
+    ) -> Result<()> {
+        instructions::set_collection_config::handler(ctx, collection_mint, params)
+    }
+
+    pub fn remove_collection_config(
+        ctx: Context<RemoveCollectionConfig>
+    ) -> Result<()> {
+        instructions::remove_collection_config::handler(ctx)
+    }
+
+    pub fn initialize_fee_vault(
+        ctx: Context<InitializeFeeVault>
+    ) -> Result<()> {
+        instructions::initialize_fee_vault::handler(ctx)
+    }
+
+    pub fn withdraw_fees(
+        ctx: Context<WithdrawFees>
+    ) -> Result<()> {
+        instructions::withdraw_fees::handler(ctx)
+    }
+
+    pub fn set_referrer(
+        ctx: Context<SetReferrer>,
+        wallet: Pubkey,
+        fee_share_basis_points: u16,
+        paused: bool,
+    ) -> Result<()> {
+        instructions::set_referrer::handler(ctx, wallet, fee_share_basis_points, paused)
+    }
+
+    pub fn remove_referrer(
+        ctx: Context<RemoveReferrer>
+    ) -> Result<()> {
+        instructions::remove_referrer::handler(ctx)
+    }
+
+    pub fn set_fee_discount_tiers(
+        ctx: Context<SetFeeDiscountTiers>,
+        tiers: Vec<FeeDiscountTier>,
+    ) -> Result<()> {
+        instructions::set_fee_discount_tiers::handler(ctx, tiers)
+    }
+
+    pub fn set_fee_burn_rate(
+        ctx: Context<SetFeeBurnRate>,
+        fee_burn_basis_points: u16,
+    ) -> Result<()> {
+        instructions::set_fee_burn_rate::handler(ctx, fee_burn_basis_points)
+    }
+
+    pub fn propose_authority(
+        ctx: Context<ProposeAuthority>,
+        new_authority: Pubkey,
+    ) -> Result<()> {
+        instructions::propose_authority::handler(ctx, new_authority)
+    }
+
+    pub fn accept_authority(
+        ctx: Context<AcceptAuthority>
+    ) -> Result<()> {
+        instructions::accept_authority::handler(ctx)
+    }
+
+    pub fn set_role(
+        ctx: Context<SetRole>,
+        role: ExchangeRole,
+        holder: Pubkey,
+    ) -> Result<()> {
+        instructions::set_role::handler(ctx, role, holder)
+    }
+
+    pub fn revoke_role(
+        ctx: Context<RevokeRole>,
+        role: ExchangeRole,
+    ) -> Result<()> {
+        instructions::revoke_role::handler(ctx, role)
+    }
+
+    pub fn set_paused_operations(
+        ctx: Context<SetPausedOperations>,
+        paused_operations: u8,
+    ) -> Result<()> {
+        instructions::set_paused_operations::handler(ctx, paused_operations)
+    }
+
+    pub fn set_payment_mint_config(
+        ctx: Context<SetPaymentMintConfig>,
+        decimals: u8,
+        min_price: u64,
+    ) -> Result<()> {
+        instructions::set_payment_mint_config::handler(ctx, decimals, min_price)
+    }
+
+    pub fn remove_payment_mint_config(
+        ctx: Context<RemovePaymentMintConfig>
+    ) -> Result<()> {
+        instructions::remove_payment_mint_config::handler(ctx)
+    }
+
+    pub fn approve_collection(
+        ctx: Context<ApproveCollection>,
+        collection_mint: Pubkey,
+    ) -> Result<()> {
+        instructions::approve_collection::handler(ctx, collection_mint)
+    }
+
+    pub fn revoke_collection(
+        ctx: Context<RevokeCollection>
+    ) -> Result<()> {
+        instructions::revoke_collection::handler(ctx)
+    }
+
+    pub fn block_wallet(
+        ctx: Context<BlockWallet>,
+        wallet: Pubkey,
+    ) -> Result<()> {
+        instructions::block_wallet::handler(ctx, wallet)
+    }
+
+    pub fn unblock_wallet(
+        ctx: Context<UnblockWallet>
+    ) -> Result<()> {
+        instructions::unblock_wallet::handler(ctx)
+    }
+
+    pub fn migrate_exchange(
+        ctx: Context<MigrateExchange>
+    ) -> Result<()> {
+        instructions::migrate_exchange::handler(ctx)
+    }
+
+    pub fn migrate_voucher_listing(
+        ctx: Context<MigrateVoucherListing>
+    ) -> Result<()> {
+        instructions::migrate_voucher_listing::handler(ctx)
+    }
+
+    pub fn migrate_voucher_bid(
+        ctx: Context<MigrateVoucherBid>
+    ) -> Result<()> {
+        instructions::migrate_voucher_bid::handler(ctx)
+    }
+
+    pub fn migrate_voucher_state(
+        ctx: Context<MigrateVoucherState>
+    ) -> Result<()> {
+        instructions::migrate_voucher_state::handler(ctx)
+    }
+
+    pub fn set_recovery_address(
+        ctx: Context<SetRecoveryAddress>,
+        recovery: Pubkey,
+    ) -> Result<()> {
+        instructions::set_recovery_address::handler(ctx, recovery)
+    }
+
+    pub fn remove_recovery_address(
+        ctx: Context<RemoveRecoveryAddress>
+    ) -> Result<()> {
+        instructions::remove_recovery_address::handler(ctx)
+    }
+
+    pub fn announce_listing_rescue(
+        ctx: Context<AnnounceListingRescue>
+    ) -> Result<()> {
+        instructions::announce_listing_rescue::handler(ctx)
+    }
+
+    pub fn announce_bid_rescue(
+        ctx: Context<AnnounceBidRescue>
+    ) -> Result<()> {
+        instructions::announce_bid_rescue::handler(ctx)
+    }
+
+    pub fn cancel_rescue(
+        ctx: Context<CancelRescue>
+    ) -> Result<()> {
+        instructions::cancel_rescue::handler(ctx)
+    }
+
+    pub fn execute_listing_rescue(
+        ctx: Context<ExecuteListingRescue>
+    ) -> Result<()> {
+        instructions::execute_listing_rescue::handler(ctx)
+    }
+
+    pub fn execute_bid_rescue(
+        ctx: Context<ExecuteBidRescue>
+    ) -> Result<()> {
+        instructions::execute_bid_rescue::handler(ctx)
+    }
+
+    pub fn begin_wind_down(
+        ctx: Context<BeginWindDown>
+    ) -> Result<()> {
+        instructions::begin_wind_down::handler(ctx)
+    }
+
+    pub fn return_listing(
+        ctx: Context<ReturnListing>
+    ) -> Result<()> {
+        instructions::return_listing::handler(ctx)
+    }
+
+    pub fn return_bid(
+        ctx: Context<ReturnBid>
+    ) -> Result<()> {
+        instructions::return_bid::handler(ctx)
+    }
+
+    pub fn close_exchange(
+        ctx: Context<CloseExchange>
+    ) -> Result<()> {
+        instructions::close_exchange::handler(ctx)
+    }
+
+    pub fn expire_voucher_listing(
+        ctx: Context<ExpireVoucherListing>
+    ) -> Result<()> {
+        instructions::expire_voucher_listing::handler(ctx)
+    }
+
+    pub fn update_voucher_listing(
+        ctx: Context<UpdateVoucherListing>,
+        price: u64,
+        expires_at: Option<i64>,
+    ) -> Result<()> {
+        instructions::update_voucher_listing::handler(ctx, price, expires_at)
+    }
+
+    pub fn place_auction_bid(
+        ctx: Context<PlaceAuctionBid>,
+        amount: u64,
+    ) -> Result<()> {
+        instructions::place_auction_bid::handler(ctx, amount)
+    }
+
+    pub fn settle_auction<'info>(
+        ctx: Context<'_, '_, 'info, 'info, SettleAuction<'info>>
+    ) -> Result<()> {
+        instructions::settle_auction::handler(ctx)
+    }
+
+    pub fn propose_collection_fee_update(
+        ctx: Context<ProposeCollectionFeeUpdate>,
+        maker_fee_basis_points: u16,
+        taker_fee_basis_points: u16,
+    ) -> Result<()> {
+        instructions::propose_collection_fee_update::handler(ctx, maker_fee_basis_points, taker_fee_basis_points)
+    }
+
+    pub fn cancel_collection_fee_update(
+        ctx: Context<CancelCollectionFeeUpdate>
+    ) -> Result<()> {
+        instructions::cancel_collection_fee_update::handler(ctx)
+    }
+
+    pub fn apply_collection_fee_update(
+        ctx: Context<ApplyCollectionFeeUpdate>
+    ) -> Result<()> {
+        instructions::apply_collection_fee_update::handler(ctx)
+    }
+
+    pub fn return_legacy_listing(
+        ctx: Context<ReturnLegacyListing>
+    ) -> Result<()> {
+        instructions::return_legacy_listing::handler(ctx)
+    }
+
+    pub fn return_legacy_bid(
+        ctx: Context<ReturnLegacyBid>
+    ) -> Result<()> {
+        instructions::return_legacy_bid::handler(ctx)
+    }
+
+    pub fn close_fee_vault(
+        ctx: Context<CloseFeeVault>
+    ) -> Result<()> {
+        instructions::close_fee_vault::handler(ctx)
+    }
+
+    pub fn refund_auction_bid(
+        ctx: Context<RefundAuctionBid>
+    ) -> Result<()> {
+        instructions::refund_auction_bid::handler(ctx)
+    }
 }
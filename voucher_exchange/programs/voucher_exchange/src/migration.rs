@@ -0,0 +1,64 @@
+use anchor_lang::prelude::*;
+use anchor_lang::system_program::{transfer, Transfer};
+use anchor_lang::Discriminator;
+use std::ops::RangeInclusive;
+use crate::errors::*;
+
+// Grows a program account to `size`, topping up its rent from `payer`, then rewrites it
+// with the account `decode` builds from its data. `decode` receives the size the account
+// was allocated at, which identifies its layout, and the data zero-extended to `size`.
+pub fn migrate_account<'info, T>(
+    account: &AccountInfo<'info>,
+    payer: &AccountInfo<'info>,
+    system_program: &AccountInfo<'info>,
+    size: usize,
+    decode: impl FnOnce(usize, &[u8]) -> Result<T>,
+) -> Result<()>
+where
+    T: AccountSerialize + Discriminator,
+{
+    // Check the account is of the expected type before touching its size
+    require!(
+        account.try_borrow_data()?.starts_with(T::DISCRIMINATOR),
+        ErrorCode::AccountDiscriminatorMismatch
+    );
+
+    let allocated_size = account.data_len();
+    if allocated_size < size {
+        let rent_due = Rent::get()?
+            .minimum_balance(size)
+            .saturating_sub(account.lamports());
+        if rent_due > 0 {
+            transfer(
+                CpiContext::new(
+                    system_program.clone(),
+                    Transfer {
+                        from: payer.clone(),
+                        to: account.clone(),
+                    },
+                ),
+                rent_due,
+            )?;
+        }
+        account.realloc(size, false)?;
+    }
+
+    let mut data = account.try_borrow_mut_data()?;
+    let migrated = decode(allocated_size, &data)?;
+
+    let mut writer: &mut [u8] = &mut data[..];
+    migrated.try_serialize(&mut writer)
+}
+
+// Checks an account read at `version` can be upgraded to `current_version`, where
+// `versions` are the layout versions its allocated size was created at
+pub fn require_upgradable(
+    version: u8,
+    versions: RangeInclusive<u8>,
+    current_version: u8,
+) -> Result<()> {
+    require!(version < current_version, VoucherExchangeError::AccountAlreadyMigrated);
+    require!(versions.contains(&version), VoucherExchangeError::UnknownAccountLayout);
+
+    Ok(())
+}
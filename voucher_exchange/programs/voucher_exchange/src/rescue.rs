@@ -0,0 +1,61 @@
+use anchor_lang::prelude::*;
+use crate::state::*;
+use crate::errors::*;
+use crate::constants::*;
+use crate::events::*;
+
+// Resolves where the rescued assets of `wallet` go: its recovery address whenever it has
+// designated one, since the always-passed PDA cannot be left out, or else the wallet itself
+pub fn rescue_destination(recovery_address: &AccountInfo, wallet: Pubkey) -> Result<Pubkey> {
+    if recovery_address.data_is_empty() {
+        return Ok(wallet);
+    }
+    let recovery_address = RecoveryAddress::try_deserialize(&mut &recovery_address.data.borrow()[..])?;
+    Ok(recovery_address.recovery)
+}
+
+// Records a rescue of the escrow behind `target`, executable once the timelock has passed
+pub fn announce_rescue(
+    rescue_request: &mut RescueRequest,
+    exchange: Pubkey,
+    target: Pubkey,
+    target_nonce: u64,
+    destination: Pubkey,
+    bump: u8,
+) -> Result<()> {
+    let now = Clock::get()?.unix_timestamp;
+    let executable_at = now
+        .checked_add(RESCUE_TIMELOCK)
+        .ok_or(VoucherExchangeError::MathOverflow)?;
+
+    rescue_request.exchange = exchange;
+    rescue_request.target = target;
+    rescue_request.destination = destination;
+    rescue_request.target_nonce = target_nonce;
+    rescue_request.announced_at = now;
+    rescue_request.executable_at = executable_at;
+    rescue_request.bump = bump;
+
+    emit!(RescueAnnounced {
+        exchange,
+        target,
+        destination,
+        executable_at,
+    });
+
+    Ok(())
+}
+
+// Checks the rescue was announced for this lifetime of the target and its timelock has passed
+pub fn require_rescue_executable(rescue_request: &RescueRequest, target_nonce: u64) -> Result<()> {
+    require!(
+        rescue_request.target_nonce == target_nonce,
+        VoucherExchangeError::StaleRescueRequest
+    );
+    require!(
+        Clock::get()?.unix_timestamp >= rescue_request.executable_at,
+        VoucherExchangeError::RescueTimelockActive
+    );
+
+    Ok(())
+}
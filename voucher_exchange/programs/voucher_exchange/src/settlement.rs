@@ -0,0 +1,415 @@
+use anchor_lang::prelude::*;
+use anchor_spl::metadata::MetadataAccount;
+use anchor_spl::token_interface::{
+    TokenAccount, Mint, TransferChecked, transfer_checked, BurnChecked, burn_checked
+};
+use crate::state::*;
+use crate::errors::*;
+use crate::constants::*;
+use crate::events::*;
+
+// Marketplace fee owed on a sale of `price` at `fee_basis_points`
+pub fn calculate_fee(price: u64, fee_basis_points: u16) -> Result<u64> {
+    let fee = (price as u128)
+        .checked_mul(fee_basis_points as u128)
+        .ok_or(VoucherExchangeError::MathOverflow)?
+        / BASIS_POINTS_DIVISOR as u128;
+
+    Ok(fee as u64)
+}
+
+// Fee and royalty terms of a sale, after applying any collection override
+pub struct SaleTerms {
+    pub maker_fee_basis_points: u16,
+    pub taker_fee_basis_points: u16,
+    pub royalty_policy: RoyaltyPolicy,
+    pub royalty_cap_basis_points: u16,
+}
+
+impl SaleTerms {
+    pub fn new(exchange: &VoucherExchange, collection_config: Option<&CollectionConfig>) -> Self {
+        match collection_config {
+            Some(config) => SaleTerms {
+                maker_fee_basis_points: config.maker_fee_basis_points,
+                taker_fee_basis_points: config.taker_fee_basis_points,
+                royalty_policy: config.royalty_policy,
+                royalty_cap_basis_points: config.royalty_cap_basis_points,
+            },
+            None => SaleTerms {
+                maker_fee_basis_points: exchange.maker_fee_basis_points,
+                taker_fee_basis_points: exchange.taker_fee_basis_points,
+                royalty_policy: exchange.royalty_policy,
+                royalty_cap_basis_points: exchange.royalty_cap_basis_points,
+            },
+        }
+    }
+
+    // Lowers the maker fee rate by the OPT holder discount of a buying maker (bidder)
+    pub fn apply_maker_discount(&mut self, discount_basis_points: u16) {
+        self.maker_fee_basis_points = self.maker_fee_basis_points.saturating_sub(discount_basis_points);
+    }
+
+    // Lowers the taker fee rate by the OPT holder discount of a buying taker
+    pub fn apply_taker_discount(&mut self, discount_basis_points: u16) {
+        self.taker_fee_basis_points = self.taker_fee_basis_points.saturating_sub(discount_basis_points);
+    }
+
+    pub fn royalty_basis_points(&self, metadata: &MetadataAccount, creator_accounts_supplied: bool) -> u16 {
+        self.royalty_policy.royalty_basis_points(
+            metadata.seller_fee_basis_points,
+            self.royalty_cap_basis_points,
+            creator_accounts_supplied,
+        )
+    }
+}
+
+// Token account the sale is paid from, along with what is needed to sign for it
+pub struct PaymentSource<'a, 'info> {
+    pub token_program: AccountInfo<'info>,
+    pub mint: &'a InterfaceAccount<'info, Mint>,
+    pub from: AccountInfo<'info>,
+    pub authority: AccountInfo<'info>,
+    pub signer_seeds: &'a [&'a [&'a [u8]]],
+}
+
+impl<'info> PaymentSource<'_, 'info> {
+    pub fn pay(&self, to: AccountInfo<'info>, amount: u64) -> Result<()> {
+        if amount == 0 {
+            return Ok(());
+        }
+
+        transfer_checked(
+            CpiContext::new_with_signer(
+                self.token_program.clone(),
+                TransferChecked {
+                    from: self.from.clone(),
+                    mint: self.mint.to_account_info(),
+                    to,
+                    authority: self.authority.clone(),
+                },
+                self.signer_seeds,
+            ),
+            amount,
+            self.mint.decimals,
+        )
+    }
+
+    pub fn burn(&self, amount: u64) -> Result<()> {
+        if amount == 0 {
+            return Ok(());
+        }
+
+        burn_checked(
+            CpiContext::new_with_signer(
+                self.token_program.clone(),
+                BurnChecked {
+                    mint: self.mint.to_account_info(),
+                    from: self.from.clone(),
+                    authority: self.authority.clone(),
+                },
+                self.signer_seeds,
+            ),
+            amount,
+            self.mint.decimals,
+        )
+    }
+}
+
+// Splits the remaining accounts of a settlement into the creator payment accounts
+// followed by one payment account per payout split of the listing
+pub fn split_remaining_accounts<'info>(
+    remaining_accounts: &'info [AccountInfo<'info>],
+    payout_split_count: usize,
+) -> Result<(&'info [AccountInfo<'info>], &'info [AccountInfo<'info>])> {
+    let creator_count = remaining_accounts
+        .len()
+        .checked_sub(payout_split_count)
+        .ok_or(VoucherExchangeError::InvalidPayoutAccount)?;
+
+    Ok(remaining_accounts.split_at(creator_count))
+}
+
+// Pays each verified creator their share of the royalty and returns the total paid.
+// `creator_accounts` holds one payment token account per verified creator with a
+// non-zero share, in the order they appear in the metadata.
+pub fn pay_royalties<'info>(
+    source: &PaymentSource<'_, 'info>,
+    metadata: &MetadataAccount,
+    royalty_basis_points: u16,
+    price: u64,
+    creator_accounts: &'info [AccountInfo<'info>],
+) -> Result<u64> {
+    let royalty = calculate_fee(price, royalty_basis_points)?;
+    if royalty == 0 {
+        return Ok(0);
+    }
+
+    let creators = metadata
+        .creators
+        .iter()
+        .flatten()
+        .filter(|creator| creator.verified && creator.share > 0);
+
+    let mut paid: u64 = 0;
+    let mut remaining_accounts = creator_accounts.iter();
+    for creator in creators {
+        let creator_account_info = remaining_accounts
+            .next()
+            .ok_or(VoucherExchangeError::InvalidCreatorAccount)?;
+        let creator_account = InterfaceAccount::<TokenAccount>::try_from(creator_account_info)?;
+        require!(
+            creator_account.mint == source.mint.key(),
+            VoucherExchangeError::InvalidCreatorAccount
+        );
+        require!(
+            creator_account.owner == creator.address,
+            VoucherExchangeError::InvalidCreatorAccount
+        );
+
+        let amount = (royalty as u128)
+            .checked_mul(creator.share as u128)
+            .ok_or(VoucherExchangeError::MathOverflow)?
+            / 100;
+        source.pay(creator_account_info.clone(), amount as u64)?;
+        paid = paid.checked_add(amount as u64).ok_or(VoucherExchangeError::MathOverflow)?;
+    }
+
+    // Every supplied account must belong to a creator
+    require!(
+        remaining_accounts.next().is_none(),
+        VoucherExchangeError::InvalidCreatorAccount
+    );
+
+    Ok(paid)
+}
+
+// Pays the referrer's share of the marketplace fee and returns the amount paid.
+// The referrer and its payment account must be passed together.
+pub fn pay_referral<'info>(
+    source: &PaymentSource<'_, 'info>,
+    exchange: &Pubkey,
+    fee: u64,
+    referrer: Option<&mut Account<'info, Referrer>>,
+    referrer_payment_account: Option<&InterfaceAccount<'info, TokenAccount>>,
+) -> Result<u64> {
+    let (referrer, referrer_payment_account) = match (referrer, referrer_payment_account) {
+        (Some(referrer), Some(referrer_payment_account)) => (referrer, referrer_payment_account),
+        (None, None) => return Ok(0),
+        _ => return err!(VoucherExchangeError::InvalidReferrer),
+    };
+
+    require_keys_eq!(referrer.exchange, *exchange, VoucherExchangeError::InvalidReferrer);
+    require!(!referrer.paused, VoucherExchangeError::ReferrerPaused);
+    require_keys_eq!(
+        referrer_payment_account.owner,
+        referrer.wallet,
+        VoucherExchangeError::InvalidReferrer
+    );
+    require_keys_eq!(
+        referrer_payment_account.mint,
+        source.mint.key(),
+        VoucherExchangeError::InvalidReferrer
+    );
+
+    let referral = calculate_fee(fee, referrer.fee_share_basis_points)?;
+    source.pay(referrer_payment_account.to_account_info(), referral)?;
+
+    referrer.total_referrals = referrer.total_referrals
+        .checked_add(1)
+        .ok_or(VoucherExchangeError::MathOverflow)?;
+
+    Ok(referral)
+}
+
+// Fee discount the buyer qualifies for through their OPT balance.
+// The discount config and the buyer's OPT token account must be passed together.
+pub fn fee_discount_basis_points(
+    buyer: &Pubkey,
+    fee_discount_config: Option<&FeeDiscountConfig>,
+    buyer_opt_account: Option<&InterfaceAccount<TokenAccount>>,
+) -> Result<u16> {
+    let (config, opt_account) = match (fee_discount_config, buyer_opt_account) {
+        (Some(config), Some(opt_account)) => (config, opt_account),
+        (None, None) => return Ok(0),
+        _ => return err!(VoucherExchangeError::InvalidFeeDiscountAccount),
+    };
+
+    require_keys_eq!(opt_account.mint, OPT_MINT, VoucherExchangeError::InvalidFeeDiscountAccount);
+    require_keys_eq!(opt_account.owner, *buyer, VoucherExchangeError::InvalidFeeDiscountAccount);
+
+    Ok(config.discount_basis_points(opt_account.amount))
+}
+
+// Burns the exchange's share of an OPT-denominated fee and returns the amount burned.
+// Fees in any other mint are never burned.
+pub fn burn_fee(source: &PaymentSource, exchange: &mut VoucherExchange, fee: u64) -> Result<u64> {
+    if source.mint.key() != OPT_MINT {
+        return Ok(0);
+    }
+
+    let burned = calculate_fee(fee, exchange.fee_burn_basis_points)?;
+    source.burn(burned)?;
+
+    exchange.total_burned = exchange.total_burned
+        .checked_add(burned)
+        .ok_or(VoucherExchangeError::MathOverflow)?;
+
+    Ok(burned)
+}
+
+// Pays the seller proceeds to the listing owner, or across the payout splits when
+// the listing has any. The last split receives the rounding remainder.
+pub fn pay_seller<'info>(
+    source: &PaymentSource<'_, 'info>,
+    seller_amount: u64,
+    owner_payment_account: AccountInfo<'info>,
+    payout_splits: &[PayoutSplit],
+    payout_accounts: &'info [AccountInfo<'info>],
+) -> Result<()> {
+    if payout_splits.is_empty() {
+        return source.pay(owner_payment_account, seller_amount);
+    }
+
+    let mut remaining = seller_amount;
+    for (index, (split, payout_account_info)) in payout_splits.iter().zip(payout_accounts).enumerate() {
+        let payout_account = InterfaceAccount::<TokenAccount>::try_from(payout_account_info)?;
+        require_keys_eq!(payout_account.mint, source.mint.key(), VoucherExchangeError::InvalidPayoutAccount);
+        require_keys_eq!(payout_account.owner, split.recipient, VoucherExchangeError::InvalidPayoutAccount);
+
+        let amount = if index == payout_splits.len() - 1 {
+            remaining
+        } else {
+            calculate_fee(seller_amount, split.basis_points)?
+        };
+        source.pay(payout_account_info.clone(), amount)?;
+        remaining = remaining.checked_sub(amount).ok_or(VoucherExchangeError::MathOverflow)?;
+    }
+
+    Ok(())
+}
+
+// A sale being settled: who trades, at what price and on which terms
+pub struct Sale<'a> {
+    pub nft_mint: Pubkey,
+    pub seller: Pubkey,
+    pub buyer: Pubkey,
+    // Whether the buyer placed the resting order, i.e. the sale accepts a bid
+    pub buyer_is_maker: bool,
+    pub price: u64,
+    pub terms: SaleTerms,
+    pub metadata: Option<&'a MetadataAccount>,
+    // Fee deposit escrowed by the buyer, which caps their fee (none when they pay it directly)
+    pub buyer_fee_deposit: Option<u64>,
+}
+
+impl Sale<'_> {
+    // Maker and taker fees of the sale
+    pub fn fees(&self) -> Result<(u64, u64)> {
+        let mut maker_fee = calculate_fee(self.price, self.terms.maker_fee_basis_points)?;
+        let mut taker_fee = calculate_fee(self.price, self.terms.taker_fee_basis_points)?;
+        if let Some(fee_deposit) = self.buyer_fee_deposit {
+            let buyer_fee = if self.buyer_is_maker { &mut maker_fee } else { &mut taker_fee };
+            *buyer_fee = (*buyer_fee).min(fee_deposit);
+        }
+
+        Ok((maker_fee, taker_fee))
+    }
+
+    // Fee charged to the buyer, the maker fee for an accepted bid and the taker fee otherwise
+    pub fn buyer_fee(&self) -> Result<u64> {
+        let (maker_fee, taker_fee) = self.fees()?;
+        Ok(if self.buyer_is_maker { maker_fee } else { taker_fee })
+    }
+}
+
+// Accounts the payments of a sale go to
+pub struct SaleAccounts<'a, 'info> {
+    pub exchange: &'a mut Account<'info, VoucherExchange>,
+    pub fee_vault: &'a mut Account<'info, FeeVault>,
+    pub fee_vault_token_account: AccountInfo<'info>,
+    pub referrer: Option<&'a mut Account<'info, Referrer>>,
+    pub referrer_payment_account: Option<&'a InterfaceAccount<'info, TokenAccount>>,
+    pub seller_payment_account: AccountInfo<'info>,
+    pub payout_splits: &'a [PayoutSplit],
+    // Creator payment accounts followed by the payout split accounts, see `split_remaining_accounts`
+    pub remaining_accounts: &'info [AccountInfo<'info>],
+}
+
+// Pays a sale from `source`: the maker and taker fees, shared with the referrer if any and
+// partly burned when paid in OPT, the creator royalties and the seller proceeds, then emits
+// `VoucherSold`. Returns the fee charged to the buyer, so the caller can refund the unused
+// part of a fee deposit.
+pub fn settle_sale<'info>(
+    source: &PaymentSource<'_, 'info>,
+    sale: &Sale,
+    accounts: SaleAccounts<'_, 'info>,
+) -> Result<u64> {
+    let (maker_fee, taker_fee) = sale.fees()?;
+    let exchange_key = accounts.exchange.key();
+
+    // 1. Transfer maker and taker fees, sharing them with the referrer if any
+    let fee = maker_fee.checked_add(taker_fee).ok_or(VoucherExchangeError::MathOverflow)?;
+    let referral = pay_referral(
+        source,
+        &exchange_key,
+        fee,
+        accounts.referrer,
+        accounts.referrer_payment_account,
+    )?;
+    let remaining_fee = fee.checked_sub(referral).ok_or(VoucherExchangeError::MathOverflow)?;
+
+    // Burn part of the fee when paid in OPT, the rest goes to the fee vault
+    let burned = burn_fee(source, accounts.exchange, remaining_fee)?;
+    let vault_fee = remaining_fee.checked_sub(burned).ok_or(VoucherExchangeError::MathOverflow)?;
+    source.pay(accounts.fee_vault_token_account, vault_fee)?;
+    accounts.fee_vault.record_fee(vault_fee)?;
+
+    // 2. Transfer creator royalties to the verified creators
+    let (creator_accounts, payout_accounts) = split_remaining_accounts(
+        accounts.remaining_accounts,
+        accounts.payout_splits.len(),
+    )?;
+    let royalties = match sale.metadata {
+        Some(metadata) => {
+            let royalty_basis_points = sale.terms.royalty_basis_points(metadata, !creator_accounts.is_empty());
+            pay_royalties(source, metadata, royalty_basis_points, sale.price, creator_accounts)?
+        }
+        None => 0,
+    };
+
+    // 3. Transfer the remaining payment to the seller or their payout splits
+    let (maker, taker, seller_fee, buyer_fee) = if sale.buyer_is_maker {
+        (sale.buyer, sale.seller, taker_fee, maker_fee)
+    } else {
+        (sale.seller, sale.buyer, maker_fee, taker_fee)
+    };
+    let seller_amount = sale.price
+        .checked_sub(seller_fee)
+        .and_then(|amount| amount.checked_sub(royalties))
+        .ok_or(VoucherExchangeError::InsufficientProceeds)?;
+    pay_seller(
+        source,
+        seller_amount,
+        accounts.seller_payment_account,
+        accounts.payout_splits,
+        payout_accounts,
+    )?;
+
+    emit!(VoucherSold {
+        exchange: exchange_key,
+        nft_mint: sale.nft_mint,
+        payment_mint: source.mint.key(),
+        seller: sale.seller,
+        buyer: sale.buyer,
+        maker,
+        taker,
+        price: sale.price,
+        maker_fee,
+        taker_fee,
+        royalties,
+        seller_proceeds: seller_amount,
+        timestamp: Clock::get()?.unix_timestamp,
+    });
+
+    Ok(buyer_fee)
+}
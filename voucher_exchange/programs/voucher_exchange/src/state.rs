@@ -1,19 +1,153 @@
 use anchor_lang::prelude::*;
+use crate::errors::*;
+use crate::constants::*;
+
+// How creator royalties from NFT metadata are applied on secondary sales
+#[derive(AnchorSerialize, AnchorDeserialize, Clone, Copy, PartialEq, Eq, Debug)]
+pub enum RoyaltyPolicy {
+    // Creators are always paid their full royalty
+    Enforced,
+    // Creators are paid only when the taker supplies their payment accounts
+    Optional,
+    // Creators are always paid, up to the exchange royalty cap
+    Capped,
+}
+
+impl RoyaltyPolicy {
+    pub fn royalty_basis_points(
+        &self,
+        seller_fee_basis_points: u16,
+        royalty_cap_basis_points: u16,
+        creator_accounts_supplied: bool,
+    ) -> u16 {
+        match self {
+            RoyaltyPolicy::Enforced => seller_fee_basis_points,
+            RoyaltyPolicy::Optional if creator_accounts_supplied => seller_fee_basis_points,
+            RoyaltyPolicy::Optional => 0,
+            RoyaltyPolicy::Capped => seller_fee_basis_points.min(royalty_cap_basis_points),
+        }
+    }
+}
+
+#[derive(AnchorSerialize, AnchorDeserialize, Clone, Copy, PartialEq, Eq, Debug)]
+pub enum ExchangeRole {
+    // Manages fees, royalties, referrers, discounts and the fee vaults
+    FeeAdmin,
+    // Flags bids for refund
+    RefundOperator,
+    // Pauses and resumes trading
+    Pauser,
+    // Manages per-collection overrides
+    CollectionCurator,
+}
 
 #[account]
 pub struct VoucherExchange {
+    // Brand namespace the exchange PDA is derived from
+    pub namespace: String,
     // Authority that can manage the exchange
     pub authority: Pubkey,
+    // Proposed new authority, waiting to accept the handover (default if none)
+    pub pending_authority: Pubkey,
+    // Holder of the fee admin role (default if unassigned)
+    pub fee_admin: Pubkey,
+    // Holder of the refund operator role (default if unassigned)
+    pub refund_operator: Pubkey,
+    // Holder of the pauser role (default if unassigned)
+    pub pauser: Pubkey,
+    // Holder of the collection curator role (default if unassigned)
+    pub collection_curator: Pubkey,
     // Total number of listings created
     pub total_listings: u64,
     // Total number of bids created
     pub total_bids: u64,
+    // Fee charged to the maker (lister or bidder) of a sale, in basis points
+    pub maker_fee_basis_points: u16,
+    // Fee charged to the taker (buyer or bid acceptor) of a sale, in basis points
+    pub taker_fee_basis_points: u16,
+    // Treasury wallet that collected marketplace fees are withdrawn to
+    pub fee_recipient: Pubkey,
+    // Minimum delay in seconds before a proposed fee update can be applied
+    pub fee_update_delay: i64,
+    // Proposed maker fee in basis points, waiting for the delay to pass
+    pub pending_maker_fee_basis_points: u16,
+    // Proposed taker fee in basis points, waiting for the delay to pass
+    pub pending_taker_fee_basis_points: u16,
+    // Proposed fee recipient, waiting for the delay to pass
+    pub pending_fee_recipient: Pubkey,
+    // Timestamp from which the pending fee update can be applied (0 if none)
+    pub pending_fee_effective_at: i64,
+    // How creator royalties are applied on sales
+    pub royalty_policy: RoyaltyPolicy,
+    // Maximum royalty in basis points under the capped policy
+    pub royalty_cap_basis_points: u16,
+    // Share of OPT-denominated fees burned instead of collected, in basis points
+    pub fee_burn_basis_points: u16,
+    // Total OPT burned from fees
+    pub total_burned: u64,
+    // Bitflags of the paused operations, see the PAUSE_* constants
+    pub paused_operations: u8,
     // Bump for PDA derivation
     pub bump: u8,
+    // Layout version, see EXCHANGE_VERSION
+    pub version: u8,
+    // Whether the exchange is being retired: no new listings or bids, escrows can be returned
+    pub winding_down: bool,
+    // Number of open fee vaults, which must all be closed before the exchange closes
+    pub fee_vault_count: u16,
+    // Number of listing and bid lifetimes started, numbering each one
+    pub escrow_nonce: u64,
+    // Space kept free for fields added by later versions
+    pub reserved: [u8; 64],
+}
+
+#[derive(AnchorSerialize, AnchorDeserialize, Clone, Copy, PartialEq, Eq, Debug)]
+pub struct PayoutSplit {
+    // Wallet receiving this share of the seller proceeds
+    pub recipient: Pubkey,
+    // Share of the seller proceeds, in basis points
+    pub basis_points: u16,
+}
+
+// How the price of a Dutch auction falls from the listing price to the end price
+#[derive(AnchorSerialize, AnchorDeserialize, Clone, Copy, PartialEq, Eq, Debug)]
+pub enum PriceDecay {
+    // Falls continuously over the auction
+    Linear,
+    // Falls once every `interval` seconds, along the linear curve
+    Stepped { interval: i64 },
+}
+
+#[derive(AnchorSerialize, AnchorDeserialize, Clone, Copy, PartialEq, Eq, Debug)]
+pub struct DutchAuction {
+    // Price the auction settles at from `end_time` on
+    pub end_price: u64,
+    // Timestamp the price starts falling from the listing price
+    pub start_time: i64,
+    // Timestamp the price reaches `end_price`
+    pub end_time: i64,
+    // Shape of the price curve
+    pub decay: PriceDecay,
+}
+
+#[derive(AnchorSerialize, AnchorDeserialize, Clone, Copy, PartialEq, Eq, Debug)]
+pub struct EnglishAuction {
+    // Smallest raise over the top bid
+    pub min_increment: u64,
+    // Timestamp bidding closes, pushed back by bids placed near the end
+    pub end_time: i64,
+    // Highest bidder so far (default pubkey before the first bid)
+    pub top_bidder: Pubkey,
+    // Highest bid so far (0 before the first bid)
+    pub top_bid: u64,
+    // Taker fee escrowed with the top bid
+    pub fee_deposit: u64,
 }
 
 #[account]
 pub struct VoucherListing {
+    // Exchange the listing belongs to
+    pub exchange: Pubkey,
     // Owner of the NFT being listed
     pub owner: Pubkey,
     // The NFT mint address
@@ -28,10 +162,28 @@ pub struct VoucherListing {
     pub active: bool,
     // Bump for PDA derivation
     pub bump: u8,
+    // How seller proceeds are split (empty to pay the owner)
+    pub payout_splits: Vec<PayoutSplit>,
+    // Layout version, see LISTING_VERSION
+    pub version: u8,
+    // Timestamp from which the listing can no longer be bought and can be expired (0 for never)
+    pub expires_at: i64,
+    // Only buyer allowed to fulfill the listing (none for a public listing)
+    pub reserved_buyer: Option<Pubkey>,
+    // Price schedule when the listing is a Dutch auction starting at `price` (none for a fixed price)
+    pub dutch_auction: Option<DutchAuction>,
+    // Bidding state when the listing is an English auction with `price` as reserve (none otherwise)
+    pub english_auction: Option<EnglishAuction>,
+    // Exchange escrow nonce the listing was created with, telling apart listings at this address
+    pub nonce: u64,
+    // Space kept free for fields added by later versions
+    pub reserved: [u8; 64],
 }
 
 #[account]
 pub struct VoucherBid {
+    // Exchange the bid belongs to
+    pub exchange: Pubkey,
     // The user who placed the bid
     pub bidder: Pubkey,
     // The NFT mint that is being bid on
@@ -50,6 +202,14 @@ pub struct VoucherBid {
     pub bump: u8,
     // Bump for escrow PDA derivation
     pub escrow_bump: u8,
+    // Maker fee escrowed on top of the price, the unused part is refunded on acceptance
+    pub fee_deposit: u64,
+    // Layout version, see BID_VERSION
+    pub version: u8,
+    // Exchange escrow nonce the bid was opened with, telling apart bids at this address
+    pub nonce: u64,
+    // Space kept free for fields added by later versions
+    pub reserved: [u8; 32],
 }
 
 #[account]
@@ -62,29 +222,377 @@ pub struct VoucherState {
     pub latest_sale_timestamp: i64,
     // Bump for PDA derivation
     pub bump: u8,
+    // Layout version, see VOUCHER_STATE_VERSION
+    pub version: u8,
+    // Space kept free for fields added by later versions
+    pub reserved: [u8; 32],
+}
+
+#[account]
+pub struct CollectionConfig {
+    // Exchange this override belongs to
+    pub exchange: Pubkey,
+    // Verified collection mint the override applies to
+    pub collection_mint: Pubkey,
+    // Maker fee in basis points, replacing the exchange maker fee
+    pub maker_fee_basis_points: u16,
+    // Taker fee in basis points, replacing the exchange taker fee
+    pub taker_fee_basis_points: u16,
+    // Proposed maker fee, applied after the exchange fee update delay
+    pub pending_maker_fee_basis_points: u16,
+    // Proposed taker fee, applied after the exchange fee update delay
+    pub pending_taker_fee_basis_points: u16,
+    // Timestamp from which the pending fee update can be applied (0 if none)
+    pub pending_fee_effective_at: i64,
+    // Royalty policy, replacing the exchange policy
+    pub royalty_policy: RoyaltyPolicy,
+    // Maximum royalty in basis points under the capped policy
+    pub royalty_cap_basis_points: u16,
+    // Minimum listing and bid price
+    pub min_price: u64,
+    // Maximum listing and bid price (0 for no maximum)
+    pub max_price: u64,
+    // Bump for PDA derivation
+    pub bump: u8,
+}
+
+#[account]
+pub struct FeeVault {
+    // Exchange this vault collects fees for
+    pub exchange: Pubkey,
+    // Payment mint of the fees held by the vault
+    pub payment_mint: Pubkey,
+    // Token account holding the collected fees, owned by this PDA
+    pub vault: Pubkey,
+    // Total fees ever collected
+    pub lifetime_fees: u64,
+    // Fees collected but not yet withdrawn
+    pub unclaimed_fees: u64,
+    // Total amount withdrawn to the treasury
+    pub total_withdrawn: u64,
+    // Bump for PDA derivation
+    pub bump: u8,
+}
+
+#[account]
+pub struct Referrer {
+    // Exchange the referrer is approved on
+    pub exchange: Pubkey,
+    // Wallet that receives the referral share
+    pub wallet: Pubkey,
+    // Share of the marketplace fee paid to the referrer, in basis points
+    pub fee_share_basis_points: u16,
+    // Whether referral payouts are paused
+    pub paused: bool,
+    // Number of sales referred
+    pub total_referrals: u64,
+    // Bump for PDA derivation
+    pub bump: u8,
+}
+
+#[derive(AnchorSerialize, AnchorDeserialize, Clone, Copy, PartialEq, Eq, Debug)]
+pub struct FeeDiscountTier {
+    // Minimum OPT balance (in base units) to qualify for the tier
+    pub min_balance: u64,
+    // Basis points taken off the marketplace fee rate
+    pub discount_basis_points: u16,
+}
+
+#[account]
+pub struct FeeDiscountConfig {
+    // Exchange the tiers apply to
+    pub exchange: Pubkey,
+    // Tiers sorted by ascending min_balance
+    pub tiers: Vec<FeeDiscountTier>,
+    // Bump for PDA derivation
+    pub bump: u8,
+}
+
+#[account]
+pub struct ApprovedCollection {
+    // Exchange the collection is approved on
+    pub exchange: Pubkey,
+    // Verified collection mint whose vouchers can be listed and bid on
+    pub collection_mint: Pubkey,
+    // Bump for PDA derivation
+    pub bump: u8,
+}
+
+#[account]
+pub struct BlockedWallet {
+    // Exchange the wallet is blocked on
+    pub exchange: Pubkey,
+    // Wallet that can no longer list, bid or buy
+    pub wallet: Pubkey,
+    // Timestamp the wallet was blocked at
+    pub blocked_at: i64,
+    // Bump for PDA derivation
+    pub bump: u8,
+}
+
+#[account]
+pub struct PaymentMintConfig {
+    // Exchange the mint is accepted on
+    pub exchange: Pubkey,
+    // Accepted payment mint
+    pub mint: Pubkey,
+    // Decimals the mint was registered with
+    pub decimals: u8,
+    // Minimum listing and bid price in this mint
+    pub min_price: u64,
+    // Bump for PDA derivation
+    pub bump: u8,
+}
+
+#[account]
+pub struct RecoveryAddress {
+    // Exchange the recovery address applies to
+    pub exchange: Pubkey,
+    // Wallet that designated the recovery address
+    pub wallet: Pubkey,
+    // Wallet receiving rescued listings and bid funds of `wallet`
+    pub recovery: Pubkey,
+    // Bump for PDA derivation
+    pub bump: u8,
+}
+
+#[account]
+pub struct RescueRequest {
+    // Exchange the rescue is announced on
+    pub exchange: Pubkey,
+    // Listing or bid whose escrowed assets are rescued
+    pub target: Pubkey,
+    // Wallet receiving the assets: the owner or bidder, or their recovery address
+    pub destination: Pubkey,
+    // Nonce of the listing or bid the rescue was announced for
+    pub target_nonce: u64,
+    // Timestamp the rescue was announced at
+    pub announced_at: i64,
+    // Timestamp from which the rescue can be executed
+    pub executable_at: i64,
+    // Bump for PDA derivation
+    pub bump: u8,
+}
+
+#[account]
+pub struct ClosedNamespace {
+    // Namespace of the closed exchange, which can no longer be initialized
+    pub namespace: String,
+    // Exchange that was closed
+    pub exchange: Pubkey,
+    // Timestamp the exchange was closed at
+    pub closed_at: i64,
+    // Bump for PDA derivation
+    pub bump: u8,
 }
 
 impl VoucherExchange {
     pub const SIZE: usize = 8 +      // discriminator
+        4 + MAX_NAMESPACE_LEN +      // namespace
         32 +                         // authority
+        32 +                         // pending_authority
+        32 +                         // fee_admin
+        32 +                         // refund_operator
+        32 +                         // pauser
+        32 +                         // collection_curator
         8 +                          // total_listings
         8 +                          // total_bids
-        1;                           // bump
+        2 +                          // maker_fee_basis_points
+        2 +                          // taker_fee_basis_points
+        32 +                         // fee_recipient
+        8 +                          // fee_update_delay
+        2 +                          // pending_maker_fee_basis_points
+        2 +                          // pending_taker_fee_basis_points
+        32 +                         // pending_fee_recipient
+        8 +                          // pending_fee_effective_at
+        1 +                          // royalty_policy
+        2 +                          // royalty_cap_basis_points
+        2 +                          // fee_burn_basis_points
+        8 +                          // total_burned
+        1 +                          // paused_operations
+        1 +                          // bump
+        1 +                          // version
+        1 +                          // winding_down
+        2 +                          // fee_vault_count
+        8 +                          // escrow_nonce
+        64;                          // reserved
+
+    // Numbers a new listing or bid, so rescues announced for an earlier one at the same
+    // address cannot be executed against it
+    pub fn next_escrow_nonce(&mut self) -> Result<u64> {
+        self.escrow_nonce = self.escrow_nonce
+            .checked_add(1)
+            .ok_or(VoucherExchangeError::MathOverflow)?;
+        Ok(self.escrow_nonce)
+    }
+
+    pub fn is_paused(&self, operation: u8) -> bool {
+        self.paused_operations & operation != 0
+    }
+
+    pub fn has_pending_fee_update(&self) -> bool {
+        self.pending_fee_effective_at != 0
+    }
+
+    pub fn role_holder(&self, role: ExchangeRole) -> Pubkey {
+        match role {
+            ExchangeRole::FeeAdmin => self.fee_admin,
+            ExchangeRole::RefundOperator => self.refund_operator,
+            ExchangeRole::Pauser => self.pauser,
+            ExchangeRole::CollectionCurator => self.collection_curator,
+        }
+    }
+
+    pub fn set_role_holder(&mut self, role: ExchangeRole, holder: Pubkey) {
+        match role {
+            ExchangeRole::FeeAdmin => self.fee_admin = holder,
+            ExchangeRole::RefundOperator => self.refund_operator = holder,
+            ExchangeRole::Pauser => self.pauser = holder,
+            ExchangeRole::CollectionCurator => self.collection_curator = holder,
+        }
+    }
+
+    // The authority holds every role implicitly
+    pub fn has_role(&self, role: ExchangeRole, key: &Pubkey) -> bool {
+        *key == self.authority || (*key != Pubkey::default() && *key == self.role_holder(role))
+    }
+
+    pub fn clear_pending_fee_update(&mut self) {
+        self.pending_maker_fee_basis_points = 0;
+        self.pending_taker_fee_basis_points = 0;
+        self.pending_fee_recipient = Pubkey::default();
+        self.pending_fee_effective_at = 0;
+    }
 }
 
 impl VoucherListing {
     pub const SIZE: usize = 8 +      // discriminator
+        32 +                         // exchange
         32 +                         // owner
         32 +                         // nft_mint
         32 +                         // nft_account
         8 +                          // price
         32 +                         // payment_mint
         1 +                          // active
-        1;                           // bump
+        1 +                          // bump
+        4 + MAX_PAYOUT_SPLITS * (32 + 2) + // payout_splits
+        1 +                          // version
+        8 +                          // expires_at
+        1 + 32 +                     // reserved_buyer
+        1 + DutchAuction::SIZE +     // dutch_auction
+        1 + EnglishAuction::SIZE +   // english_auction
+        8 +                          // nonce
+        64;                          // reserved
+
+    pub fn is_expired(&self, now: i64) -> bool {
+        self.expires_at != 0 && now >= self.expires_at
+    }
+
+    pub fn is_buyer_allowed(&self, buyer: &Pubkey) -> bool {
+        self.reserved_buyer.map_or(true, |reserved_buyer| reserved_buyer == *buyer)
+    }
+
+    // Price the listing can be bought at, following the Dutch auction schedule if any
+    pub fn current_price(&self, now: i64) -> Result<u64> {
+        match &self.dutch_auction {
+            Some(auction) => auction.price_at(self.price, now),
+            None => Ok(self.price),
+        }
+    }
+
+    pub fn has_auction_bids(&self) -> bool {
+        self.english_auction.is_some_and(|auction| auction.has_bids())
+    }
+
+    // Top bidder of the auction (default pubkey when there is none)
+    pub fn auction_top_bidder(&self) -> Pubkey {
+        self.english_auction.map_or(Pubkey::default(), |auction| auction.top_bidder)
+    }
+}
+
+impl DutchAuction {
+    pub const SIZE: usize = 8 +      // end_price
+        8 +                          // start_time
+        8 +                          // end_time
+        1 + 8;                       // decay
+
+    pub fn price_at(&self, start_price: u64, now: i64) -> Result<u64> {
+        if now <= self.start_time {
+            return Ok(start_price);
+        }
+        if now >= self.end_time {
+            return Ok(self.end_price);
+        }
+
+        let mut elapsed = now
+            .checked_sub(self.start_time)
+            .ok_or(VoucherExchangeError::MathOverflow)?;
+        if let PriceDecay::Stepped { interval } = self.decay {
+            let into_step = elapsed
+                .checked_rem(interval)
+                .ok_or(VoucherExchangeError::MathOverflow)?;
+            elapsed = elapsed
+                .checked_sub(into_step)
+                .ok_or(VoucherExchangeError::MathOverflow)?;
+        }
+        let duration = self.end_time
+            .checked_sub(self.start_time)
+            .ok_or(VoucherExchangeError::MathOverflow)?;
+        let price_range = start_price
+            .checked_sub(self.end_price)
+            .ok_or(VoucherExchangeError::MathOverflow)?;
+
+        let drop = (price_range as u128)
+            .checked_mul(u128::try_from(elapsed).map_err(|_| VoucherExchangeError::MathOverflow)?)
+            .ok_or(VoucherExchangeError::MathOverflow)?
+            .checked_div(u128::try_from(duration).map_err(|_| VoucherExchangeError::MathOverflow)?)
+            .ok_or(VoucherExchangeError::MathOverflow)?;
+        let drop = u64::try_from(drop).map_err(|_| VoucherExchangeError::MathOverflow)?;
+
+        Ok(start_price
+            .checked_sub(drop)
+            .ok_or(VoucherExchangeError::MathOverflow)?)
+    }
+}
+
+impl EnglishAuction {
+    pub const SIZE: usize = 8 +      // min_increment
+        8 +                          // end_time
+        32 +                         // top_bidder
+        8 +                          // top_bid
+        8;                           // fee_deposit
+
+    pub fn has_bids(&self) -> bool {
+        self.top_bid > 0
+    }
+
+    // Lowest amount the next bid can be: the reserve, then the top bid plus the increment
+    pub fn min_bid(&self, reserve_price: u64) -> Result<u64> {
+        if !self.has_bids() {
+            return Ok(reserve_price);
+        }
+        Ok(self.top_bid
+            .checked_add(self.min_increment)
+            .ok_or(VoucherExchangeError::MathOverflow)?)
+    }
+
+    // Top bid together with its fee deposit, as held in the auction escrow
+    pub fn escrowed_amount(&self) -> Result<u64> {
+        Ok(self.top_bid
+            .checked_add(self.fee_deposit)
+            .ok_or(VoucherExchangeError::MathOverflow)?)
+    }
+
+    pub fn clear_top_bid(&mut self) {
+        self.top_bidder = Pubkey::default();
+        self.top_bid = 0;
+        self.fee_deposit = 0;
+    }
 }
 
 impl VoucherBid {
     pub const SIZE: usize = 8 +      // discriminator
+        32 +                         // exchange
         32 +                         // bidder
         32 +                         // nft_mint
         8 +                          // price
@@ -93,7 +601,18 @@ impl VoucherBid {
         1 +                          // active
         1 +                          // requires_refund
         1 +                          // bump
-        1;                           // escrow_bump
+        1 +                          // escrow_bump
+        8 +                          // fee_deposit
+        1 +                          // version
+        8 +                          // nonce
+        32;                          // reserved
+
+    // Total held in the bid escrow: the price and the maker fee deposit
+    pub fn escrowed_amount(&self) -> Result<u64> {
+        self.price
+            .checked_add(self.fee_deposit)
+            .ok_or(error!(VoucherExchangeError::MathOverflow))
+    }
 }
 
 impl VoucherState {
@@ -101,5 +620,135 @@ impl VoucherState {
         32 +                         // nft_mint
         1 +                          // sold
         8 +                          // latest_sale_timestamp
+        1 +                          // bump
+        1 +                          // version
+        32;                          // reserved
+}
+
+impl CollectionConfig {
+    pub const SIZE: usize = 8 +      // discriminator
+        32 +                         // exchange
+        32 +                         // collection_mint
+        2 +                          // maker_fee_basis_points
+        2 +                          // taker_fee_basis_points
+        2 +                          // pending_maker_fee_basis_points
+        2 +                          // pending_taker_fee_basis_points
+        8 +                          // pending_fee_effective_at
+        1 +                          // royalty_policy
+        2 +                          // royalty_cap_basis_points
+        8 +                          // min_price
+        8 +                          // max_price
+        1;                           // bump
+
+    pub fn is_price_allowed(&self, price: u64) -> bool {
+        price >= self.min_price && (self.max_price == 0 || price <= self.max_price)
+    }
+
+    pub fn has_pending_fee_update(&self) -> bool {
+        self.pending_fee_effective_at != 0
+    }
+
+    pub fn clear_pending_fee_update(&mut self) {
+        self.pending_maker_fee_basis_points = 0;
+        self.pending_taker_fee_basis_points = 0;
+        self.pending_fee_effective_at = 0;
+    }
+}
+
+impl FeeVault {
+    pub const SIZE: usize = 8 +      // discriminator
+        32 +                         // exchange
+        32 +                         // payment_mint
+        32 +                         // vault
+        8 +                          // lifetime_fees
+        8 +                          // unclaimed_fees
+        8 +                          // total_withdrawn
+        1;                           // bump
+
+    pub fn record_fee(&mut self, fee: u64) -> Result<()> {
+        self.lifetime_fees = self.lifetime_fees
+            .checked_add(fee)
+            .ok_or(VoucherExchangeError::MathOverflow)?;
+        self.unclaimed_fees = self.unclaimed_fees
+            .checked_add(fee)
+            .ok_or(VoucherExchangeError::MathOverflow)?;
+        Ok(())
+    }
+}
+
+impl Referrer {
+    pub const SIZE: usize = 8 +      // discriminator
+        32 +                         // exchange
+        32 +                         // wallet
+        2 +                          // fee_share_basis_points
+        1 +                          // paused
+        8 +                          // total_referrals
+        1;                           // bump
+}
+
+impl FeeDiscountConfig {
+    pub const SIZE: usize = 8 +      // discriminator
+        32 +                         // exchange
+        4 + MAX_FEE_DISCOUNT_TIERS * (8 + 2) + // tiers
+        1;                           // bump
+
+    // Discount of the highest tier the balance qualifies for
+    pub fn discount_basis_points(&self, balance: u64) -> u16 {
+        self.tiers
+            .iter()
+            .rev()
+            .find(|tier| balance >= tier.min_balance)
+            .map_or(0, |tier| tier.discount_basis_points)
+    }
+}
+
+impl PaymentMintConfig {
+    pub const SIZE: usize = 8 +      // discriminator
+        32 +                         // exchange
+        32 +                         // mint
+        1 +                          // decimals
+        8 +                          // min_price
+        1;                           // bump
+}
+
+impl ApprovedCollection {
+    pub const SIZE: usize = 8 +      // discriminator
+        32 +                         // exchange
+        32 +                         // collection_mint
+        1;                           // bump
+}
+
+impl BlockedWallet {
+    pub const SIZE: usize = 8 +      // discriminator
+        32 +                         // exchange
+        32 +                         // wallet
+        8 +                          // blocked_at
+        1;                           // bump
+}
+
+impl RecoveryAddress {
+    pub const SIZE: usize = 8 +      // discriminator
+        32 +                         // exchange
+        32 +                         // wallet
+        32 +                         // recovery
+        1;                           // bump
+}
+
+impl RescueRequest {
+    pub const SIZE: usize = 8 +      // discriminator
+        32 +                         // exchange
+        32 +                         // target
+        32 +                         // destination
+        8 +                          // target_nonce
+        8 +                          // announced_at
+        8 +                          // executable_at
+        1;                           // bump
+}
+
+impl ClosedNamespace {
+    pub const SIZE: usize = 8 +      // discriminator
+        4 + MAX_NAMESPACE_LEN +      // namespace
+        32 +                         // exchange
+        8 +                          // closed_at
         1;                           // bump
 }
@@ -0,0 +1,70 @@
+import { Connection, Keypair, PublicKey } from "@solana/web3.js";
+import { createUmi } from "@metaplex-foundation/umi-bundle-defaults";
+import {
+  createNft,
+  findMetadataPda,
+  mplTokenMetadata,
+  verifyCollectionV1,
+} from "@metaplex-foundation/mpl-token-metadata";
+import {
+  generateSigner,
+  keypairIdentity,
+  percentAmount,
+  Umi,
+} from "@metaplex-foundation/umi";
+import {
+  fromWeb3JsKeypair,
+  fromWeb3JsPublicKey,
+  toWeb3JsPublicKey,
+} from "@metaplex-foundation/umi-web3js-adapters";
+
+// Metaplex collection NFT the test vouchers are minted into. Every voucher gets
+// metadata with this collection verified, which listing and bidding require.
+export class VoucherCollection {
+  private constructor(private readonly umi: Umi, readonly mint: PublicKey) {}
+
+  // Creates the collection NFT, `authority` paying for it and signing verifications
+  static async create(
+    connection: Connection,
+    authority: Keypair
+  ): Promise<VoucherCollection> {
+    const umi = createUmi(connection.rpcEndpoint)
+      .use(mplTokenMetadata())
+      .use(keypairIdentity(fromWeb3JsKeypair(authority)));
+
+    const collectionMint = generateSigner(umi);
+    await createNft(umi, {
+      mint: collectionMint,
+      name: "Voucher Collection",
+      uri: "",
+      sellerFeeBasisPoints: percentAmount(0),
+      isCollection: true,
+    }).sendAndConfirm(umi);
+
+    return new VoucherCollection(
+      umi,
+      toWeb3JsPublicKey(collectionMint.publicKey)
+    );
+  }
+
+  // Mints a voucher NFT of the collection into the associated token account of
+  // `owner` and verifies it. Vouchers carry no royalties.
+  async mintVoucher(owner: PublicKey): Promise<PublicKey> {
+    const mint = generateSigner(this.umi);
+    await createNft(this.umi, {
+      mint,
+      tokenOwner: fromWeb3JsPublicKey(owner),
+      name: "Voucher",
+      uri: "",
+      sellerFeeBasisPoints: percentAmount(0),
+      collection: { key: fromWeb3JsPublicKey(this.mint), verified: false },
+    }).sendAndConfirm(this.umi);
+
+    await verifyCollectionV1(this.umi, {
+      metadata: findMetadataPda(this.umi, { mint: mint.publicKey }),
+      collectionMint: fromWeb3JsPublicKey(this.mint),
+    }).sendAndConfirm(this.umi);
+
+    return toWeb3JsPublicKey(mint.publicKey);
+  }
+}
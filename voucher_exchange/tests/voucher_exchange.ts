@@ -13,12 +13,30 @@ import {
   ASSOCIATED_TOKEN_PROGRAM_ID,
   createMint,
   createAssociatedTokenAccount,
+  getAssociatedTokenAddressSync,
   mintTo,
   getAccount,
   getMint,
 } from "@solana/spl-token";
 import { BN } from "bn.js";
 import { assert } from "chai";
+import { VoucherCollection } from "./fixtures/voucher_collection";
+
+const TOKEN_METADATA_PROGRAM_ID = new PublicKey(
+  "metaqbxxUerdq28cj1RbAWkYQm3ybzjb6a8bt518x1s"
+);
+
+// Metaplex metadata PDA of a mint; test vouchers carry no royalties
+function findMetadataPDA(mint: PublicKey): PublicKey {
+  return PublicKey.findProgramAddressSync(
+    [
+      Buffer.from("metadata"),
+      TOKEN_METADATA_PROGRAM_ID.toBuffer(),
+      mint.toBuffer(),
+    ],
+    TOKEN_METADATA_PROGRAM_ID
+  )[0];
+}
 
 describe("voucher_exchange", () => {
   // Configure the client to use the local cluster
@@ -36,6 +54,47 @@ describe("voucher_exchange", () => {
   // Define PDAs
   let exchangePDA: PublicKey;
   let exchangeBump: number;
+  let closedNamespacePDA: PublicKey;
+  let feeVaultPDA: PublicKey;
+  let feeVaultTokenAccount: PublicKey;
+  let paymentMintConfigPDA: PublicKey;
+
+  // Verified collection the test vouchers belong to
+  let voucherCollection: VoucherCollection;
+  let voucherCollectionMint: PublicKey;
+  let approvedCollectionPDA: PublicKey;
+  let voucherCollectionConfigPDA: PublicKey;
+
+  // BlockedWallet PDA of a wallet, which must not exist for it to trade
+  const findBlockedWalletPDA = (wallet: PublicKey) =>
+    PublicKey.findProgramAddressSync(
+      [
+        Buffer.from("blocked_wallet"),
+        exchangePDA.toBuffer(),
+        wallet.toBuffer(),
+      ],
+      program.programId
+    )[0];
+
+  const findRecoveryAddressPDA = (wallet: PublicKey) =>
+    PublicKey.findProgramAddressSync(
+      [
+        Buffer.from("recovery_address"),
+        exchangePDA.toBuffer(),
+        wallet.toBuffer(),
+      ],
+      program.programId
+    )[0];
+
+  const findRescueRequestPDA = (target: PublicKey) =>
+    PublicKey.findProgramAddressSync(
+      [
+        Buffer.from("rescue_request"),
+        exchangePDA.toBuffer(),
+        target.toBuffer(),
+      ],
+      program.programId
+    )[0];
 
   // Token accounts
   let nftMint: PublicKey;
@@ -52,6 +111,12 @@ describe("voucher_exchange", () => {
   let nftTokenProgramId: PublicKey;
   let paymentTokenProgramId: PublicKey;
 
+  // Exchange parameters
+  const exchangeNamespace = "omnipoints"; // Brand the exchange is namespaced by
+  const makerFeeBasisPoints = 100; // 1%
+  const takerFeeBasisPoints = 250; // 2.5%
+  const feeUpdateDelay = new BN(24 * 60 * 60); // Minimum delay, fee updates cannot apply during tests
+
   // Listing and bid parameters
   const listingPrice = new BN(500_000_000);
   const bidPrice = new BN(400_000_000);
@@ -76,14 +141,15 @@ describe("voucher_exchange", () => {
     // Wait for confirmations
     await new Promise((resolve) => setTimeout(resolve, 2000));
 
-    // Create NFT and payment tokens
-    nftMint = await createMint(
+    // Create the voucher collection and mint the NFT into it
+    voucherCollection = await VoucherCollection.create(
       provider.connection,
-      nftOwner,
-      nftOwner.publicKey,
-      null,
-      0 // 0 decimals for NFT
+      (provider.wallet as anchor.Wallet).payer
     );
+    voucherCollectionMint = voucherCollection.mint;
+    nftMint = await voucherCollection.mintVoucher(nftOwner.publicKey);
+
+    // Create payment token
 
     paymentMint = await createMint(
       provider.connection,
@@ -98,12 +164,7 @@ describe("voucher_exchange", () => {
     paymentTokenProgramId = TOKEN_PROGRAM_ID; // Using standard token for this test
 
     // Create token accounts
-    nftOwnerAccount = await createAssociatedTokenAccount(
-      provider.connection,
-      nftOwner,
-      nftMint,
-      nftOwner.publicKey
-    );
+    nftOwnerAccount = getAssociatedTokenAddressSync(nftMint, nftOwner.publicKey);
 
     buyerNftAccount = await createAssociatedTokenAccount(
       provider.connection,
@@ -147,16 +208,6 @@ describe("voucher_exchange", () => {
       bidder.publicKey
     );
 
-    // Mint NFT to owner
-    await mintTo(
-      provider.connection,
-      nftOwner,
-      nftMint,
-      nftOwnerAccount,
-      nftOwner.publicKey,
-      1 // NFT amount
-    );
-
     // Mint payment tokens to buyer and bidder
     await mintTo(
       provider.connection,
@@ -176,89 +227,1025 @@ describe("voucher_exchange", () => {
       1000000000
     );
 
-    // Derive PDAs
-    [exchangePDA, exchangeBump] = await PublicKey.findProgramAddress(
-      [Buffer.from("voucher_exchange")],
-      program.programId
+    // Derive PDAs
+    [exchangePDA, exchangeBump] = await PublicKey.findProgramAddress(
+      [Buffer.from("voucher_exchange"), Buffer.from(exchangeNamespace)],
+      program.programId
+    );
+
+    [closedNamespacePDA] = PublicKey.findProgramAddressSync(
+      [Buffer.from("closed_namespace"), Buffer.from(exchangeNamespace)],
+      program.programId
+    );
+
+    [paymentMintConfigPDA] = PublicKey.findProgramAddressSync(
+      [
+        Buffer.from("payment_mint_config"),
+        exchangePDA.toBuffer(),
+        paymentMint.toBuffer(),
+      ],
+      program.programId
+    );
+
+    [approvedCollectionPDA] = PublicKey.findProgramAddressSync(
+      [
+        Buffer.from("approved_collection"),
+        exchangePDA.toBuffer(),
+        voucherCollectionMint.toBuffer(),
+      ],
+      program.programId
+    );
+
+    // No override is set for the voucher collection, but verified NFTs must pass its PDA
+    [voucherCollectionConfigPDA] = PublicKey.findProgramAddressSync(
+      [
+        Buffer.from("collection_config"),
+        exchangePDA.toBuffer(),
+        voucherCollectionMint.toBuffer(),
+      ],
+      program.programId
+    );
+
+    [feeVaultPDA] = PublicKey.findProgramAddressSync(
+      [
+        Buffer.from("fee_vault"),
+        exchangePDA.toBuffer(),
+        paymentMint.toBuffer(),
+      ],
+      program.programId
+    );
+
+    feeVaultTokenAccount = getAssociatedTokenAddressSync(
+      paymentMint,
+      feeVaultPDA,
+      true,
+      paymentTokenProgramId
+    );
+
+    // Updated PDA derivation
+    [listingPDA, listingBump] = await PublicKey.findProgramAddress(
+      [
+        Buffer.from("voucher_listing"),
+        exchangePDA.toBuffer(),
+        nftOwner.publicKey.toBuffer(),
+        nftMint.toBuffer(),
+      ],
+      program.programId
+    );
+
+    [bidPDA, bidBump] = await PublicKey.findProgramAddress(
+      [
+        Buffer.from("voucher_bid"),
+        exchangePDA.toBuffer(),
+        bidder.publicKey.toBuffer(),
+        nftMint.toBuffer(),
+      ],
+      program.programId
+    );
+
+    [escrowNftPDA, escrowNftBump] = await PublicKey.findProgramAddress(
+      [Buffer.from("escrow"), exchangePDA.toBuffer(), nftMint.toBuffer()],
+      program.programId
+    );
+
+    [escrowBidPDA, escrowBidBump] = await PublicKey.findProgramAddress(
+      [Buffer.from("escrow"), exchangePDA.toBuffer(), bidder.publicKey.toBuffer(), nftMint.toBuffer()],
+      program.programId
+    );
+
+    [nftStatePDA, nftStateBump] = await PublicKey.findProgramAddress(
+      [Buffer.from("voucher_state"), nftMint.toBuffer()],
+      program.programId
+    );
+  });
+
+  it("Initialize Exchange", async () => {
+    // Initialize the voucher exchange
+    const tx = await program.methods
+      .initializeExchange(
+        exchangeNamespace,
+        makerFeeBasisPoints,
+        takerFeeBasisPoints,
+        admin.publicKey,
+        feeUpdateDelay
+      )
+      .accounts({
+        exchange: exchangePDA,
+        closedNamespace: closedNamespacePDA,
+        authority: admin.publicKey,
+        tokenProgram: paymentTokenProgramId,
+        systemProgram: SystemProgram.programId,
+        rent: SYSVAR_RENT_PUBKEY,
+      })
+      .signers([admin])
+      .rpc();
+
+    console.log("Initialize transaction:", tx);
+
+    // Verify exchange was initialized correctly
+    const exchangeAccount = await program.account.voucherExchange.fetch(
+      exchangePDA
+    );
+    assert.equal(
+      exchangeAccount.authority.toString(),
+      admin.publicKey.toString()
+    );
+    assert.equal(exchangeAccount.namespace, exchangeNamespace);
+    assert.equal(exchangeAccount.totalListings.toNumber(), 0);
+    assert.equal(exchangeAccount.totalBids.toNumber(), 0);
+    assert.equal(exchangeAccount.makerFeeBasisPoints, makerFeeBasisPoints);
+    assert.equal(exchangeAccount.takerFeeBasisPoints, takerFeeBasisPoints);
+    assert.equal(
+      exchangeAccount.feeRecipient.toString(),
+      admin.publicKey.toString()
+    );
+    assert.equal(exchangeAccount.pendingFeeEffectiveAt.toNumber(), 0);
+    assert.equal(exchangeAccount.version, 1);
+    assert.equal(exchangeAccount.feeVaultCount, 0);
+  });
+
+  it("Initialize a Second Branded Exchange", async () => {
+    // Another brand runs its own market with its own authority and fees
+    const brandNamespace = "vietjet";
+    const [brandExchangePDA] = PublicKey.findProgramAddressSync(
+      [Buffer.from("voucher_exchange"), Buffer.from(brandNamespace)],
+      program.programId
+    );
+    const [brandClosedNamespacePDA] = PublicKey.findProgramAddressSync(
+      [Buffer.from("closed_namespace"), Buffer.from(brandNamespace)],
+      program.programId
+    );
+
+    await program.methods
+      .initializeExchange(brandNamespace, 0, 500, buyer.publicKey, feeUpdateDelay)
+      .accounts({
+        exchange: brandExchangePDA,
+        closedNamespace: brandClosedNamespacePDA,
+        authority: buyer.publicKey,
+        tokenProgram: paymentTokenProgramId,
+        systemProgram: SystemProgram.programId,
+        rent: SYSVAR_RENT_PUBKEY,
+      })
+      .signers([buyer])
+      .rpc();
+
+    const brandExchange = await program.account.voucherExchange.fetch(
+      brandExchangePDA
+    );
+    assert.equal(brandExchange.namespace, brandNamespace);
+    assert.equal(brandExchange.authority.toString(), buyer.publicKey.toString());
+    assert.equal(brandExchange.takerFeeBasisPoints, 500);
+
+    // The original exchange is untouched
+    const exchangeAccount = await program.account.voucherExchange.fetch(
+      exchangePDA
+    );
+    assert.equal(exchangeAccount.authority.toString(), admin.publicKey.toString());
+    assert.equal(exchangeAccount.takerFeeBasisPoints, takerFeeBasisPoints);
+  });
+
+  it("Propose and Cancel Fee Updates Behind the Delay", async () => {
+    // Propose a fee hike and then withdraw it
+    await program.methods
+      .proposeFeeUpdate(200, 500, admin.publicKey)
+      .accounts({
+        authority: admin.publicKey,
+        exchange: exchangePDA,
+      })
+      .signers([admin])
+      .rpc();
+
+    let exchangeAccount = await program.account.voucherExchange.fetch(
+      exchangePDA
+    );
+    assert.equal(exchangeAccount.takerFeeBasisPoints, takerFeeBasisPoints);
+    assert.equal(exchangeAccount.pendingMakerFeeBasisPoints, 200);
+    assert.equal(exchangeAccount.pendingTakerFeeBasisPoints, 500);
+    assert.notEqual(exchangeAccount.pendingFeeEffectiveAt.toNumber(), 0);
+
+    await program.methods
+      .cancelFeeUpdate()
+      .accounts({
+        authority: admin.publicKey,
+        exchange: exchangePDA,
+      })
+      .signers([admin])
+      .rpc();
+
+    exchangeAccount = await program.account.voucherExchange.fetch(exchangePDA);
+    assert.equal(exchangeAccount.pendingFeeEffectiveAt.toNumber(), 0);
+
+    // A new proposal cannot be applied before its delay has passed
+    await program.methods
+      .proposeFeeUpdate(200, 500, admin.publicKey)
+      .accounts({
+        authority: admin.publicKey,
+        exchange: exchangePDA,
+      })
+      .signers([admin])
+      .rpc();
+
+    try {
+      await program.methods
+        .applyFeeUpdate()
+        .accounts({
+          exchange: exchangePDA,
+        })
+        .rpc();
+      assert.fail("Fee update should wait for its delay");
+    } catch (e) {
+      assert.include(e.toString(), "FeeUpdateNotReady");
+    }
+
+    await program.methods
+      .cancelFeeUpdate()
+      .accounts({
+        authority: admin.publicKey,
+        exchange: exchangePDA,
+      })
+      .signers([admin])
+      .rpc();
+
+    exchangeAccount = await program.account.voucherExchange.fetch(exchangePDA);
+    assert.equal(exchangeAccount.makerFeeBasisPoints, makerFeeBasisPoints);
+    assert.equal(exchangeAccount.takerFeeBasisPoints, takerFeeBasisPoints);
+    assert.equal(exchangeAccount.pendingFeeEffectiveAt.toNumber(), 0);
+  });
+
+  it("Set Royalty Policy", async () => {
+    await program.methods
+      .setRoyaltyPolicy({ capped: {} }, 500)
+      .accounts({
+        authority: admin.publicKey,
+        exchange: exchangePDA,
+      })
+      .signers([admin])
+      .rpc();
+
+    const exchangeAccount = await program.account.voucherExchange.fetch(
+      exchangePDA
+    );
+    assert.deepEqual(exchangeAccount.royaltyPolicy, { capped: {} });
+    assert.equal(exchangeAccount.royaltyCapBasisPoints, 500);
+  });
+
+  it("Set and Remove Collection Config", async () => {
+    const collectionMint = Keypair.generate().publicKey;
+    const [collectionConfigPDA] = PublicKey.findProgramAddressSync(
+      [
+        Buffer.from("collection_config"),
+        exchangePDA.toBuffer(),
+        collectionMint.toBuffer(),
+      ],
+      program.programId
+    );
+
+    await program.methods
+      .setCollectionConfig(collectionMint, {
+        royaltyPolicy: { enforced: {} },
+        royaltyCapBasisPoints: 0,
+        minPrice: new BN(1_000_000),
+        maxPrice: new BN(0),
+      })
+      .accounts({
+        collectionConfig: collectionConfigPDA,
+        authority: admin.publicKey,
+        exchange: exchangePDA,
+        systemProgram: SystemProgram.programId,
+      })
+      .signers([admin])
+      .rpc();
+
+    const config = await program.account.collectionConfig.fetch(
+      collectionConfigPDA
+    );
+    assert.equal(config.collectionMint.toString(), collectionMint.toString());
+    assert.equal(config.minPrice.toNumber(), 1_000_000);
+
+    // A new override keeps charging the exchange fees
+    assert.equal(config.makerFeeBasisPoints, makerFeeBasisPoints);
+    assert.equal(config.takerFeeBasisPoints, takerFeeBasisPoints);
+
+    // Override fee changes wait for the exchange fee update delay
+    const collectionFeeAccounts = {
+      collectionConfig: collectionConfigPDA,
+      authority: admin.publicKey,
+      exchange: exchangePDA,
+    };
+    await program.methods
+      .proposeCollectionFeeUpdate(50, 100)
+      .accounts(collectionFeeAccounts)
+      .signers([admin])
+      .rpc();
+
+    try {
+      await program.methods
+        .applyCollectionFeeUpdate()
+        .accounts({
+          collectionConfig: collectionConfigPDA,
+          exchange: exchangePDA,
+        })
+        .rpc();
+      assert.fail("Collection fee update should wait for its delay");
+    } catch (e) {
+      assert.include(e.toString(), "FeeUpdateNotReady");
+    }
+
+    const pendingConfig = await program.account.collectionConfig.fetch(
+      collectionConfigPDA
+    );
+    assert.equal(pendingConfig.makerFeeBasisPoints, makerFeeBasisPoints);
+    assert.equal(pendingConfig.pendingMakerFeeBasisPoints, 50);
+    assert.equal(pendingConfig.pendingTakerFeeBasisPoints, 100);
+
+    await program.methods
+      .cancelCollectionFeeUpdate()
+      .accounts(collectionFeeAccounts)
+      .signers([admin])
+      .rpc();
+
+    await program.methods
+      .removeCollectionConfig()
+      .accounts({
+        collectionConfig: collectionConfigPDA,
+        authority: admin.publicKey,
+        exchange: exchangePDA,
+      })
+      .signers([admin])
+      .rpc();
+
+    const closed = await provider.connection.getAccountInfo(
+      collectionConfigPDA
+    );
+    assert.isNull(closed);
+  });
+
+  it("Approve and Revoke Collection", async () => {
+    const otherCollectionMint = Keypair.generate().publicKey;
+    const [otherApprovedCollectionPDA] = PublicKey.findProgramAddressSync(
+      [
+        Buffer.from("approved_collection"),
+        exchangePDA.toBuffer(),
+        otherCollectionMint.toBuffer(),
+      ],
+      program.programId
+    );
+
+    for (const [collectionMint, approvedCollection] of [
+      [voucherCollectionMint, approvedCollectionPDA],
+      [otherCollectionMint, otherApprovedCollectionPDA],
+    ]) {
+      await program.methods
+        .approveCollection(collectionMint)
+        .accounts({
+          approvedCollection,
+          authority: admin.publicKey,
+          exchange: exchangePDA,
+          systemProgram: SystemProgram.programId,
+        })
+        .signers([admin])
+        .rpc();
+    }
+
+    const approved = await program.account.approvedCollection.fetch(
+      approvedCollectionPDA
+    );
+    assert.equal(
+      approved.collectionMint.toString(),
+      voucherCollectionMint.toString()
+    );
+
+    await program.methods
+      .revokeCollection()
+      .accounts({
+        approvedCollection: otherApprovedCollectionPDA,
+        authority: admin.publicKey,
+        exchange: exchangePDA,
+      })
+      .signers([admin])
+      .rpc();
+
+    const revoked = await provider.connection.getAccountInfo(
+      otherApprovedCollectionPDA
+    );
+    assert.isNull(revoked);
+  });
+
+  it("Allowlist Payment Mint", async () => {
+    // The decimals passed must match the mint
+    try {
+      await program.methods
+        .setPaymentMintConfig(9, new BN(0))
+        .accounts({
+          paymentMintConfig: paymentMintConfigPDA,
+          paymentMint: paymentMint,
+          authority: admin.publicKey,
+          exchange: exchangePDA,
+          systemProgram: SystemProgram.programId,
+        })
+        .signers([admin])
+        .rpc();
+      assert.fail("Mismatched decimals should be rejected");
+    } catch (e) {
+      assert.include(e.toString(), "InvalidPaymentMintDecimals");
+    }
+
+    await program.methods
+      .setPaymentMintConfig(6, new BN(1_000_000))
+      .accounts({
+        paymentMintConfig: paymentMintConfigPDA,
+        paymentMint: paymentMint,
+        authority: admin.publicKey,
+        exchange: exchangePDA,
+        systemProgram: SystemProgram.programId,
+      })
+      .signers([admin])
+      .rpc();
+
+    const config = await program.account.paymentMintConfig.fetch(
+      paymentMintConfigPDA
+    );
+    assert.equal(config.mint.toString(), paymentMint.toString());
+    assert.equal(config.decimals, 6);
+    assert.equal(config.minPrice.toNumber(), 1_000_000);
+  });
+
+  it("Initialize Fee Vault", async () => {
+    await program.methods
+      .initializeFeeVault()
+      .accounts({
+        feeVault: feeVaultPDA,
+        vaultTokenAccount: feeVaultTokenAccount,
+        authority: admin.publicKey,
+        exchange: exchangePDA,
+        paymentMint: paymentMint,
+        tokenProgram: paymentTokenProgramId,
+        associatedTokenProgram: ASSOCIATED_TOKEN_PROGRAM_ID,
+        systemProgram: SystemProgram.programId,
+      })
+      .signers([admin])
+      .rpc();
+
+    const feeVaultAccount = await program.account.feeVault.fetch(feeVaultPDA);
+    assert.equal(feeVaultAccount.paymentMint.toString(), paymentMint.toString());
+    assert.equal(
+      feeVaultAccount.vault.toString(),
+      feeVaultTokenAccount.toString()
+    );
+    assert.equal(feeVaultAccount.lifetimeFees.toNumber(), 0);
+
+    const exchangeAccount = await program.account.voucherExchange.fetch(
+      exchangePDA
+    );
+    assert.equal(exchangeAccount.feeVaultCount, 1);
+  });
+
+  it("Set, Pause and Remove Referrer", async () => {
+    const partnerWallet = Keypair.generate().publicKey;
+    const [referrerPDA] = PublicKey.findProgramAddressSync(
+      [
+        Buffer.from("referrer"),
+        exchangePDA.toBuffer(),
+        partnerWallet.toBuffer(),
+      ],
+      program.programId
+    );
+
+    // Approve the partner with 20% of the marketplace fee
+    await program.methods
+      .setReferrer(partnerWallet, 2000, false)
+      .accounts({
+        referrer: referrerPDA,
+        authority: admin.publicKey,
+        exchange: exchangePDA,
+        systemProgram: SystemProgram.programId,
+      })
+      .signers([admin])
+      .rpc();
+
+    let referrerAccount = await program.account.referrer.fetch(referrerPDA);
+    assert.equal(referrerAccount.wallet.toString(), partnerWallet.toString());
+    assert.equal(referrerAccount.feeShareBasisPoints, 2000);
+    assert.equal(referrerAccount.paused, false);
+
+    // Pause payouts
+    await program.methods
+      .setReferrer(partnerWallet, 2000, true)
+      .accounts({
+        referrer: referrerPDA,
+        authority: admin.publicKey,
+        exchange: exchangePDA,
+        systemProgram: SystemProgram.programId,
+      })
+      .signers([admin])
+      .rpc();
+
+    referrerAccount = await program.account.referrer.fetch(referrerPDA);
+    assert.equal(referrerAccount.paused, true);
+
+    // Shares above the cap are rejected
+    try {
+      await program.methods
+        .setReferrer(partnerWallet, 6000, false)
+        .accounts({
+          referrer: referrerPDA,
+          authority: admin.publicKey,
+          exchange: exchangePDA,
+          systemProgram: SystemProgram.programId,
+        })
+        .signers([admin])
+        .rpc();
+      assert.fail("Share above the cap should be rejected");
+    } catch (e) {
+      assert.include(e.toString(), "ReferrerShareTooHigh");
+    }
+
+    await program.methods
+      .removeReferrer()
+      .accounts({
+        referrer: referrerPDA,
+        authority: admin.publicKey,
+        exchange: exchangePDA,
+      })
+      .signers([admin])
+      .rpc();
+
+    assert.isNull(await provider.connection.getAccountInfo(referrerPDA));
+  });
+
+  it("Set Fee Discount Tiers", async () => {
+    const [feeDiscountPDA] = PublicKey.findProgramAddressSync(
+      [Buffer.from("fee_discount"), exchangePDA.toBuffer()],
+      program.programId
+    );
+
+    // 0.5% off at 10k OPT, 1% off at 100k OPT (OPT has 6 decimals)
+    const tiers = [
+      { minBalance: new BN(10_000_000_000), discountBasisPoints: 50 },
+      { minBalance: new BN(100_000_000_000), discountBasisPoints: 100 },
+    ];
+
+    await program.methods
+      .setFeeDiscountTiers(tiers)
+      .accounts({
+        feeDiscountConfig: feeDiscountPDA,
+        authority: admin.publicKey,
+        exchange: exchangePDA,
+        systemProgram: SystemProgram.programId,
+      })
+      .signers([admin])
+      .rpc();
+
+    const config = await program.account.feeDiscountConfig.fetch(
+      feeDiscountPDA
+    );
+    assert.equal(config.tiers.length, 2);
+    assert.equal(config.tiers[1].discountBasisPoints, 100);
+
+    // Unsorted tiers are rejected
+    try {
+      await program.methods
+        .setFeeDiscountTiers([tiers[1], tiers[0]])
+        .accounts({
+          feeDiscountConfig: feeDiscountPDA,
+          authority: admin.publicKey,
+          exchange: exchangePDA,
+          systemProgram: SystemProgram.programId,
+        })
+        .signers([admin])
+        .rpc();
+      assert.fail("Unsorted tiers should be rejected");
+    } catch (e) {
+      assert.include(e.toString(), "InvalidFeeDiscountTiers");
+    }
+  });
+
+  it("Set Fee Burn Rate", async () => {
+    // Burn 30% of every OPT-denominated fee
+    await program.methods
+      .setFeeBurnRate(3000)
+      .accounts({
+        authority: admin.publicKey,
+        exchange: exchangePDA,
+      })
+      .signers([admin])
+      .rpc();
+
+    const exchangeAccount = await program.account.voucherExchange.fetch(
+      exchangePDA
+    );
+    assert.equal(exchangeAccount.feeBurnBasisPoints, 3000);
+    assert.equal(exchangeAccount.totalBurned.toNumber(), 0);
+  });
+
+  it("Hand Over and Return Exchange Authority", async () => {
+    // Propose the buyer as authority; nothing changes until they accept
+    await program.methods
+      .proposeAuthority(buyer.publicKey)
+      .accounts({
+        authority: admin.publicKey,
+        exchange: exchangePDA,
+      })
+      .signers([admin])
+      .rpc();
+
+    let exchangeAccount = await program.account.voucherExchange.fetch(
+      exchangePDA
+    );
+    assert.equal(exchangeAccount.authority.toString(), admin.publicKey.toString());
+    assert.equal(
+      exchangeAccount.pendingAuthority.toString(),
+      buyer.publicKey.toString()
+    );
+
+    // Only the proposed authority can accept
+    try {
+      await program.methods
+        .acceptAuthority()
+        .accounts({
+          newAuthority: bidder.publicKey,
+          exchange: exchangePDA,
+        })
+        .signers([bidder])
+        .rpc();
+      assert.fail("Only the pending authority should be able to accept");
+    } catch (e) {
+      assert.include(e.toString(), "NotPendingAuthority");
+    }
+
+    await program.methods
+      .acceptAuthority()
+      .accounts({
+        newAuthority: buyer.publicKey,
+        exchange: exchangePDA,
+      })
+      .signers([buyer])
+      .rpc();
+
+    exchangeAccount = await program.account.voucherExchange.fetch(exchangePDA);
+    assert.equal(exchangeAccount.authority.toString(), buyer.publicKey.toString());
+    assert.equal(
+      exchangeAccount.pendingAuthority.toString(),
+      PublicKey.default.toString()
+    );
+
+    // Hand the exchange back to the admin for the remaining tests
+    await program.methods
+      .proposeAuthority(admin.publicKey)
+      .accounts({
+        authority: buyer.publicKey,
+        exchange: exchangePDA,
+      })
+      .signers([buyer])
+      .rpc();
+
+    await program.methods
+      .acceptAuthority()
+      .accounts({
+        newAuthority: admin.publicKey,
+        exchange: exchangePDA,
+      })
+      .signers([admin])
+      .rpc();
+
+    exchangeAccount = await program.account.voucherExchange.fetch(exchangePDA);
+    assert.equal(exchangeAccount.authority.toString(), admin.publicKey.toString());
+  });
+
+  it("Grant and Revoke Refund Operator Role", async () => {
+    const refundOperator = Keypair.generate();
+
+    await program.methods
+      .setRole({ refundOperator: {} }, refundOperator.publicKey)
+      .accounts({
+        authority: admin.publicKey,
+        exchange: exchangePDA,
+      })
+      .signers([admin])
+      .rpc();
+
+    let exchangeAccount = await program.account.voucherExchange.fetch(
+      exchangePDA
+    );
+    assert.equal(
+      exchangeAccount.refundOperator.toString(),
+      refundOperator.publicKey.toString()
+    );
+
+    // The refund operator cannot touch the fee schedule
+    try {
+      await program.methods
+        .setFeeBurnRate(0)
+        .accounts({
+          authority: refundOperator.publicKey,
+          exchange: exchangePDA,
+        })
+        .signers([refundOperator])
+        .rpc();
+      assert.fail("Refund operator should not be able to change fees");
+    } catch (e) {
+      assert.include(e.toString(), "MissingRole");
+    }
+
+    await program.methods
+      .revokeRole({ refundOperator: {} })
+      .accounts({
+        authority: admin.publicKey,
+        exchange: exchangePDA,
+      })
+      .signers([admin])
+      .rpc();
+
+    exchangeAccount = await program.account.voucherExchange.fetch(exchangePDA);
+    assert.equal(
+      exchangeAccount.refundOperator.toString(),
+      PublicKey.default.toString()
+    );
+  });
+
+  it("Pause and Resume Trading", async () => {
+    const PAUSE_CREATE_LISTING = 1 << 0;
+    const PAUSE_ACCEPT_BID = 1 << 3;
+    const PAUSE_ALL = (1 << 5) - 1;
+
+    // Mint a new voucher of the collection and list it before pausing, to cancel it while paused
+    const nftMintP = await voucherCollection.mintVoucher(nftOwner.publicKey);
+    const nftOwnerAccountP = getAssociatedTokenAddressSync(
+      nftMintP,
+      nftOwner.publicKey
     );
 
-    // Updated PDA derivation
-    [listingPDA, listingBump] = await PublicKey.findProgramAddress(
+    const [listingPDAP] = await PublicKey.findProgramAddress(
       [
         Buffer.from("voucher_listing"),
+        exchangePDA.toBuffer(),
         nftOwner.publicKey.toBuffer(),
-        nftMint.toBuffer(),
+        nftMintP.toBuffer(),
       ],
       program.programId
     );
 
-    [bidPDA, bidBump] = await PublicKey.findProgramAddress(
-      [
-        Buffer.from("voucher_bid"),
-        bidder.publicKey.toBuffer(),
-        nftMint.toBuffer(),
-      ],
+    const [escrowNftPDAP] = await PublicKey.findProgramAddress(
+      [Buffer.from("escrow"), exchangePDA.toBuffer(), nftMintP.toBuffer()],
       program.programId
     );
 
-    [escrowNftPDA, escrowNftBump] = await PublicKey.findProgramAddress(
-      [Buffer.from("escrow"), nftMint.toBuffer()],
-      program.programId
+    const listVoucher = (
+      mint: PublicKey,
+      listing: PublicKey,
+      ownerNftAccount: PublicKey,
+      escrowNftAccount: PublicKey
+    ) =>
+      program.methods
+        .createVoucherListing(listingPrice, [], null, null, null, null)
+        .accounts({
+          ownerBlockedWallet: findBlockedWalletPDA(nftOwner.publicKey),
+          listing: listing,
+          exchange: exchangePDA,
+          owner: nftOwner.publicKey,
+          nftMint: mint,
+          nftMetadata: findMetadataPDA(mint),
+          collectionConfig: voucherCollectionConfigPDA,
+          approvedCollection: approvedCollectionPDA,
+          ownerNftAccount: ownerNftAccount,
+          escrowNftAccount: escrowNftAccount,
+          paymentMint: paymentMint,
+          paymentMintConfig: paymentMintConfigPDA,
+          tokenProgram: nftTokenProgramId,
+          tokenMetadataProgram: TOKEN_METADATA_PROGRAM_ID,
+          systemProgram: SystemProgram.programId,
+          rent: SYSVAR_RENT_PUBKEY,
+        })
+        .signers([nftOwner])
+        .rpc();
+
+    await listVoucher(nftMintP, listingPDAP, nftOwnerAccountP, escrowNftPDAP);
+
+    await program.methods
+      .setPausedOperations(PAUSE_CREATE_LISTING | PAUSE_ACCEPT_BID)
+      .accounts({
+        authority: admin.publicKey,
+        exchange: exchangePDA,
+      })
+      .signers([admin])
+      .rpc();
+
+    let exchangeAccount = await program.account.voucherExchange.fetch(
+      exchangePDA
+    );
+    assert.equal(
+      exchangeAccount.pausedOperations,
+      PAUSE_CREATE_LISTING | PAUSE_ACCEPT_BID
     );
 
-    [escrowBidPDA, escrowBidBump] = await PublicKey.findProgramAddress(
-      [Buffer.from("escrow"), bidder.publicKey.toBuffer(), nftMint.toBuffer()],
+    // Paused operations are rejected
+    try {
+      await listVoucher(nftMint, listingPDA, nftOwnerAccount, escrowNftPDA);
+      assert.fail("Listing creation should be paused");
+    } catch (e) {
+      assert.include(e.toString(), "ExchangePaused");
+    }
+
+    // Unknown flags are rejected
+    try {
+      await program.methods
+        .setPausedOperations(1 << 7)
+        .accounts({
+          authority: admin.publicKey,
+          exchange: exchangePDA,
+        })
+        .signers([admin])
+        .rpc();
+      assert.fail("Unknown pause flags should be rejected");
+    } catch (e) {
+      assert.include(e.toString(), "InvalidPauseFlags");
+    }
+
+    // Even with everything paused, owners can still take their vouchers back
+    await program.methods
+      .setPausedOperations(PAUSE_ALL)
+      .accounts({
+        authority: admin.publicKey,
+        exchange: exchangePDA,
+      })
+      .signers([admin])
+      .rpc();
+
+    await program.methods
+      .cancelVoucherListing()
+      .accounts({
+        listing: listingPDAP,
+        owner: nftOwner.publicKey,
+        nftMint: nftMintP,
+        ownerNftAccount: nftOwnerAccountP,
+        escrowNftAccount: escrowNftPDAP,
+        exchange: exchangePDA,
+        tokenProgram: nftTokenProgramId,
+        systemProgram: SystemProgram.programId,
+      })
+      .signers([nftOwner])
+      .rpc();
+
+    assert.isNull(await provider.connection.getAccountInfo(listingPDAP));
+    const ownerNftP = await getAccount(provider.connection, nftOwnerAccountP);
+    assert.equal(ownerNftP.amount.toString(), "1");
+
+    // Resume everything for the remaining tests
+    await program.methods
+      .setPausedOperations(0)
+      .accounts({
+        authority: admin.publicKey,
+        exchange: exchangePDA,
+      })
+      .signers([admin])
+      .rpc();
+
+    exchangeAccount = await program.account.voucherExchange.fetch(exchangePDA);
+    assert.equal(exchangeAccount.pausedOperations, 0);
+  });
+
+  it("Block and Unblock a Wallet", async () => {
+    const suspect = Keypair.generate().publicKey;
+    const blockedWalletPDA = findBlockedWalletPDA(suspect);
+
+    const blockWallet = (wallet: PublicKey) =>
+      program.methods
+        .blockWallet(wallet)
+        .accounts({
+          blockedWallet: findBlockedWalletPDA(wallet),
+          authority: admin.publicKey,
+          exchange: exchangePDA,
+          systemProgram: SystemProgram.programId,
+        })
+        .signers([admin])
+        .rpc();
+
+    const unblockWallet = (wallet: PublicKey) =>
+      program.methods
+        .unblockWallet()
+        .accounts({
+          blockedWallet: findBlockedWalletPDA(wallet),
+          authority: admin.publicKey,
+          exchange: exchangePDA,
+        })
+        .signers([admin])
+        .rpc();
+
+    await blockWallet(suspect);
+
+    const blocked = await program.account.blockedWallet.fetch(blockedWalletPDA);
+    assert.equal(blocked.wallet.toString(), suspect.toString());
+    assert.isAbove(blocked.blockedAt.toNumber(), 0);
+
+    await unblockWallet(suspect);
+
+    assert.isNull(await provider.connection.getAccountInfo(blockedWalletPDA));
+
+    // Mint a new voucher of the collection and list it before the owner is blocked
+    const nftMintB = await voucherCollection.mintVoucher(nftOwner.publicKey);
+    const nftOwnerAccountB = getAssociatedTokenAddressSync(
+      nftMintB,
+      nftOwner.publicKey
+    );
+
+    const [listingPDAB] = await PublicKey.findProgramAddress(
+      [
+        Buffer.from("voucher_listing"),
+        exchangePDA.toBuffer(),
+        nftOwner.publicKey.toBuffer(),
+        nftMintB.toBuffer(),
+      ],
       program.programId
     );
 
-    [nftStatePDA, nftStateBump] = await PublicKey.findProgramAddress(
-      [Buffer.from("voucher_state"), nftMint.toBuffer()],
+    const [escrowNftPDAB] = await PublicKey.findProgramAddress(
+      [Buffer.from("escrow"), exchangePDA.toBuffer(), nftMintB.toBuffer()],
       program.programId
     );
-  });
 
-  it("Initialize Exchange", async () => {
-    // Initialize the voucher exchange
-    const tx = await program.methods
-      .initializeExchange()
+    const listVoucher = (
+      mint: PublicKey,
+      listing: PublicKey,
+      ownerNftAccount: PublicKey,
+      escrowNftAccount: PublicKey
+    ) =>
+      program.methods
+        .createVoucherListing(listingPrice, [], null, null, null, null)
+        .accounts({
+          ownerBlockedWallet: findBlockedWalletPDA(nftOwner.publicKey),
+          listing: listing,
+          exchange: exchangePDA,
+          owner: nftOwner.publicKey,
+          nftMint: mint,
+          nftMetadata: findMetadataPDA(mint),
+          collectionConfig: voucherCollectionConfigPDA,
+          approvedCollection: approvedCollectionPDA,
+          ownerNftAccount: ownerNftAccount,
+          escrowNftAccount: escrowNftAccount,
+          paymentMint: paymentMint,
+          paymentMintConfig: paymentMintConfigPDA,
+          tokenProgram: nftTokenProgramId,
+          tokenMetadataProgram: TOKEN_METADATA_PROGRAM_ID,
+          systemProgram: SystemProgram.programId,
+          rent: SYSVAR_RENT_PUBKEY,
+        })
+        .signers([nftOwner])
+        .rpc();
+
+    await listVoucher(nftMintB, listingPDAB, nftOwnerAccountB, escrowNftPDAB);
+    await blockWallet(nftOwner.publicKey);
+
+    // A blocked wallet cannot list
+    try {
+      await listVoucher(nftMint, listingPDA, nftOwnerAccount, escrowNftPDA);
+      assert.fail("Blocked wallet should not be able to list");
+    } catch (e) {
+      assert.include(e.toString(), "WalletBlocked");
+    }
+
+    // But it can still cancel its own listing and take the voucher back
+    await program.methods
+      .cancelVoucherListing()
       .accounts({
+        listing: listingPDAB,
+        owner: nftOwner.publicKey,
+        nftMint: nftMintB,
+        ownerNftAccount: nftOwnerAccountB,
+        escrowNftAccount: escrowNftPDAB,
         exchange: exchangePDA,
-        authority: admin.publicKey,
-        feeAccount: adminPaymentAccount,
-        tokenProgram: paymentTokenProgramId,
+        tokenProgram: nftTokenProgramId,
         systemProgram: SystemProgram.programId,
-        rent: SYSVAR_RENT_PUBKEY,
       })
-      .signers([admin])
+      .signers([nftOwner])
       .rpc();
 
-    console.log("Initialize transaction:", tx);
+    assert.isNull(await provider.connection.getAccountInfo(listingPDAB));
+    const ownerNftB = await getAccount(provider.connection, nftOwnerAccountB);
+    assert.equal(ownerNftB.amount.toString(), "1");
 
-    // Verify exchange was initialized correctly
-    const exchangeAccount = await program.account.voucherExchange.fetch(
-      exchangePDA
-    );
-    assert.equal(
-      exchangeAccount.authority.toString(),
-      admin.publicKey.toString()
-    );
-    assert.equal(exchangeAccount.totalListings.toNumber(), 0);
-    assert.equal(exchangeAccount.totalBids.toNumber(), 0);
+    // Unblock the owner for the remaining tests
+    await unblockWallet(nftOwner.publicKey);
   });
 
   it("Create Voucher Listing", async () => {
     // Create a voucher listing
     const tx = await program.methods
-      .createVoucherListing(listingPrice)
+      .createVoucherListing(listingPrice, [], null, null, null, null)
       .accounts({
+        ownerBlockedWallet: findBlockedWalletPDA(nftOwner.publicKey),
         listing: listingPDA,
         exchange: exchangePDA,
         owner: nftOwner.publicKey,
         nftMint: nftMint,
+        nftMetadata: findMetadataPDA(nftMint),
+        collectionConfig: voucherCollectionConfigPDA,
+        approvedCollection: approvedCollectionPDA,
         ownerNftAccount: nftOwnerAccount,
         escrowNftAccount: escrowNftPDA,
         paymentMint: paymentMint,
+        paymentMintConfig: paymentMintConfigPDA,
         tokenProgram: nftTokenProgramId,
+        tokenMetadataProgram: TOKEN_METADATA_PROGRAM_ID,
         systemProgram: SystemProgram.programId,
         rent: SYSVAR_RENT_PUBKEY,
       })
@@ -281,6 +1268,7 @@ describe("voucher_exchange", () => {
     assert.equal(listingAccount.paymentMint.toString(), paymentMint.toString());
     assert.equal(listingAccount.active, true);
     assert.equal(listingAccount.bump, listingBump);
+    assert.equal(listingAccount.version, 1);
     // No more escrow_bump assertion
 
     // Verify exchange counter was incremented
@@ -295,15 +1283,21 @@ describe("voucher_exchange", () => {
     const tx = await program.methods
       .createVoucherBid(bidPrice) // Removed escrowBump parameter
       .accounts({
+        bidderBlockedWallet: findBlockedWalletPDA(bidder.publicKey),
         bid: bidPDA,
         exchange: exchangePDA,
         bidder: bidder.publicKey,
         nftMint: nftMint,
+        nftMetadata: findMetadataPDA(nftMint),
+        collectionConfig: voucherCollectionConfigPDA,
+        approvedCollection: approvedCollectionPDA,
         nftState: null, // Optional, may not exist yet
         paymentMint: paymentMint,
+        paymentMintConfig: paymentMintConfigPDA,
         bidderTokenAccount: bidderPaymentAccount,
         escrowAccount: escrowBidPDA,
         tokenProgram: paymentTokenProgramId,
+        tokenMetadataProgram: TOKEN_METADATA_PROGRAM_ID,
         systemProgram: SystemProgram.programId,
         rent: SYSVAR_RENT_PUBKEY,
       })
@@ -321,10 +1315,18 @@ describe("voucher_exchange", () => {
     assert.equal(bidAccount.escrowAccount.toString(), escrowBidPDA.toString());
     assert.equal(bidAccount.active, true);
     assert.equal(bidAccount.requiresRefund, false);
+    assert.equal(bidAccount.version, 1);
+
+    // Bidder is the maker and escrows the maker fee with the bid
+    const feeDeposit = bidPrice.muln(makerFeeBasisPoints).divn(10000);
+    assert.equal(bidAccount.feeDeposit.toString(), feeDeposit.toString());
 
     // Verify funds were moved to escrow
     const escrowInfo = await getAccount(provider.connection, escrowBidPDA);
-    assert.equal(escrowInfo.amount.toString(), bidPrice.toString());
+    assert.equal(
+      escrowInfo.amount.toString(),
+      bidPrice.add(feeDeposit).toString()
+    );
 
     // Verify exchange counter was incremented
     const exchangeAccount = await program.account.voucherExchange.fetch(
@@ -333,24 +1335,188 @@ describe("voucher_exchange", () => {
     assert.equal(exchangeAccount.totalBids.toNumber(), 1);
   });
 
+  it("Reject Migrating Accounts Already at the Current Version", async () => {
+    const migrations = [
+      program.methods
+        .migrateExchange()
+        .accounts({ exchange: exchangePDA, payer: admin.publicKey }),
+      program.methods
+        .migrateVoucherListing()
+        .accounts({ listing: listingPDA, payer: admin.publicKey }),
+      program.methods
+        .migrateVoucherBid()
+        .accounts({ bid: bidPDA, payer: admin.publicKey }),
+    ];
+
+    for (const migration of migrations) {
+      try {
+        await migration.signers([admin]).rpc();
+        assert.fail("Current accounts should not be migrated again");
+      } catch (e) {
+        assert.include(e.toString(), "AccountAlreadyMigrated");
+      }
+    }
+
+    // A listing passed as a bid is rejected before being resized
+    try {
+      await program.methods
+        .migrateVoucherBid()
+        .accounts({ bid: listingPDA, payer: admin.publicKey })
+        .signers([admin])
+        .rpc();
+      assert.fail("Accounts of another type should be rejected");
+    } catch (e) {
+      assert.include(e.toString(), "AccountDiscriminatorMismatch");
+    }
+  });
+
+  it("Announce, Time Lock and Cancel a Listing Rescue", async () => {
+    const recovery = Keypair.generate().publicKey;
+    const recoveryAddressPDA = findRecoveryAddressPDA(nftOwner.publicKey);
+    const rescueRequestPDA = findRescueRequestPDA(listingPDA);
+
+    await program.methods
+      .setRecoveryAddress(recovery)
+      .accounts({
+        recoveryAddress: recoveryAddressPDA,
+        wallet: nftOwner.publicKey,
+        exchange: exchangePDA,
+        systemProgram: SystemProgram.programId,
+      })
+      .signers([nftOwner])
+      .rpc();
+
+    // The rescue goes to the recovery address designated by the owner
+    await program.methods
+      .announceListingRescue()
+      .accounts({
+        rescueRequest: rescueRequestPDA,
+        listing: listingPDA,
+        recoveryAddress: recoveryAddressPDA,
+        authority: admin.publicKey,
+        exchange: exchangePDA,
+        systemProgram: SystemProgram.programId,
+      })
+      .signers([admin])
+      .rpc();
+
+    let rescueRequest = await program.account.rescueRequest.fetch(
+      rescueRequestPDA
+    );
+    assert.equal(rescueRequest.target.toString(), listingPDA.toString());
+    assert.equal(rescueRequest.destination.toString(), recovery.toString());
+    const rescuedListing = await program.account.voucherListing.fetch(listingPDA);
+    assert.equal(
+      rescueRequest.targetNonce.toString(),
+      rescuedListing.nonce.toString()
+    );
+    assert.equal(
+      rescueRequest.executableAt.toNumber() -
+        rescueRequest.announcedAt.toNumber(),
+      30 * 24 * 60 * 60
+    );
+
+    const cancelRescue = () =>
+      program.methods
+        .cancelRescue()
+        .accounts({
+          rescueRequest: rescueRequestPDA,
+          authority: admin.publicKey,
+          exchange: exchangePDA,
+        })
+        .signers([admin])
+        .rpc();
+
+    await cancelRescue();
+
+    await program.methods
+      .removeRecoveryAddress()
+      .accounts({
+        recoveryAddress: recoveryAddressPDA,
+        wallet: nftOwner.publicKey,
+        exchange: exchangePDA,
+      })
+      .signers([nftOwner])
+      .rpc();
+
+    assert.isNull(await provider.connection.getAccountInfo(recoveryAddressPDA));
+
+    // Without a recovery address the rescue goes back to the owner
+    await program.methods
+      .announceListingRescue()
+      .accounts({
+        rescueRequest: rescueRequestPDA,
+        listing: listingPDA,
+        recoveryAddress: recoveryAddressPDA,
+        authority: admin.publicKey,
+        exchange: exchangePDA,
+        systemProgram: SystemProgram.programId,
+      })
+      .signers([admin])
+      .rpc();
+
+    rescueRequest = await program.account.rescueRequest.fetch(rescueRequestPDA);
+    assert.equal(
+      rescueRequest.destination.toString(),
+      nftOwner.publicKey.toString()
+    );
+
+    // Nothing moves before the timelock has passed
+    try {
+      await program.methods
+        .executeListingRescue()
+        .accounts({
+          rescueRequest: rescueRequestPDA,
+          listing: listingPDA,
+          destination: nftOwner.publicKey,
+          destinationNftAccount: nftOwnerAccount,
+          nftMint: nftMint,
+          escrowNftAccount: escrowNftPDA,
+          authority: admin.publicKey,
+          exchange: exchangePDA,
+          tokenProgram: nftTokenProgramId,
+        })
+        .signers([admin])
+        .rpc();
+      assert.fail("Rescue should wait for the timelock");
+    } catch (e) {
+      assert.include(e.toString(), "RescueTimelockActive");
+    }
+
+    await cancelRescue();
+    assert.isNull(await provider.connection.getAccountInfo(rescueRequestPDA));
+  });
+
   it("Accept Voucher Bid", async () => {
     // Accept the bid
     const tx = await program.methods
       .acceptVoucherBid()
       .accounts({
+        ownerBlockedWallet: findBlockedWalletPDA(nftOwner.publicKey),
+        bidderBlockedWallet: findBlockedWalletPDA(bidder.publicKey),
         bid: bidPDA,
         owner: nftOwner.publicKey,
         bidder: bidder.publicKey,
         nftMint: nftMint,
         nftState: nftStatePDA,
+        nftMetadata: findMetadataPDA(nftMint),
+        collectionConfig: voucherCollectionConfigPDA,
         escrowNftAccount: escrowNftPDA, // Updated to use escrow account
         listing: listingPDA, // Added listing account
         bidderNftAccount: bidderNftAccount,
         paymentMint: paymentMint,
         escrowPaymentAccount: escrowBidPDA, // Renamed to clarify
+        bidderPaymentAccount: bidderPaymentAccount, // Receives any unused fee deposit
         ownerPaymentAccount: nftOwnerPaymentAccount,
+        feeVault: feeVaultPDA,
+        feeVaultTokenAccount: feeVaultTokenAccount,
+        referrer: null, // No referrer for this sale
+        referrerPaymentAccount: null,
+        feeDiscountConfig: null, // No OPT holder discount for this sale
+        bidderOptAccount: null,
         exchange: exchangePDA, // Added exchange to update stats
         tokenProgram: nftTokenProgramId,
+        tokenMetadataProgram: TOKEN_METADATA_PROGRAM_ID,
         associatedTokenProgram: ASSOCIATED_TOKEN_PROGRAM_ID,
         systemProgram: SystemProgram.programId,
         // Removed rent and clock - optimized stack usage
@@ -360,8 +1526,11 @@ describe("voucher_exchange", () => {
 
     console.log("Accept bid transaction:", tx);
 
-    // In the updated contract, all payment goes to seller
-    const sellerAmount = bidPrice;
+    // Bidder pays the maker fee from the deposit, seller pays the taker fee
+    const makerFee = bidPrice.muln(makerFeeBasisPoints).divn(10000);
+    const takerFee = bidPrice.muln(takerFeeBasisPoints).divn(10000);
+    const fee = makerFee.add(takerFee);
+    const sellerAmount = bidPrice.sub(takerFee);
 
     // Verify NFT was transferred to bidder
     const bidderNftAccountInfo = await getAccount(
@@ -380,6 +1549,17 @@ describe("voucher_exchange", () => {
       sellerAmount.toString()
     );
 
+    // Verify the fee was collected in the fee vault
+    const feeVaultTokenInfo = await getAccount(
+      provider.connection,
+      feeVaultTokenAccount
+    );
+    assert.equal(feeVaultTokenInfo.amount.toString(), fee.toString());
+
+    const feeVaultAccount = await program.account.feeVault.fetch(feeVaultPDA);
+    assert.equal(feeVaultAccount.lifetimeFees.toString(), fee.toString());
+    assert.equal(feeVaultAccount.unclaimedFees.toString(), fee.toString());
+
     // Listing account should be closed after accept bid
     try {
       await program.account.voucherListing.fetch(listingPDA);
@@ -405,19 +1585,11 @@ describe("voucher_exchange", () => {
   });
 
   it("Create Another Listing and Fulfill It", async () => {
-    // Create a new NFT mint for this test
-    const nftMint2 = await createMint(
-      provider.connection,
-      nftOwner,
-      nftOwner.publicKey,
-      null,
-      0
-    );
+    // Mint a new voucher of the collection for this test
+    const nftMint2 = await voucherCollection.mintVoucher(nftOwner.publicKey);
 
     // Create token accounts for the new NFT
-    const nftOwnerAccount2 = await createAssociatedTokenAccount(
-      provider.connection,
-      nftOwner,
+    const nftOwnerAccount2 = getAssociatedTokenAddressSync(
       nftMint2,
       nftOwner.publicKey
     );
@@ -429,16 +1601,6 @@ describe("voucher_exchange", () => {
       buyer.publicKey
     );
 
-    // Mint NFT to owner
-    await mintTo(
-      provider.connection,
-      nftOwner,
-      nftMint2,
-      nftOwnerAccount2,
-      nftOwner.publicKey,
-      1
-    );
-
     // Create a new NFT state PDA
     const [nftStatePDA2] = await PublicKey.findProgramAddress(
       [Buffer.from("voucher_state"), nftMint2.toBuffer()],
@@ -451,6 +1613,7 @@ describe("voucher_exchange", () => {
     const [listingPDA2] = await PublicKey.findProgramAddress(
       [
         Buffer.from("voucher_listing"),
+        exchangePDA.toBuffer(),
         nftOwner.publicKey.toBuffer(),
         nftMint2.toBuffer(),
       ],
@@ -459,22 +1622,28 @@ describe("voucher_exchange", () => {
 
     // Create escrow NFT account PDA
     const [escrowNftPDA2] = await PublicKey.findProgramAddress(
-      [Buffer.from("escrow"), nftMint2.toBuffer()],
+      [Buffer.from("escrow"), exchangePDA.toBuffer(), nftMint2.toBuffer()],
       program.programId
     );
 
-    // Create the second listing
+    // Create the second listing at a higher price, lowered below before the sale
     const createTx = await program.methods
-      .createVoucherListing(listingPrice2)
+      .createVoucherListing(listingPrice2.muln(2), [], null, null, null, null)
       .accounts({
+        ownerBlockedWallet: findBlockedWalletPDA(nftOwner.publicKey),
         listing: listingPDA2,
         exchange: exchangePDA,
         owner: nftOwner.publicKey,
         nftMint: nftMint2,
+        nftMetadata: findMetadataPDA(nftMint2),
+        collectionConfig: voucherCollectionConfigPDA,
+        approvedCollection: approvedCollectionPDA,
         ownerNftAccount: nftOwnerAccount2,
         escrowNftAccount: escrowNftPDA2,
         paymentMint: paymentMint,
+        paymentMintConfig: paymentMintConfigPDA,
         tokenProgram: nftTokenProgramId,
+        tokenMetadataProgram: TOKEN_METADATA_PROGRAM_ID,
         systemProgram: SystemProgram.programId,
         rent: SYSVAR_RENT_PUBKEY,
       })
@@ -483,34 +1652,89 @@ describe("voucher_exchange", () => {
 
     console.log("Create second listing transaction:", createTx);
 
-    // Buyer fulfills the listing
-    const fulfillTx = await program.methods
-      .fulfillVoucherListing()
+    // Reprice in place and add an expiry, the NFT stays in escrow
+    const expiresAt2 = new BN(Math.floor(Date.now() / 1000) + 3600);
+    await program.methods
+      .updateVoucherListing(listingPrice2, expiresAt2)
       .accounts({
         listing: listingPDA2,
-        buyer: buyer.publicKey,
+        exchange: exchangePDA,
         owner: nftOwner.publicKey,
+        ownerBlockedWallet: findBlockedWalletPDA(nftOwner.publicKey),
         nftMint: nftMint2,
-        nftState: nftStatePDA2,
-        escrowNftAccount: escrowNftPDA2, // Updated from nft_account to escrow_nft_account
-        buyerNftAccount: buyerNftAccount2,
+        nftMetadata: findMetadataPDA(nftMint2),
+        collectionConfig: voucherCollectionConfigPDA,
         paymentMint: paymentMint,
-        buyerPaymentAccount: buyerPaymentAccount,
-        ownerPaymentAccount: nftOwnerPaymentAccount,
-        exchange: exchangePDA, // Added exchange to update stats
-        tokenProgram: paymentTokenProgramId,
-        associatedTokenProgram: ASSOCIATED_TOKEN_PROGRAM_ID,
-        systemProgram: SystemProgram.programId,
-        rent: SYSVAR_RENT_PUBKEY,
-        clock: anchor.web3.SYSVAR_CLOCK_PUBKEY,
+        paymentMintConfig: paymentMintConfigPDA,
+        tokenMetadataProgram: TOKEN_METADATA_PROGRAM_ID,
       })
+      .signers([nftOwner])
+      .rpc();
+
+    const updatedListing = await program.account.voucherListing.fetch(
+      listingPDA2
+    );
+    assert.equal(updatedListing.price.toString(), listingPrice2.toString());
+    assert.equal(updatedListing.expiresAt.toString(), expiresAt2.toString());
+    const escrowNft2 = await getAccount(provider.connection, escrowNftPDA2);
+    assert.equal(escrowNft2.amount.toString(), "1");
+
+    const fulfillAccounts2 = {
+      buyerBlockedWallet: findBlockedWalletPDA(buyer.publicKey),
+      ownerBlockedWallet: findBlockedWalletPDA(nftOwner.publicKey),
+      listing: listingPDA2,
+      buyer: buyer.publicKey,
+      owner: nftOwner.publicKey,
+      nftMint: nftMint2,
+      nftState: nftStatePDA2,
+      nftMetadata: findMetadataPDA(nftMint2),
+      collectionConfig: voucherCollectionConfigPDA,
+      escrowNftAccount: escrowNftPDA2, // Updated from nft_account to escrow_nft_account
+      buyerNftAccount: buyerNftAccount2,
+      paymentMint: paymentMint,
+      buyerPaymentAccount: buyerPaymentAccount,
+      ownerPaymentAccount: nftOwnerPaymentAccount,
+      feeVault: feeVaultPDA,
+      feeVaultTokenAccount: feeVaultTokenAccount,
+      referrer: null, // No referrer for this sale
+      referrerPaymentAccount: null,
+      feeDiscountConfig: null, // No OPT holder discount for this sale
+      buyerOptAccount: null,
+      exchange: exchangePDA, // Added exchange to update stats
+      tokenProgram: paymentTokenProgramId,
+      tokenMetadataProgram: TOKEN_METADATA_PROGRAM_ID,
+      associatedTokenProgram: ASSOCIATED_TOKEN_PROGRAM_ID,
+      systemProgram: SystemProgram.programId,
+      rent: SYSVAR_RENT_PUBKEY,
+      clock: anchor.web3.SYSVAR_CLOCK_PUBKEY,
+    };
+
+    // The buyer's maximum price guards against paying more than expected
+    try {
+      await program.methods
+        .fulfillVoucherListing(listingPrice2.subn(1))
+        .accounts(fulfillAccounts2)
+        .signers([buyer])
+        .rpc();
+      assert.fail("Price above the buyer's maximum should be rejected");
+    } catch (e) {
+      assert.include(e.toString(), "PriceAboveMaximum");
+    }
+
+    // Buyer fulfills the listing
+    const fulfillTx = await program.methods
+      .fulfillVoucherListing(listingPrice2)
+      .accounts(fulfillAccounts2)
       .signers([buyer])
       .rpc();
 
     console.log("Fulfill listing transaction:", fulfillTx);
 
-    // The entire listing price should be sent to the seller, no fee in this version
-    const sellerAmount2 = listingPrice2;
+    // Seller (maker) receives the listing price minus the maker fee,
+    // the buyer (taker) pays the taker fee on top
+    const sellerAmount2 = listingPrice2.sub(
+      listingPrice2.muln(makerFeeBasisPoints).divn(10000)
+    );
 
     // Verify NFT was transferred to buyer
     const buyerNftAccountInfo = await getAccount(
@@ -535,9 +1759,130 @@ describe("voucher_exchange", () => {
       `Seller should have received at least ${sellerAmount2.toString()}, but got ${ownerPaymentAccountInfo.amount.toString()}`
     );
 
-    // Verify listing is no longer active - it should be closed now
+    // Verify listing is no longer active - it should be closed now
+    try {
+      await program.account.voucherListing.fetch(listingPDA2);
+      assert.fail("Listing account should be closed");
+    } catch (e) {
+      // Expected behavior - account is closed
+      console.log("Listing account successfully closed");
+    }
+  });
+
+  it("Cancel a Listing", async () => {
+    // Mint a new voucher of the collection for this test
+    const nftMint3 = await voucherCollection.mintVoucher(nftOwner.publicKey);
+
+    const nftOwnerAccount3 = getAssociatedTokenAddressSync(
+      nftMint3,
+      nftOwner.publicKey
+    );
+
+    // Create a new listing to cancel
+    const listingPrice3 = new BN(700_000_000);
+
+    const [listingPDA3] = await PublicKey.findProgramAddress(
+      [
+        Buffer.from("voucher_listing"),
+        exchangePDA.toBuffer(),
+        nftOwner.publicKey.toBuffer(),
+        nftMint3.toBuffer(),
+      ],
+      program.programId
+    );
+
+    // Create the escrow NFT account PDA
+    const [escrowNftPDA3] = await PublicKey.findProgramAddress(
+      [Buffer.from("escrow"), exchangePDA.toBuffer(), nftMint3.toBuffer()],
+      program.programId
+    );
+
+    // List it as a Dutch auction falling to half the price over an hour, in 10 minute steps
+    const now = Math.floor(Date.now() / 1000);
+    const dutchAuction3 = {
+      endPrice: listingPrice3.divn(2),
+      startTime: new BN(now),
+      endTime: new BN(now + 3600),
+      decay: { stepped: { interval: new BN(600) } },
+    };
+
+    const listDutchAuction = (auction: typeof dutchAuction3) =>
+      program.methods
+        .createVoucherListing(listingPrice3, [], null, null, auction, null)
+        .accounts({
+          ownerBlockedWallet: findBlockedWalletPDA(nftOwner.publicKey),
+          listing: listingPDA3,
+          exchange: exchangePDA,
+          owner: nftOwner.publicKey,
+          nftMint: nftMint3,
+          nftMetadata: findMetadataPDA(nftMint3),
+          collectionConfig: voucherCollectionConfigPDA,
+          approvedCollection: approvedCollectionPDA,
+          ownerNftAccount: nftOwnerAccount3,
+          escrowNftAccount: escrowNftPDA3,
+          paymentMint: paymentMint,
+          paymentMintConfig: paymentMintConfigPDA,
+          tokenProgram: nftTokenProgramId,
+          tokenMetadataProgram: TOKEN_METADATA_PROGRAM_ID,
+          systemProgram: SystemProgram.programId,
+          rent: SYSVAR_RENT_PUBKEY,
+        })
+        .signers([nftOwner])
+        .rpc();
+
+    // Schedules that already ended or run longer than a year are rejected
+    const invalidSchedules = [
+      { ...dutchAuction3, startTime: new BN(now - 7200), endTime: new BN(now - 3600) },
+      { ...dutchAuction3, endTime: new BN(now + 366 * 24 * 60 * 60) },
+    ];
+    for (const schedule of invalidSchedules) {
+      try {
+        await listDutchAuction(schedule);
+        assert.fail("Invalid Dutch auction schedule should be rejected");
+      } catch (e) {
+        assert.include(e.toString(), "InvalidDutchAuction");
+      }
+    }
+
+    // Create the listing
+    const createTx = await listDutchAuction(dutchAuction3);
+
+    console.log("Create listing to cancel transaction:", createTx);
+
+    const listingAccount3 = await program.account.voucherListing.fetch(
+      listingPDA3
+    );
+    assert.equal(listingAccount3.price.toString(), listingPrice3.toString());
+    assert.equal(
+      listingAccount3.dutchAuction.endPrice.toString(),
+      dutchAuction3.endPrice.toString()
+    );
+    assert.equal(
+      listingAccount3.dutchAuction.decay.stepped.interval.toNumber(),
+      600
+    );
+
+    // Cancel the listing
+    const cancelTx = await program.methods
+      .cancelVoucherListing()
+      .accounts({
+        listing: listingPDA3,
+        owner: nftOwner.publicKey,
+        nftMint: nftMint3,
+        ownerNftAccount: nftOwnerAccount3,
+        escrowNftAccount: escrowNftPDA3,
+        exchange: exchangePDA, // Added exchange to update stats
+        tokenProgram: nftTokenProgramId,
+        systemProgram: SystemProgram.programId,
+      })
+      .signers([nftOwner])
+      .rpc();
+
+    console.log("Cancel listing transaction:", cancelTx);
+
+    // Verify listing is no longer active - it should be closed
     try {
-      await program.account.voucherListing.fetch(listingPDA2);
+      await program.account.voucherListing.fetch(listingPDA3);
       assert.fail("Listing account should be closed");
     } catch (e) {
       // Expected behavior - account is closed
@@ -545,126 +1890,392 @@ describe("voucher_exchange", () => {
     }
   });
 
-  it("Cancel a Listing", async () => {
-    // Create a new NFT mint for this test
-    const nftMint3 = await createMint(
-      provider.connection,
-      nftOwner,
-      nftOwner.publicKey,
-      null,
-      0
-    );
+  it("Expire a Listing", async () => {
+    // Mint a new voucher of the collection for this test
+    const nftMint6 = await voucherCollection.mintVoucher(nftOwner.publicKey);
 
-    // Create token account for the new NFT
-    const nftOwnerAccount3 = await createAssociatedTokenAccount(
-      provider.connection,
-      nftOwner,
-      nftMint3,
+    const nftOwnerAccount6 = getAssociatedTokenAddressSync(
+      nftMint6,
       nftOwner.publicKey
     );
 
-    // Mint NFT to owner
-    await mintTo(
-      provider.connection,
-      nftOwner,
-      nftMint3,
-      nftOwnerAccount3,
-      nftOwner.publicKey,
-      1
-    );
-
-    // Create a new listing to cancel
-    const listingPrice3 = new BN(700_000_000);
+    // Create a listing that expires in a few seconds
+    const listingPrice6 = new BN(700_000_000);
 
-    const [listingPDA3] = await PublicKey.findProgramAddress(
+    const [listingPDA6] = await PublicKey.findProgramAddress(
       [
         Buffer.from("voucher_listing"),
+        exchangePDA.toBuffer(),
         nftOwner.publicKey.toBuffer(),
-        nftMint3.toBuffer(),
+        nftMint6.toBuffer(),
       ],
       program.programId
     );
 
     // Create the escrow NFT account PDA
-    const [escrowNftPDA3] = await PublicKey.findProgramAddress(
-      [Buffer.from("escrow"), nftMint3.toBuffer()],
+    const [escrowNftPDA6] = await PublicKey.findProgramAddress(
+      [Buffer.from("escrow"), exchangePDA.toBuffer(), nftMint6.toBuffer()],
       program.programId
     );
 
     // Create the listing
-    const createTx = await program.methods
-      .createVoucherListing(listingPrice3)
+    const expiresAt = Math.floor(Date.now() / 1000) + 5;
+    await program.methods
+      .createVoucherListing(listingPrice6, [], new BN(expiresAt), null, null, null)
       .accounts({
-        listing: listingPDA3,
+        ownerBlockedWallet: findBlockedWalletPDA(nftOwner.publicKey),
+        listing: listingPDA6,
         exchange: exchangePDA,
         owner: nftOwner.publicKey,
-        nftMint: nftMint3,
-        ownerNftAccount: nftOwnerAccount3,
-        escrowNftAccount: escrowNftPDA3,
+        nftMint: nftMint6,
+        nftMetadata: findMetadataPDA(nftMint6),
+        collectionConfig: voucherCollectionConfigPDA,
+        approvedCollection: approvedCollectionPDA,
+        ownerNftAccount: nftOwnerAccount6,
+        escrowNftAccount: escrowNftPDA6,
         paymentMint: paymentMint,
+        paymentMintConfig: paymentMintConfigPDA,
         tokenProgram: nftTokenProgramId,
+        tokenMetadataProgram: TOKEN_METADATA_PROGRAM_ID,
         systemProgram: SystemProgram.programId,
         rent: SYSVAR_RENT_PUBKEY,
       })
       .signers([nftOwner])
       .rpc();
 
-    console.log("Create listing to cancel transaction:", createTx);
+    const expireListing = () =>
+      program.methods
+        .expireVoucherListing()
+        .accounts({
+          listing: listingPDA6,
+          owner: nftOwner.publicKey,
+          ownerNftAccount: nftOwnerAccount6,
+          nftMint: nftMint6,
+          escrowNftAccount: escrowNftPDA6,
+          cranker: buyer.publicKey,
+          exchange: exchangePDA,
+          tokenProgram: nftTokenProgramId,
+        })
+        .signers([buyer])
+        .rpc();
+
+    // Live listings cannot be expired
+    try {
+      await expireListing();
+      assert.fail("Listing should not expire early");
+    } catch (e) {
+      assert.include(e.toString(), "ListingNotExpired");
+    }
 
-    // Cancel the listing
-    const cancelTx = await program.methods
-      .cancelVoucherListing()
+    await new Promise((resolve) => setTimeout(resolve, 6000));
+
+    // Anyone can expire the listing and is paid from its rent
+    const crankerBefore = await provider.connection.getBalance(buyer.publicKey);
+    await expireListing();
+    const crankerAfter = await provider.connection.getBalance(buyer.publicKey);
+    assert.isAbove(crankerAfter, crankerBefore);
+
+    // The NFT is back with its owner and the listing is closed
+    const ownerNft = await getAccount(provider.connection, nftOwnerAccount6);
+    assert.equal(ownerNft.amount.toString(), "1");
+    assert.isNull(await provider.connection.getAccountInfo(listingPDA6));
+    assert.isNull(await provider.connection.getAccountInfo(escrowNftPDA6));
+  });
+
+  it("Run an English Auction", async () => {
+    // Mint a new voucher of the collection for this test
+    const nftMint7 = await voucherCollection.mintVoucher(nftOwner.publicKey);
+
+    // Create token accounts for the new NFT
+    const nftOwnerAccount7 = getAssociatedTokenAddressSync(
+      nftMint7,
+      nftOwner.publicKey
+    );
+
+    const buyerNftAccount7 = await createAssociatedTokenAccount(
+      provider.connection,
+      buyer,
+      nftMint7,
+      buyer.publicKey
+    );
+
+    // Auction with the listing price as reserve, ending in a minute
+    const reservePrice7 = new BN(300_000_000);
+    const minIncrement7 = new BN(50_000_000);
+    const englishAuction7 = {
+      minIncrement: minIncrement7,
+      endTime: new BN(Math.floor(Date.now() / 1000) + 60),
+    };
+
+    const [listingPDA7] = await PublicKey.findProgramAddress(
+      [
+        Buffer.from("voucher_listing"),
+        exchangePDA.toBuffer(),
+        nftOwner.publicKey.toBuffer(),
+        nftMint7.toBuffer(),
+      ],
+      program.programId
+    );
+
+    const [escrowNftPDA7] = await PublicKey.findProgramAddress(
+      [Buffer.from("escrow"), exchangePDA.toBuffer(), nftMint7.toBuffer()],
+      program.programId
+    );
+
+    // Bid escrow of the auction, keyed by the listing
+    const [auctionEscrowPDA7] = await PublicKey.findProgramAddress(
+      [
+        Buffer.from("escrow"),
+        exchangePDA.toBuffer(),
+        listingPDA7.toBuffer(),
+        nftMint7.toBuffer(),
+      ],
+      program.programId
+    );
+
+    await program.methods
+      .createVoucherListing(reservePrice7, [], null, null, null, englishAuction7)
       .accounts({
-        listing: listingPDA3,
+        ownerBlockedWallet: findBlockedWalletPDA(nftOwner.publicKey),
+        listing: listingPDA7,
+        exchange: exchangePDA,
         owner: nftOwner.publicKey,
-        nftMint: nftMint3,
-        ownerNftAccount: nftOwnerAccount3,
-        escrowNftAccount: escrowNftPDA3,
-        exchange: exchangePDA, // Added exchange to update stats
+        nftMint: nftMint7,
+        nftMetadata: findMetadataPDA(nftMint7),
+        collectionConfig: voucherCollectionConfigPDA,
+        approvedCollection: approvedCollectionPDA,
+        ownerNftAccount: nftOwnerAccount7,
+        escrowNftAccount: escrowNftPDA7,
+        paymentMint: paymentMint,
+        paymentMintConfig: paymentMintConfigPDA,
         tokenProgram: nftTokenProgramId,
+        tokenMetadataProgram: TOKEN_METADATA_PROGRAM_ID,
         systemProgram: SystemProgram.programId,
+        rent: SYSVAR_RENT_PUBKEY,
       })
       .signers([nftOwner])
       .rpc();
 
-    console.log("Cancel listing transaction:", cancelTx);
-
-    // Verify listing is no longer active - it should be closed
+    const placeBid = (
+      wallet: Keypair,
+      walletPaymentAccount: PublicKey,
+      amount: BN,
+      previousBidderTokenAccount: PublicKey | null
+    ) =>
+      program.methods
+        .placeAuctionBid(amount)
+        .accounts({
+          listing: listingPDA7,
+          exchange: exchangePDA,
+          bidder: wallet.publicKey,
+          bidderBlockedWallet: findBlockedWalletPDA(wallet.publicKey),
+          nftMint: nftMint7,
+          nftMetadata: findMetadataPDA(nftMint7),
+          collectionConfig: voucherCollectionConfigPDA,
+          paymentMint: paymentMint,
+          bidderTokenAccount: walletPaymentAccount,
+          escrowAccount: auctionEscrowPDA7,
+          previousBidderTokenAccount: previousBidderTokenAccount,
+          tokenProgram: paymentTokenProgramId,
+          tokenMetadataProgram: TOKEN_METADATA_PROGRAM_ID,
+          systemProgram: SystemProgram.programId,
+        })
+        .signers([wallet])
+        .rpc();
+
+    // The first bid must meet the reserve
     try {
-      await program.account.voucherListing.fetch(listingPDA3);
-      assert.fail("Listing account should be closed");
+      await placeBid(bidder, bidderPaymentAccount, reservePrice7.subn(1), null);
+      assert.fail("Bid below the reserve should be rejected");
     } catch (e) {
-      // Expected behavior - account is closed
-      console.log("Listing account successfully closed");
+      assert.include(e.toString(), "BidTooLow");
     }
-  });
 
-  it("Create and Cancel a Bid", async () => {
-    // Create a new NFT mint for this test
-    const nftMint4 = await createMint(
+    const bidderBalanceBefore = await getAccount(
       provider.connection,
-      nftOwner,
-      nftOwner.publicKey,
-      null,
-      0
+      bidderPaymentAccount,
+      undefined,
+      paymentTokenProgramId
     );
+    await placeBid(bidder, bidderPaymentAccount, reservePrice7, null);
+
+    const refundTopBid = (caller: Keypair) =>
+      program.methods
+        .refundAuctionBid()
+        .accounts({
+          listing: listingPDA7,
+          exchange: exchangePDA,
+          authority: caller.publicKey,
+          topBidder: bidder.publicKey,
+          topBidderTokenAccount: bidderPaymentAccount,
+          ownerBlockedWallet: findBlockedWalletPDA(nftOwner.publicKey),
+          topBidderBlockedWallet: findBlockedWalletPDA(bidder.publicKey),
+          nftMint: nftMint7,
+          paymentMint: paymentMint,
+          escrowAccount: auctionEscrowPDA7,
+          tokenProgram: paymentTokenProgramId,
+        })
+        .signers([caller])
+        .rpc();
+
+    // A running auction keeps its top bid, even for the refund operator
+    try {
+      await refundTopBid(admin);
+      assert.fail("Top bid should not be refundable before the auction ends");
+    } catch (e) {
+      assert.include(e.toString(), "AuctionNotEnded");
+    }
+
+    // Raises must beat the top bid by the minimum increment
+    try {
+      await placeBid(
+        buyer,
+        buyerPaymentAccount,
+        reservePrice7.add(minIncrement7).subn(1),
+        bidderPaymentAccount
+      );
+      assert.fail("Bid below the minimum increment should be rejected");
+    } catch (e) {
+      assert.include(e.toString(), "BidTooLow");
+    }
 
-    // Create token account for the new NFT
-    const nftOwnerAccount4 = await createAssociatedTokenAccount(
+    // The outbid bidder must be refunded to their own token account
+    const winningBid7 = reservePrice7.add(minIncrement7);
+    try {
+      await placeBid(buyer, buyerPaymentAccount, winningBid7, buyerPaymentAccount);
+      assert.fail("Refund to another wallet should be rejected");
+    } catch (e) {
+      assert.include(e.toString(), "NotTopBidder");
+    }
+
+    // Outbidding refunds the previous top bid and its fee deposit in full
+    await placeBid(buyer, buyerPaymentAccount, winningBid7, bidderPaymentAccount);
+    const bidderBalanceAfter = await getAccount(
       provider.connection,
-      nftOwner,
-      nftMint4,
-      nftOwner.publicKey
+      bidderPaymentAccount,
+      undefined,
+      paymentTokenProgramId
+    );
+    assert.equal(
+      bidderBalanceAfter.amount.toString(),
+      bidderBalanceBefore.amount.toString()
     );
 
-    // Mint NFT to owner
-    await mintTo(
+    // A bid in the final minutes pushes the end back by the extension window
+    const auctionListing = await program.account.voucherListing.fetch(
+      listingPDA7
+    );
+    assert.equal(
+      auctionListing.englishAuction.topBidder.toString(),
+      buyer.publicKey.toString()
+    );
+    assert.equal(
+      auctionListing.englishAuction.topBid.toString(),
+      winningBid7.toString()
+    );
+    assert.isAbove(
+      auctionListing.englishAuction.endTime.toNumber(),
+      englishAuction7.endTime.toNumber()
+    );
+
+    // The seller cannot pull the voucher once bidding has started
+    try {
+      await program.methods
+        .cancelVoucherListing()
+        .accounts({
+          listing: listingPDA7,
+          exchange: exchangePDA,
+          owner: nftOwner.publicKey,
+          ownerNftAccount: nftOwnerAccount7,
+          nftMint: nftMint7,
+          escrowNftAccount: escrowNftPDA7,
+          tokenProgram: nftTokenProgramId,
+        })
+        .signers([nftOwner])
+        .rpc();
+      assert.fail("Auction with bids should not be cancellable");
+    } catch (e) {
+      assert.include(e.toString(), "AuctionHasBids");
+    }
+
+    const settleAuction = () =>
+      program.methods
+        .settleAuction()
+        .accounts({
+          listing: listingPDA7,
+          owner: nftOwner.publicKey,
+          winner: buyer.publicKey,
+          ownerBlockedWallet: findBlockedWalletPDA(nftOwner.publicKey),
+          winnerBlockedWallet: findBlockedWalletPDA(buyer.publicKey),
+          cranker: bidder.publicKey,
+          nftMint: nftMint7,
+          nftMetadata: findMetadataPDA(nftMint7),
+          collectionConfig: voucherCollectionConfigPDA,
+          escrowNftAccount: escrowNftPDA7,
+          winnerNftAccount: buyerNftAccount7,
+          paymentMint: paymentMint,
+          escrowAccount: auctionEscrowPDA7,
+          winnerPaymentAccount: buyerPaymentAccount,
+          ownerPaymentAccount: nftOwnerPaymentAccount,
+          feeVault: feeVaultPDA,
+          feeVaultTokenAccount: feeVaultTokenAccount,
+          exchange: exchangePDA,
+          tokenProgram: paymentTokenProgramId,
+          tokenMetadataProgram: TOKEN_METADATA_PROGRAM_ID,
+        })
+        .signers([bidder])
+        .rpc();
+
+    try {
+      await settleAuction();
+      assert.fail("Auction should not settle before it ends");
+    } catch (e) {
+      assert.include(e.toString(), "AuctionNotEnded");
+    }
+
+    // Wait out the extended end, then anyone can settle
+    const waitMs =
+      (auctionListing.englishAuction.endTime.toNumber() + 2) * 1000 - Date.now();
+    await new Promise((resolve) => setTimeout(resolve, Math.max(waitMs, 0)));
+
+    const ownerBalanceBefore = await getAccount(
       provider.connection,
-      nftOwner,
+      nftOwnerPaymentAccount,
+      undefined,
+      paymentTokenProgramId
+    );
+    await settleAuction();
+    const ownerBalanceAfter = await getAccount(
+      provider.connection,
+      nftOwnerPaymentAccount,
+      undefined,
+      paymentTokenProgramId
+    );
+
+    // Seller (maker) receives the top bid minus the maker fee
+    const sellerAmount7 = winningBid7.sub(
+      winningBid7.muln(makerFeeBasisPoints).divn(10000)
+    );
+    assert.equal(
+      (ownerBalanceAfter.amount - ownerBalanceBefore.amount).toString(),
+      sellerAmount7.toString()
+    );
+
+    // The winner holds the voucher and the auction accounts are closed
+    const buyerNft7 = await getAccount(provider.connection, buyerNftAccount7);
+    assert.equal(buyerNft7.amount.toString(), "1");
+    assert.isNull(await provider.connection.getAccountInfo(listingPDA7));
+    assert.isNull(await provider.connection.getAccountInfo(escrowNftPDA7));
+    assert.isNull(await provider.connection.getAccountInfo(auctionEscrowPDA7));
+  });
+
+  it("Create and Cancel a Bid", async () => {
+    // Mint a new voucher of the collection for this test
+    const nftMint4 = await voucherCollection.mintVoucher(nftOwner.publicKey);
+
+    const nftOwnerAccount4 = getAssociatedTokenAddressSync(
       nftMint4,
-      nftOwnerAccount4,
-      nftOwner.publicKey,
-      1
+      nftOwner.publicKey
     );
 
     // Create another bid
@@ -673,6 +2284,7 @@ describe("voucher_exchange", () => {
     const [bidPDA2] = await PublicKey.findProgramAddress(
       [
         Buffer.from("voucher_bid"),
+        exchangePDA.toBuffer(),
         bidder.publicKey.toBuffer(),
         nftMint4.toBuffer(),
       ],
@@ -680,7 +2292,7 @@ describe("voucher_exchange", () => {
     );
 
     const [escrowBidPDA2] = await PublicKey.findProgramAddress(
-      [Buffer.from("escrow"), bidder.publicKey.toBuffer(), nftMint4.toBuffer()],
+      [Buffer.from("escrow"), exchangePDA.toBuffer(), bidder.publicKey.toBuffer(), nftMint4.toBuffer()],
       program.programId
     );
 
@@ -688,15 +2300,21 @@ describe("voucher_exchange", () => {
     const createBidTx = await program.methods
       .createVoucherBid(bidPrice2) // Removed escrowBump parameter
       .accounts({
+        bidderBlockedWallet: findBlockedWalletPDA(bidder.publicKey),
         bid: bidPDA2,
         exchange: exchangePDA,
         bidder: bidder.publicKey,
         nftMint: nftMint4,
+        nftMetadata: findMetadataPDA(nftMint4),
+        collectionConfig: voucherCollectionConfigPDA,
+        approvedCollection: approvedCollectionPDA,
         nftState: null, // Use null since this NFT hasn't been sold yet
         paymentMint: paymentMint,
+        paymentMintConfig: paymentMintConfigPDA,
         bidderTokenAccount: bidderPaymentAccount,
         escrowAccount: escrowBidPDA2,
         tokenProgram: paymentTokenProgramId,
+        tokenMetadataProgram: TOKEN_METADATA_PROGRAM_ID,
         systemProgram: SystemProgram.programId,
         rent: SYSVAR_RENT_PUBKEY,
       })
@@ -710,7 +2328,11 @@ describe("voucher_exchange", () => {
       provider.connection,
       escrowBidPDA2
     );
-    assert.equal(escrowInfoBefore.amount.toString(), bidPrice2.toString());
+    const feeDeposit2 = bidPrice2.muln(makerFeeBasisPoints).divn(10000);
+    assert.equal(
+      escrowInfoBefore.amount.toString(),
+      bidPrice2.add(feeDeposit2).toString()
+    );
 
     // Get bidder token balance before cancel
     const bidderAccountBefore = await getAccount(
@@ -752,23 +2374,18 @@ describe("voucher_exchange", () => {
     );
     const bidderBalanceDifference =
       bidderAccountAfter.amount - bidderAccountBefore.amount;
-    assert.equal(bidderBalanceDifference.toString(), bidPrice2.toString());
+    assert.equal(
+      bidderBalanceDifference.toString(),
+      bidPrice2.add(feeDeposit2).toString()
+    );
   });
 
   it("Mark a Bid for Refund", async () => {
-    // Create a new NFT mint for this test
-    const nftMint5 = await createMint(
-      provider.connection,
-      nftOwner,
-      nftOwner.publicKey,
-      null,
-      0
-    );
+    // Mint a new voucher of the collection for this test
+    const nftMint5 = await voucherCollection.mintVoucher(nftOwner.publicKey);
 
     // Create token accounts for the new NFT
-    const nftOwnerAccount5 = await createAssociatedTokenAccount(
-      provider.connection,
-      nftOwner,
+    const nftOwnerAccount5 = getAssociatedTokenAddressSync(
       nftMint5,
       nftOwner.publicKey
     );
@@ -781,16 +2398,6 @@ describe("voucher_exchange", () => {
       buyer.publicKey // This is crucial - must match the buyer in the fulfillVoucherListing call
     );
 
-    // Mint NFT to owner
-    await mintTo(
-      provider.connection,
-      nftOwner,
-      nftMint5,
-      nftOwnerAccount5,
-      nftOwner.publicKey,
-      1
-    );
-
     // Create a new NFT state PDA
     const [nftStatePDA5] = await PublicKey.findProgramAddress(
       [Buffer.from("voucher_state"), nftMint5.toBuffer()],
@@ -799,7 +2406,7 @@ describe("voucher_exchange", () => {
 
     // Create escrow NFT account PDA
     const [escrowNftPDA5] = await PublicKey.findProgramAddress(
-      [Buffer.from("escrow"), nftMint5.toBuffer()],
+      [Buffer.from("escrow"), exchangePDA.toBuffer(), nftMint5.toBuffer()],
       program.programId
     );
 
@@ -809,6 +2416,7 @@ describe("voucher_exchange", () => {
     const [listingPDA5] = await PublicKey.findProgramAddress(
       [
         Buffer.from("voucher_listing"),
+        exchangePDA.toBuffer(),
         nftOwner.publicKey.toBuffer(),
         nftMint5.toBuffer(),
       ],
@@ -817,16 +2425,22 @@ describe("voucher_exchange", () => {
 
     // Create the listing
     await program.methods
-      .createVoucherListing(listingPrice5)
+      .createVoucherListing(listingPrice5, [], null, buyer.publicKey, null, null)
       .accounts({
+        ownerBlockedWallet: findBlockedWalletPDA(nftOwner.publicKey),
         listing: listingPDA5,
         exchange: exchangePDA,
         owner: nftOwner.publicKey,
         nftMint: nftMint5,
+        nftMetadata: findMetadataPDA(nftMint5),
+        collectionConfig: voucherCollectionConfigPDA,
+        approvedCollection: approvedCollectionPDA,
         ownerNftAccount: nftOwnerAccount5,
         escrowNftAccount: escrowNftPDA5,
         paymentMint: paymentMint,
+        paymentMintConfig: paymentMintConfigPDA,
         tokenProgram: nftTokenProgramId,
+        tokenMetadataProgram: TOKEN_METADATA_PROGRAM_ID,
         systemProgram: SystemProgram.programId,
         rent: SYSVAR_RENT_PUBKEY,
       })
@@ -839,6 +2453,7 @@ describe("voucher_exchange", () => {
     const [bidPDA3] = await PublicKey.findProgramAddress(
       [
         Buffer.from("voucher_bid"),
+        exchangePDA.toBuffer(),
         bidder.publicKey.toBuffer(),
         nftMint5.toBuffer(),
       ],
@@ -846,7 +2461,7 @@ describe("voucher_exchange", () => {
     );
 
     const [escrowBidPDA3] = await PublicKey.findProgramAddress(
-      [Buffer.from("escrow"), bidder.publicKey.toBuffer(), nftMint5.toBuffer()],
+      [Buffer.from("escrow"), exchangePDA.toBuffer(), bidder.publicKey.toBuffer(), nftMint5.toBuffer()],
       program.programId
     );
 
@@ -854,15 +2469,21 @@ describe("voucher_exchange", () => {
     const createBidTx = await program.methods
       .createVoucherBid(bidPrice3) // Removed escrowBump parameter
       .accounts({
+        bidderBlockedWallet: findBlockedWalletPDA(bidder.publicKey),
         bid: bidPDA3,
         exchange: exchangePDA,
         bidder: bidder.publicKey,
         nftMint: nftMint5,
+        nftMetadata: findMetadataPDA(nftMint5),
+        collectionConfig: voucherCollectionConfigPDA,
+        approvedCollection: approvedCollectionPDA,
         nftState: null, // Use null since this NFT hasn't been sold yet
         paymentMint: paymentMint,
+        paymentMintConfig: paymentMintConfigPDA,
         bidderTokenAccount: bidderPaymentAccount,
         escrowAccount: escrowBidPDA3,
         tokenProgram: paymentTokenProgramId,
+        tokenMetadataProgram: TOKEN_METADATA_PROGRAM_ID,
         systemProgram: SystemProgram.programId,
         rent: SYSVAR_RENT_PUBKEY,
       })
@@ -871,27 +2492,64 @@ describe("voucher_exchange", () => {
 
     console.log("Create bid for refund test transaction:", createBidTx);
 
+    // The listing is reserved for the buyer, nobody else can fulfill it
+    const listingAccount5 = await program.account.voucherListing.fetch(
+      listingPDA5
+    );
+    assert.equal(
+      listingAccount5.reservedBuyer.toString(),
+      buyer.publicKey.toString()
+    );
+
+    const fulfillAccounts5 = {
+      buyerBlockedWallet: findBlockedWalletPDA(buyer.publicKey),
+      ownerBlockedWallet: findBlockedWalletPDA(nftOwner.publicKey),
+      listing: listingPDA5,
+      buyer: buyer.publicKey,
+      owner: nftOwner.publicKey,
+      nftMint: nftMint5,
+      nftState: nftStatePDA5,
+      nftMetadata: findMetadataPDA(nftMint5),
+      collectionConfig: voucherCollectionConfigPDA,
+      escrowNftAccount: escrowNftPDA5, // Updated name
+      buyerNftAccount: buyerNftAccount5,
+      paymentMint: paymentMint,
+      buyerPaymentAccount: buyerPaymentAccount,
+      ownerPaymentAccount: nftOwnerPaymentAccount,
+      feeVault: feeVaultPDA,
+      feeVaultTokenAccount: feeVaultTokenAccount,
+      referrer: null, // No referrer for this sale
+      referrerPaymentAccount: null,
+      feeDiscountConfig: null, // No OPT holder discount for this sale
+      buyerOptAccount: null,
+      exchange: exchangePDA, // Added exchange to update stats
+      tokenProgram: nftTokenProgramId,
+      tokenMetadataProgram: TOKEN_METADATA_PROGRAM_ID,
+      associatedTokenProgram: ASSOCIATED_TOKEN_PROGRAM_ID,
+      systemProgram: SystemProgram.programId,
+      rent: SYSVAR_RENT_PUBKEY,
+      clock: anchor.web3.SYSVAR_CLOCK_PUBKEY,
+    };
+
+    try {
+      await program.methods
+        .fulfillVoucherListing(listingPrice5)
+        .accounts({
+          ...fulfillAccounts5,
+          buyerBlockedWallet: findBlockedWalletPDA(bidder.publicKey),
+          buyer: bidder.publicKey,
+        })
+        .signers([bidder])
+        .rpc();
+      assert.fail("Only the reserved buyer should fulfill the listing");
+    } catch (e) {
+      assert.include(e.toString(), "NotReservedBuyer");
+    }
+
     // Now fulfill the listing to create the NFT state as "sold"
     await program.methods
-      .fulfillVoucherListing()
-      .accounts({
-        listing: listingPDA5,
-        buyer: buyer.publicKey,
-        owner: nftOwner.publicKey,
-        nftMint: nftMint5,
-        nftState: nftStatePDA5,
-        escrowNftAccount: escrowNftPDA5, // Updated name
-        buyerNftAccount: buyerNftAccount5,
-        paymentMint: paymentMint,
-        buyerPaymentAccount: buyerPaymentAccount,
-        ownerPaymentAccount: nftOwnerPaymentAccount,
-        exchange: exchangePDA, // Added exchange to update stats
-        tokenProgram: nftTokenProgramId,
-        associatedTokenProgram: ASSOCIATED_TOKEN_PROGRAM_ID,
-        systemProgram: SystemProgram.programId,
-        rent: SYSVAR_RENT_PUBKEY,
-        clock: anchor.web3.SYSVAR_CLOCK_PUBKEY,
-      })
+      .fulfillVoucherListing(listingPrice5)
+      .accounts(fulfillAccounts5)
       .signers([buyer])
       .rpc();
 
@@ -943,4 +2601,141 @@ describe("voucher_exchange", () => {
       console.log("Bid account successfully closed");
     }
   });
+
+  it("Withdraw Fees", async () => {
+    const vaultBefore = await getAccount(
+      provider.connection,
+      feeVaultTokenAccount
+    );
+    const treasuryBefore = await getAccount(
+      provider.connection,
+      adminPaymentAccount
+    );
+
+    await program.methods
+      .withdrawFees()
+      .accounts({
+        feeVault: feeVaultPDA,
+        vaultTokenAccount: feeVaultTokenAccount,
+        treasuryTokenAccount: adminPaymentAccount,
+        authority: admin.publicKey,
+        exchange: exchangePDA,
+        paymentMint: paymentMint,
+        tokenProgram: paymentTokenProgramId,
+      })
+      .signers([admin])
+      .rpc();
+
+    const treasuryAfter = await getAccount(
+      provider.connection,
+      adminPaymentAccount
+    );
+    assert.equal(
+      (treasuryAfter.amount - treasuryBefore.amount).toString(),
+      vaultBefore.amount.toString()
+    );
+
+    const feeVaultAccount = await program.account.feeVault.fetch(feeVaultPDA);
+    assert.equal(feeVaultAccount.unclaimedFees.toNumber(), 0);
+    assert.equal(
+      feeVaultAccount.totalWithdrawn.toString(),
+      vaultBefore.amount.toString()
+    );
+  });
+
+  it("Wind Down and Close the Exchange", async () => {
+    const closeExchange = () =>
+      program.methods
+        .closeExchange()
+        .accounts({
+          authority: admin.publicKey,
+          exchange: exchangePDA,
+          closedNamespace: closedNamespacePDA,
+          systemProgram: SystemProgram.programId,
+        })
+        .signers([admin])
+        .rpc();
+
+    // Open exchanges cannot be closed
+    try {
+      await closeExchange();
+      assert.fail("Exchange should wind down before closing");
+    } catch (e) {
+      assert.include(e.toString(), "ExchangeNotWindingDown");
+    }
+
+    await program.methods
+      .beginWindDown()
+      .accounts({
+        authority: admin.publicKey,
+        exchange: exchangePDA,
+      })
+      .signers([admin])
+      .rpc();
+
+    // Every listing and bid was settled, cancelled or refunded above
+    const exchangeAccount = await program.account.voucherExchange.fetch(
+      exchangePDA
+    );
+    assert.equal(exchangeAccount.windingDown, true);
+    assert.equal(exchangeAccount.totalListings.toNumber(), 0);
+    assert.equal(exchangeAccount.totalBids.toNumber(), 0);
+
+    // Fee vaults have to be closed first
+    try {
+      await closeExchange();
+      assert.fail("Exchange with open fee vaults should not close");
+    } catch (e) {
+      assert.include(e.toString(), "FeeVaultsOpen");
+    }
+
+    // The vault was emptied by the withdrawal above
+    await program.methods
+      .closeFeeVault()
+      .accounts({
+        feeVault: feeVaultPDA,
+        vaultTokenAccount: feeVaultTokenAccount,
+        authority: admin.publicKey,
+        exchange: exchangePDA,
+        paymentMint: paymentMint,
+        tokenProgram: paymentTokenProgramId,
+      })
+      .signers([admin])
+      .rpc();
+    assert.isNull(await provider.connection.getAccountInfo(feeVaultPDA));
+
+    await closeExchange();
+    assert.isNull(await provider.connection.getAccountInfo(exchangePDA));
+
+    const closedNamespace = await program.account.closedNamespace.fetch(
+      closedNamespacePDA
+    );
+    assert.equal(closedNamespace.namespace, exchangeNamespace);
+    assert.equal(closedNamespace.exchange.toString(), exchangePDA.toString());
+
+    // The namespace cannot be claimed again
+    try {
+      await program.methods
+        .initializeExchange(
+          exchangeNamespace,
+          makerFeeBasisPoints,
+          takerFeeBasisPoints,
+          admin.publicKey,
+          feeUpdateDelay
+        )
+        .accounts({
+          exchange: exchangePDA,
+          closedNamespace: closedNamespacePDA,
+          authority: admin.publicKey,
+          tokenProgram: paymentTokenProgramId,
+          systemProgram: SystemProgram.programId,
+          rent: SYSVAR_RENT_PUBKEY,
+        })
+        .signers([admin])
+        .rpc();
+      assert.fail("Closed namespace should not be initialized again");
+    } catch (e) {
+      assert.include(e.toString(), "NamespaceClosed");
+    }
+  });
 });